@@ -13,6 +13,7 @@ use mcp2003a::{
         LinBreakDuration, LinBusConfig, LinBusSpeed, LinInterFrameSpace, LinReadDeviceResponseTimeout,
         LinWakeupDuration,
     },
+    frame::MAX_DATA_LEN,
     Mcp2003a, Mcp2003aError,
 };
 
@@ -61,6 +62,7 @@ fn main() {
         wakeup_duration: LinWakeupDuration::Minimum250Microseconds, // Test for your application
         read_device_response_timeout: LinReadDeviceResponseTimeout::DelayMilliseconds(15), // Test for your application
         inter_frame_space: LinInterFrameSpace::DelayMilliseconds(1), // Test for your application
+        ..LinBusConfig::default()
     };
 
     // Initialize the MCP2003A LIN Transceiver
@@ -69,7 +71,10 @@ fn main() {
     log::info!("MCP2003A LIN Transceiver initialized");
 
     // Wakeup the LIN Bus
-    mcp2003a.send_wakeup();
+    match mcp2003a.send_wakeup() {
+        Ok(_) => log::info!("LIN Bus wakeup sent"),
+        Err(e) => log::error!("Error sending LIN Bus wakeup: {:?}", e),
+    }
 
     loop {
         // Send a frame on the LIN bus to a device with Command frame of 0x00:
@@ -93,7 +98,7 @@ fn main() {
         // Read the feedback / diagnostic frame 0x01 from the LIN bus:
         // - LIN Id: 0x01 --> PID: 0xC1
         // - Data: Buffer of 8 bytes will explicitly try to read 8 bytes then a checksum
-        let mut data = [0u8; 8];
+        let mut data = [0u8; MAX_DATA_LEN];
         match mcp2003a.read_frame(0xC1, &mut data) {
             Ok(checksum) => {
                 // Data is stored in the buffer
@@ -0,0 +1,225 @@
+//! Counting the delays and bytes of the core operations, to compare timing configurations on the host.
+//!
+//! Wrap the UART and delay of a mock (or real) driver in a `CountingUart` and a `CountingDelay`,
+//! then call the `bench_` versions of the operations to get what each one did:
+//!
+//! ```rust,ignore
+//! let mut mcp2003a = Mcp2003a::new(CountingUart::new(uart), break_pin, CountingDelay::new(delay));
+//! mcp2003a.init(lin_bus_config);
+//! let (result, counts) = mcp2003a.bench_read_frame(0xC1, &mut buffer);
+//! println!("{} delays for {} ns", counts.delay_calls, counts.delay_ns);
+//! ```
+//!
+//! - Note: Only available with the `bench` feature, which is meant for the host and not for production builds.
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+use embedded_hal_nb::nb;
+use embedded_hal_nb::serial::{ErrorType, Read, Write};
+
+use crate::frame::MAX_FRAME_LEN;
+use crate::{Mcp2003a, Mcp2003aError};
+
+/// What an operation did, counted by the `CountingUart` and `CountingDelay` of the driver.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BenchCounts {
+    /// Number of delays requested, each `delay_ns`, `delay_us`, or `delay_ms` call counting once.
+    pub delay_calls: u32,
+    /// Total time of the delays requested in nanoseconds.
+    pub delay_ns: u64,
+    /// Number of bytes written to the UART.
+    pub bytes_written: u32,
+    /// Number of bytes read from the UART.
+    pub bytes_read: u32,
+}
+
+/// UART wrapper counting the bytes written and read.
+pub struct CountingUart<UART> {
+    inner: UART,
+    bytes_written: u32,
+    bytes_read: u32,
+}
+
+impl<UART> CountingUart<UART> {
+    /// Wrap the given UART.
+    pub fn new(inner: UART) -> Self {
+        CountingUart {
+            inner,
+            bytes_written: 0,
+            bytes_read: 0,
+        }
+    }
+
+    /// Get the wrapped UART, e.g. to queue the bytes a mock returns through `Mcp2003a::bench_uart_mut`.
+    pub fn inner_mut(&mut self) -> &mut UART {
+        &mut self.inner
+    }
+
+    /// Release the wrapped UART.
+    pub fn into_inner(self) -> UART {
+        self.inner
+    }
+}
+
+impl<UART: ErrorType> ErrorType for CountingUart<UART> {
+    type Error = UART::Error;
+}
+
+impl<UART: Read> Read for CountingUart<UART> {
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        let byte = self.inner.read()?;
+        self.bytes_read += 1;
+        Ok(byte)
+    }
+}
+
+impl<UART: Write> Write for CountingUart<UART> {
+    fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+        self.inner.write(word)?;
+        self.bytes_written += 1;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        self.inner.flush()
+    }
+}
+
+/// Delay wrapper counting the delays requested and their total time.
+pub struct CountingDelay<DELAY> {
+    inner: DELAY,
+    delay_calls: u32,
+    delay_ns: u64,
+}
+
+impl<DELAY> CountingDelay<DELAY> {
+    /// Wrap the given delay.
+    pub fn new(inner: DELAY) -> Self {
+        CountingDelay {
+            inner,
+            delay_calls: 0,
+            delay_ns: 0,
+        }
+    }
+
+    /// Release the wrapped delay.
+    pub fn into_inner(self) -> DELAY {
+        self.inner
+    }
+
+    fn count(&mut self, ns: u64) {
+        self.delay_calls += 1;
+        self.delay_ns += ns;
+    }
+}
+
+impl<DELAY: DelayNs> DelayNs for CountingDelay<DELAY> {
+    fn delay_ns(&mut self, ns: u32) {
+        self.count(ns as u64);
+        self.inner.delay_ns(ns);
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        self.count(us as u64 * 1_000);
+        self.inner.delay_us(us);
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.count(ms as u64 * 1_000_000);
+        self.inner.delay_ms(ms);
+    }
+}
+
+impl<UART, GPIO, DELAY, E> Mcp2003a<CountingUart<UART>, GPIO, CountingDelay<DELAY>>
+where
+    UART: Read<Error = E> + Write<Error = E>,
+    E: embedded_hal_nb::serial::Error,
+    GPIO: OutputPin,
+    DELAY: DelayNs,
+{
+    /// Get the counts since the last `reset_bench_counts`, or since the driver was created.
+    pub fn bench_counts(&self) -> BenchCounts {
+        BenchCounts {
+            delay_calls: self.delay.delay_calls,
+            delay_ns: self.delay.delay_ns,
+            bytes_written: self.uart.bytes_written,
+            bytes_read: self.uart.bytes_read,
+        }
+    }
+
+    /// Reset the counts to zero.
+    pub fn reset_bench_counts(&mut self) {
+        self.delay.delay_calls = 0;
+        self.delay.delay_ns = 0;
+        self.uart.bytes_written = 0;
+        self.uart.bytes_read = 0;
+    }
+
+    /// Get the UART wrapped in the `CountingUart` of the driver, e.g. to queue the bytes a mock returns
+    /// before a `bench_read_frame`.
+    pub fn bench_uart_mut(&mut self) -> &mut UART {
+        self.uart.inner_mut()
+    }
+
+    /// Send a frame like `send_frame`, returning its result and the counts of the call alone.
+    pub fn bench_send_frame(
+        &mut self,
+        id: u8,
+        data: &[u8],
+        checksum: u8,
+    ) -> (Result<[u8; MAX_FRAME_LEN], Mcp2003aError<E>>, BenchCounts) {
+        self.reset_bench_counts();
+        let result = self.send_frame(id, data, checksum);
+        (result, self.bench_counts())
+    }
+
+    /// Read a frame like `read_frame`, returning its result and the counts of the call alone.
+    pub fn bench_read_frame(&mut self, id: u8, buffer: &mut [u8]) -> (Result<u8, Mcp2003aError<E>>, BenchCounts) {
+        self.reset_bench_counts();
+        let result = self.read_frame(id, buffer);
+        (result, self.bench_counts())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::config::{LinBusConfig, LinInterFrameSpace};
+    use crate::tests::{MockDelay, MockPin, MockUart};
+    use crate::typestate::Uninitialized;
+
+    #[test]
+    fn test_bench_counts() {
+        let mut mcp2003a = Uninitialized::new(
+            CountingUart::new(MockUart::default()),
+            MockPin::default(),
+            CountingDelay::new(MockDelay::default()),
+        )
+        .init(LinBusConfig::default());
+
+        let (result, counts) = mcp2003a.bench_send_frame(0x80, &[0x01], 0x7D);
+        result.unwrap();
+        assert_eq!(counts.bytes_written, 4);
+        assert_eq!(counts.bytes_read, 0);
+        // Bus idle, break, break delimiter, inter-frame space
+        assert_eq!(counts.delay_calls, 4);
+        assert_eq!(counts.delay_ns, 100_000 + 677_079 + 52_083 + 1_000_000);
+
+        mcp2003a.bench_uart_mut().rx = std::vec![0x55, 0xC1, 0x02, 0x03];
+        let mut buffer = [0u8; 1];
+        let (result, counts) = mcp2003a.bench_read_frame(0xC1, &mut buffer);
+        assert_eq!(result.unwrap(), 0x03);
+        assert_eq!(counts.bytes_written, 2);
+        assert_eq!(counts.bytes_read, 4);
+
+        // A shorter inter-frame space shows in the counts
+        mcp2003a.init(LinBusConfig {
+            inter_frame_space: LinInterFrameSpace::None,
+            ..LinBusConfig::default()
+        });
+        let (_, shorter) = mcp2003a.bench_send_frame(0x80, &[0x01], 0x7D);
+        assert_eq!(shorter.delay_ns, 100_000 + 677_079 + 52_083);
+    }
+}
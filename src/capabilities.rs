@@ -0,0 +1,108 @@
+//! Reporting the optional features the driver was built with and its active configuration, e.g. to log at startup.
+//!
+//! ```rust,ignore
+//! let capabilities = mcp2003a.capabilities();
+//! log::info!("LIN driver: {:?}", capabilities);
+//! ```
+//!
+//! The blocking methods are always compiled, and the async methods with the `async` feature. Which of them are
+//! available depends on the traits implemented by the UART, GPIO, and Delay drivers, so it is checked by the compiler
+//! instead of reported here.
+
+use crate::config::{BreakMethod, LinBusSpeed};
+use crate::Mcp2003a;
+
+/// Optional Cargo features of the crate, each true when enabled in this build.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Features {
+    /// `unchecked-init`: `Mcp2003a::new` without a configuration.
+    pub unchecked_init: bool,
+    /// `last-frame`: raw bytes of the last frame kept for `last_tx` and `last_rx`.
+    pub last_frame: bool,
+    /// `transport`: diagnostic services over the LIN transport layer.
+    pub transport: bool,
+    /// `async`: the `_async` methods on top of `embedded-hal-async` and `embedded-io-async`.
+    pub async_methods: bool,
+    /// `bench`: counting UART and delay wrappers with instrumented operations.
+    pub bench: bool,
+}
+
+/// Optional Cargo features enabled in this build, evaluated at compile time.
+pub const FEATURES: Features = Features {
+    unchecked_init: cfg!(feature = "unchecked-init"),
+    last_frame: cfg!(feature = "last-frame"),
+    transport: cfg!(feature = "transport"),
+    async_methods: cfg!(feature = "async"),
+    bench: cfg!(feature = "bench"),
+};
+
+/// The features the driver was built with and the parts of its configuration changing how frames are sent and read.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Capabilities {
+    /// Name of the driver instance set with `set_name`, to tell multiple LIN buses apart.
+    pub name: Option<&'static str>,
+    /// Optional Cargo features enabled in this build.
+    pub features: Features,
+    /// Configured LIN bus speed.
+    pub speed: LinBusSpeed,
+    /// Configured method generating the break.
+    pub break_method: BreakMethod,
+    /// Whether a separate wakeup pin was given, instead of sending the wakeup on the break pin.
+    pub wakeup_pin: bool,
+    /// Whether an enable pin was given to gate the transceiver.
+    pub enable_pin: bool,
+    /// Whether the header is expected to be echoed back before the slave response.
+    pub expect_header_echo: bool,
+    /// Whether `read_frame` calculates the PID from a raw ID.
+    pub auto_pid: bool,
+}
+
+impl<UART, GPIO, DELAY> Mcp2003a<UART, GPIO, DELAY> {
+    /// Get the features the driver was built with and its active configuration, e.g. to log at startup.
+    ///
+    /// - Note: Frames are sent with the checksum given by the caller, so there is no configured checksum mode.
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            name: self.name,
+            features: FEATURES,
+            speed: self.config.speed,
+            break_method: self.config.break_method,
+            wakeup_pin: self.wakeup_pin.is_some(),
+            enable_pin: self.enable_pin.is_some(),
+            expect_header_echo: self.config.expect_header_echo,
+            auto_pid: self.config.auto_pid,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LinBusConfig;
+    use crate::tests::mock_mcp2003a;
+
+    #[test]
+    fn test_capabilities() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.init(LinBusConfig {
+            speed: LinBusSpeed::Baud9600,
+            auto_pid: true,
+            ..LinBusConfig::default()
+        });
+        assert_eq!(mcp2003a.capabilities().name, None);
+        mcp2003a.set_name("body");
+
+        let capabilities = mcp2003a.capabilities();
+        assert_eq!(capabilities.name, Some("body"));
+        assert_eq!(mcp2003a.name(), Some("body"));
+        assert_eq!(capabilities.features.last_frame, cfg!(feature = "last-frame"));
+        assert_eq!(capabilities.features.transport, cfg!(feature = "transport"));
+        assert_eq!(capabilities.features.async_methods, cfg!(feature = "async"));
+        assert_eq!(capabilities.speed, LinBusSpeed::Baud9600);
+        assert_eq!(capabilities.break_method, LinBusConfig::default().break_method);
+        assert!(!capabilities.wakeup_pin);
+        assert!(!capabilities.enable_pin);
+        assert!(capabilities.expect_header_echo);
+        assert!(capabilities.auto_pid);
+    }
+}
@@ -0,0 +1,211 @@
+//! Fixed-capacity buffer of captured LIN frames, without allocation.
+//!
+//! Frames observed on the bus are pushed into a `CaptureBuffer` of the last `N` frames, so the application can
+//! drain the accumulated traffic periodically instead of handling each frame as it is read.
+//! When the buffer is full, the oldest frame is dropped to make room and the drop is counted.
+//!
+//! ```rust,ignore
+//! let mut captured = CaptureBuffer::<16>::new();
+//! let checksum = mcp2003a.read_frame(0xC1, &mut buffer)?;
+//! captured.push(CapturedFrame::new(0xC1, &buffer, checksum));
+//!
+//! for frame in captured.drain_captured() {
+//!     log::info!("{:02X}: {:02X?}", frame.pid, frame.data());
+//! }
+//! ```
+//!
+//! To reconstruct the schedule timing of the master from the capture, tell the buffer the time elapsed between
+//! frames with `elapse`, e.g. the `last_frame_duration_ns` of each read and the delays of the application.
+//! Each pushed frame is stamped with the time elapsed since the previous one:
+//!
+//! ```rust,ignore
+//! let checksum = mcp2003a.read_frame(0xC1, &mut buffer)?;
+//! captured.elapse(mcp2003a.last_frame_duration_ns());
+//! captured.push(CapturedFrame::new(0xC1, &buffer, checksum));
+//! ```
+//!
+//! - Note: There is no hardware clock available to the driver, so the times are the sum of the configured timings
+//!   and requested delays, not measurements. They miss the execution time of the code between delays and the
+//!   inaccuracy of the `DelayNs` implementation, so the real spacing of the frames is somewhat longer.
+
+use crate::frame::{ParsedFrame, MAX_DATA_LEN};
+
+/// A LIN frame observed on the bus.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CapturedFrame {
+    /// Protected identifier (PID) of the frame.
+    pub pid: u8,
+    /// Data bytes of the frame, of which the first `len` are used.
+    pub data: [u8; MAX_DATA_LEN],
+    /// Number of data bytes of the frame.
+    pub len: usize,
+    /// Checksum received after the data.
+    pub checksum: u8,
+    /// Time in nanoseconds elapsed since the previous frame was pushed to the `CaptureBuffer`, from the times
+    /// given to its `elapse`. For the first frame, the time since the buffer was created.
+    pub since_previous_ns: u64,
+}
+
+impl CapturedFrame {
+    /// Create a captured frame from its PID, data, and checksum.
+    ///
+    /// - Note: Panics if there are more than 8 data bytes.
+    pub fn new(pid: u8, data: &[u8], checksum: u8) -> Self {
+        assert!(data.len() <= MAX_DATA_LEN, "A LIN frame has at most 8 data bytes");
+        let mut frame = CapturedFrame {
+            pid,
+            len: data.len(),
+            checksum,
+            ..Default::default()
+        };
+        frame.data[..data.len()].copy_from_slice(data);
+        frame
+    }
+
+    /// Get the used data bytes of the frame.
+    pub fn data(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+}
+
+impl From<&ParsedFrame> for CapturedFrame {
+    fn from(frame: &ParsedFrame) -> Self {
+        CapturedFrame::new(frame.pid(), frame.data(), frame.checksum())
+    }
+}
+
+/// Ring buffer of the last `N` captured frames.
+#[derive(Clone, Debug)]
+pub struct CaptureBuffer<const N: usize> {
+    frames: [CapturedFrame; N],
+    /// Index of the oldest frame.
+    head: usize,
+    len: usize,
+    dropped: u32,
+    /// Time elapsed since the last frame was pushed.
+    elapsed_ns: u64,
+}
+
+impl<const N: usize> CaptureBuffer<N> {
+    /// Create an empty capture buffer.
+    pub fn new() -> Self {
+        CaptureBuffer {
+            frames: [CapturedFrame::default(); N],
+            head: 0,
+            len: 0,
+            dropped: 0,
+            elapsed_ns: 0,
+        }
+    }
+
+    /// Account for the given time in nanoseconds passing on the bus, stamped on the next frame pushed.
+    pub fn elapse(&mut self, ns: u64) {
+        self.elapsed_ns = self.elapsed_ns.saturating_add(ns);
+    }
+
+    /// Add a frame to the buffer, stamped with the time elapsed since the previous frame.
+    /// When the buffer is full, the oldest frame is dropped and counted in `dropped`.
+    pub fn push(&mut self, mut frame: CapturedFrame) {
+        frame.since_previous_ns = self.elapsed_ns;
+        self.elapsed_ns = 0;
+        if N == 0 {
+            self.dropped = self.dropped.saturating_add(1);
+            return;
+        }
+        if self.len == N {
+            self.head = (self.head + 1) % N;
+            self.len -= 1;
+            self.dropped = self.dropped.saturating_add(1);
+        }
+        self.frames[(self.head + self.len) % N] = frame;
+        self.len += 1;
+    }
+
+    /// Remove and return the oldest frame of the buffer.
+    pub fn pop(&mut self) -> Option<CapturedFrame> {
+        if self.len == 0 {
+            return None;
+        }
+        let frame = self.frames[self.head];
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+        Some(frame)
+    }
+
+    /// Remove the frames of the buffer, oldest first.
+    pub fn drain_captured(&mut self) -> impl Iterator<Item = CapturedFrame> + '_ {
+        core::iter::from_fn(move || self.pop())
+    }
+
+    /// Get the number of frames in the buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the buffer holds no frames.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the number of frames dropped because the buffer was full, since it was created.
+    pub fn dropped(&self) -> u32 {
+        self.dropped
+    }
+}
+
+impl<const N: usize> Default for CaptureBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_buffer_drain() {
+        let mut captured = CaptureBuffer::<4>::new();
+        captured.push(CapturedFrame::new(0xC1, &[0x01, 0x02], 0x3B));
+        captured.push(CapturedFrame::new(0x80, &[0x03], 0x7C));
+        assert_eq!(captured.len(), 2);
+
+        let mut drained = captured.drain_captured();
+        let first = drained.next().unwrap();
+        assert_eq!(
+            (first.pid, first.data(), first.checksum),
+            (0xC1, &[0x01, 0x02][..], 0x3B)
+        );
+        assert_eq!(drained.next().unwrap().pid, 0x80);
+        assert_eq!(drained.next(), None);
+        drop(drained);
+        assert!(captured.is_empty());
+    }
+
+    #[test]
+    fn test_capture_buffer_timestamps() {
+        let mut captured = CaptureBuffer::<4>::new();
+        captured.elapse(3_000_000);
+        captured.push(CapturedFrame::new(0xC1, &[0x01], 0x3D));
+        // A read of 5 ms, then a 5 ms wait of the application before the next frame
+        captured.elapse(5_000_000);
+        captured.elapse(5_000_000);
+        captured.push(CapturedFrame::new(0x80, &[0x03], 0x7C));
+        captured.push(CapturedFrame::new(0x42, &[0x04], 0x00));
+
+        let times: [u64; 3] = core::array::from_fn(|_| captured.pop().unwrap().since_previous_ns);
+        assert_eq!(times, [3_000_000, 10_000_000, 0]);
+    }
+
+    #[test]
+    fn test_capture_buffer_overflow() {
+        let mut captured = CaptureBuffer::<2>::new();
+        for pid in 0..5 {
+            captured.push(CapturedFrame::new(pid, &[], 0x00));
+        }
+        assert_eq!(captured.dropped(), 3);
+        assert_eq!(captured.pop().unwrap().pid, 3);
+        assert_eq!(captured.pop().unwrap().pid, 4);
+        assert_eq!(captured.pop(), None);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::frame::MAX_DATA_LEN;
+
 /// LIN Break Duration for the MCP2003A transceiver.
 /// The specification requires a minimum of 13 bits for the break signal, but the actual underlying
 /// implementation of the LIN devices may require more bits for stability (maybe 13 bits + 1 or 2 bits).
@@ -15,6 +17,34 @@ impl LinBreakDuration {
             LinBreakDuration::Minimum13BitsPlus(bits) => bit_period_ns * ((13u8) + bits) as u32,
         }
     }
+
+    /// Get the break duration extended by the given number of bit periods, saturating at 13 + 255 bits.
+    pub fn extended(self, bits: u8) -> Self {
+        match self {
+            LinBreakDuration::Minimum13Bits if bits == 0 => LinBreakDuration::Minimum13Bits,
+            LinBreakDuration::Minimum13Bits => LinBreakDuration::Minimum13BitsPlus(bits),
+            LinBreakDuration::Minimum13BitsPlus(plus) => LinBreakDuration::Minimum13BitsPlus(plus.saturating_add(bits)),
+        }
+    }
+}
+
+/// How the break signal at the beginning of a frame is generated.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BreakMethod {
+    /// Drive the break pin high for the configured `break_duration`, holding the bus dominant.
+    BreakPin,
+    /// Write a single 0x00 byte on the UART, holding the bus dominant for 9 bit periods of the UART baud rate.
+    /// This is only a valid break if the UART baud rate is lowered to 9/13 of the LIN bus speed or less
+    /// while the byte is written (e.g. 9600 baud for a 19200 baud bus). Register a `BaudSwitcher` with
+    /// `set_baud_switcher` to have the driver lower the baud rate to last the `break_duration` and restore it,
+    /// else the application must do it and the `break_duration` is not used.
+    UartBreak,
+    /// Hold the UART TX line low with the break control of the UART for the configured `break_duration`,
+    /// suppressing the stop bit so the slaves detecting the break as a UART framing error are guaranteed to see one.
+    /// This needs a HAL exposing the break control of the UART (e.g. the break bit of a 16550 line control
+    /// register, or a send break request), registered with `set_tx_break_control`, and the UART TX wired to the
+    /// TXD of the transceiver. Without a registered break control, the break pin is driven like `BreakPin`.
+    FramingError,
 }
 
 /// LIN Wakeup Signal Duration for the MCP2003A transceiver.
@@ -31,28 +61,164 @@ impl LinWakeupDuration {
     pub fn get_duration_ns(&self) -> u32 {
         match self {
             LinWakeupDuration::Minimum250Microseconds => 250_000,
-            LinWakeupDuration::Minimum250MicrosecondsPlus(extra) => 250_000 + extra,
+            LinWakeupDuration::Minimum250MicrosecondsPlus(extra) => extra.saturating_add(250_000),
             LinWakeupDuration::Maximum5Milliseconds => 5_000_000,
         }
     }
 }
 
+/// How `send_wakeup` wakes the slaves of the bus.
+/// The wakeup pulse is the standard LIN wakeup signal, but some slaves ignore it and only wake on bus traffic.
+/// For those, bus traffic is generated with headers that no slave answers: a break, its delimiter,
+/// and the sync byte, without an ID. The inter-frame space is applied after each of them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WakeupMethod {
+    /// Drive the wakeup pulse of the configured `wakeup_duration`.
+    Pulse,
+    /// Send the given number of headers without an ID instead of the pulse.
+    FrameTraffic(u8),
+    /// Drive the wakeup pulse, then send the given number of headers without an ID.
+    PulseAndFrameTraffic(u8),
+}
+
+impl WakeupMethod {
+    /// Whether the wakeup pulse is driven.
+    pub fn pulse(&self) -> bool {
+        matches!(self, WakeupMethod::Pulse | WakeupMethod::PulseAndFrameTraffic(_))
+    }
+
+    /// Get the number of headers without an ID sent for the bus traffic.
+    pub fn traffic_headers(&self) -> u8 {
+        match self {
+            WakeupMethod::Pulse => 0,
+            WakeupMethod::FrameTraffic(headers) | WakeupMethod::PulseAndFrameTraffic(headers) => *headers,
+        }
+    }
+}
+
+/// A configurable wait, e.g. the settle time after a wakeup or the idle time before a break.
+/// `None` skips the wait.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LinDelay {
+    None,
+    DelayMicroseconds(u32),
+    DelayMilliseconds(u32),
+}
+
+impl LinDelay {
+    /// Get the duration in nanoseconds of the wait.
+    pub fn get_duration_ns(&self) -> u64 {
+        match self {
+            LinDelay::None => 0,
+            LinDelay::DelayMicroseconds(us) => *us as u64 * 1_000,
+            LinDelay::DelayMilliseconds(ms) => *ms as u64 * 1_000_000,
+        }
+    }
+}
+
+/// What to do when flushing the UART fails after writing bytes, e.g. with UART drivers that do not implement it.
+///
+/// Flushing waits for the written bytes to leave the UART, so the break, inter-byte, and inter-frame timings
+/// start after the bytes are on the bus. Waiting the nominal time of the bytes instead keeps frames working
+/// with such drivers, at the cost of accuracy: the wait starts when the bytes were queued, not when they
+/// started leaving the UART, and errors of the transmission itself are no longer reported.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FlushFallback {
+    /// Return the flush error as `UartWriteNotReady` (blocking) or `AsyncUartError` (async).
+    Fail,
+    /// Wait the nominal time of the written bytes at the bus speed instead of failing.
+    /// The blocking UART falls back on any flush error, as `embedded-hal-nb` errors have no unsupported kind.
+    /// The async UART only falls back on errors of the `Unsupported` kind.
+    WaitByteTime,
+}
+
+/// How a read decides the response ended, when fewer bytes than a complete frame were received.
+/// A complete frame always ends the read once its checksum byte was received.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FrameEndDetection {
+    /// Stop when the UART has no more bytes (`WouldBlock`, or `ReadReady::read_ready` returning false for an async UART).
+    WouldBlock,
+    /// Stop after the expected number of bytes (the header echo, the data, and the checksum), whatever they are.
+    /// For buffered or DMA UARTs where bytes arrive continuously, so the UART may never run out of bytes.
+    /// Skipped break bytes are not counted.
+    ByteCount,
+}
+
+/// Framing of each byte on the UART: a start bit, the data bits, an optional parity bit, and the stop bits.
+/// LIN specifies 8N1 (10 bit periods per byte), but the UART is configured outside the driver, so a UART
+/// configured with parity or 2 stop bits sends longer bytes. The timing calculations use the real byte time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UartFraming {
+    /// Number of data bits, typically 8.
+    pub data_bits: u8,
+    /// Whether a parity bit (even or odd) follows the data bits.
+    pub parity: bool,
+    /// Number of stop bits, 1 or 2.
+    pub stop_bits: u8,
+}
+
+impl UartFraming {
+    /// 8 data bits, no parity, and 1 stop bit, as specified by LIN.
+    pub const EIGHT_N_ONE: UartFraming = UartFraming::new(8, false, 1);
+    /// 8 data bits, a parity bit, and 1 stop bit.
+    pub const EIGHT_E_ONE: UartFraming = UartFraming::new(8, true, 1);
+    /// 8 data bits, no parity, and 2 stop bits.
+    pub const EIGHT_N_TWO: UartFraming = UartFraming::new(8, false, 2);
+
+    /// Create the framing with the given data bits, parity bit, and stop bits.
+    pub const fn new(data_bits: u8, parity: bool, stop_bits: u8) -> Self {
+        UartFraming {
+            data_bits,
+            parity,
+            stop_bits,
+        }
+    }
+
+    /// Get the number of bit periods of a byte on the bus, including the start bit.
+    pub fn bits_per_byte(&self) -> u32 {
+        1 + self.data_bits as u32 + self.parity as u32 + self.stop_bits as u32
+    }
+}
+
+impl Default for UartFraming {
+    fn default() -> Self {
+        UartFraming::EIGHT_N_ONE
+    }
+}
+
 /// How long to wait after sending a read header before reading the response, allowing the slave device to respond.
 /// Typically this is a 1-10 ms delay but can vary by system.
+///
+/// A fixed delay either over-waits for short responses or under-waits for long ones,
+/// so `PerByteBitPeriods` scales the delay with the expected response length instead:
+/// the given number of bit periods for each expected byte (the data bytes and the checksum).
+/// A byte takes 10 bit periods on the bus, so e.g. `PerByteBitPeriods(14)` allows 40% for inter-byte space.
+/// The bit periods are given for 10 bit bytes (8N1), and scaled to the byte time of the configured `uart_framing`.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum LinReadDeviceResponseTimeout {
     None,
     DelayMicroseconds(u32),
     DelayMilliseconds(u32),
+    PerByteBitPeriods(u32),
 }
 
 impl LinReadDeviceResponseTimeout {
-    /// Get the duration in nanoseconds for the LIN read device response timeout.
-    pub fn get_duration_ns(&self) -> u32 {
+    /// Get the duration in nanoseconds for the LIN read device response timeout,
+    /// for a response with the given number of data bytes.
+    pub fn get_duration_ns(&self, bit_period_ns: u32, data_len: usize) -> u64 {
+        self.get_duration_ns_framed(bit_period_ns, data_len, UartFraming::EIGHT_N_ONE)
+    }
+
+    /// Get the duration in nanoseconds for the LIN read device response timeout, for a response with the given
+    /// number of data bytes, with `PerByteBitPeriods` scaled to the byte time of the given UART framing.
+    pub fn get_duration_ns_framed(&self, bit_period_ns: u32, data_len: usize, framing: UartFraming) -> u64 {
         match self {
             LinReadDeviceResponseTimeout::None => 0,
-            LinReadDeviceResponseTimeout::DelayMicroseconds(us) => *us * 1_000,
-            LinReadDeviceResponseTimeout::DelayMilliseconds(ms) => *ms * 1_000_000,
+            LinReadDeviceResponseTimeout::DelayMicroseconds(us) => *us as u64 * 1_000,
+            LinReadDeviceResponseTimeout::DelayMilliseconds(ms) => *ms as u64 * 1_000_000,
+            LinReadDeviceResponseTimeout::PerByteBitPeriods(bits) => {
+                (data_len as u64 + 1) * *bits as u64 * bit_period_ns as u64 * framing.bits_per_byte() as u64 / 10
+            }
         }
     }
 }
@@ -68,11 +234,11 @@ pub enum LinInterFrameSpace {
 
 impl LinInterFrameSpace {
     /// Get the duration in nanoseconds for the LIN inter-frame space.
-    pub fn get_duration_ns(&self) -> u32 {
+    pub fn get_duration_ns(&self) -> u64 {
         match self {
             LinInterFrameSpace::None => 0,
-            LinInterFrameSpace::DelayMicroseconds(us) => *us * 1_000,
-            LinInterFrameSpace::DelayMilliseconds(ms) => *ms * 1_000_000,
+            LinInterFrameSpace::DelayMicroseconds(us) => *us as u64 * 1_000,
+            LinInterFrameSpace::DelayMilliseconds(ms) => *ms as u64 * 1_000_000,
         }
     }
 }
@@ -109,24 +275,259 @@ pub struct LinBusConfig {
     /// LIN Bus Speed / Baud Rate in bits per second.
     /// Required to calculate the bit period for the break signal duration.
     pub speed: LinBusSpeed,
+    /// Framing of the bytes on the UART, configured outside the driver. LIN specifies 8N1.
+    pub uart_framing: UartFraming,
     /// Duration of the break signal at the beginning of a frame.
     pub break_duration: LinBreakDuration,
+    /// How the break signal at the beginning of a frame is generated.
+    pub break_method: BreakMethod,
+    /// Whether the `break_duration` includes the 1 bit break delimiter after the dominant break.
+    /// When false, the bus is held dominant for the whole `break_duration`, then the delimiter follows,
+    /// for a break field of `break_duration` + 1 bit. When true, the bus is held dominant for
+    /// `break_duration` - 1 bit, for a break field of `break_duration`, e.g. to match an oscilloscope
+    /// measurement of the whole break field.
+    pub break_includes_delimiter: bool,
     /// Duration of the wakeup signal at the beginning of communication.
     pub wakeup_duration: LinWakeupDuration,
+    /// How the wakeup signal wakes the slaves: the standard pulse, bus traffic for slaves ignoring the pulse, or both.
+    pub wakeup_method: WakeupMethod,
+    /// How long to wait after the wakeup signal before sending frames, allowing the slave devices to boot.
+    /// The LIN specification has the master wait 100 ms to 150 ms after a wakeup signal.
+    pub wakeup_settle: LinDelay,
+    /// How many frames sent or read after `send_wakeup` get their break extended by `post_wakeup_break_extension`,
+    /// for slaves that need a longer break while they finish waking up. 0 disables the extension.
+    pub post_wakeup_break_frames: u8,
+    /// How many bit periods are added to the `break_duration` of the first `post_wakeup_break_frames` frames
+    /// after `send_wakeup`.
+    pub post_wakeup_break_extension: u8,
     /// How long to wait after sending a read header before reading the response from the device.
     pub read_device_response_timeout: LinReadDeviceResponseTimeout,
+    /// How long to wait once more for the checksum byte, when all the data bytes of a response were read but the
+    /// UART ran out of bytes before the checksum, for slaves leaving a larger gap before it. The read continues
+    /// after the wait, so a late checksum is read instead of returning `LinReadNoChecksumReceived`.
+    pub checksum_wait: LinDelay,
     /// How long to wait after sending a frame before sending the next frame.
     pub inter_frame_space: LinInterFrameSpace,
+    /// How long to wait between each byte of a frame sent by the master, after the previous byte left the UART.
+    /// Some low-end slaves with software UARTs need time between bytes. Typically this is none (back-to-back).
+    pub inter_byte_space: LinDelay,
+    /// How long the bus must be idle before each break, after the inter-frame space of the previous frame,
+    /// letting the previous transmission settle.
+    pub bus_idle_before_break: LinDelay,
+    /// What to do when flushing the UART fails after writing bytes.
+    pub flush_fallback: FlushFallback,
+    /// How long to hold the bus recessive (idle) when resetting the bus with `reset_bus`,
+    /// letting the bus devices drop any partially received frame.
+    pub reset_idle: LinDelay,
+    /// How long the bus must stay quiet after the go-to-sleep command for `go_to_sleep` to confirm the slaves
+    /// went to sleep. `None` sends the command without verifying.
+    pub sleep_verify: LinDelay,
+    /// How long to wait after enabling the transceiver with its enable pin before using the bus, for the
+    /// transceiver to leave its low power mode. Check the datasheet of your transceiver for this time.
+    pub enable_settle: LinDelay,
+    /// Enable the transceiver with its enable pin before each break and wakeup signal, if it is not enabled yet,
+    /// so the firmware only has to `disable` it between bursts of frames. Has no effect without an enable pin.
+    pub auto_enable: bool,
+    /// How many bytes read back before the sync byte may be the break itself, surfaced by the UART as a 0x00 byte
+    /// or a framing error, and skipped instead of being treated as stray data or a UART error.
+    /// Framing errors can only be detected by the blocking `read_frame`, as async UART errors have no framing kind.
+    pub break_byte_tolerance: u8,
+    /// How many 0x55 sync bytes may be read back before the ID byte, for transceivers or capture tools that repeat
+    /// the sync byte. Up to this many 0x55 bytes are taken as sync bytes, so the PID 0x55 (ID 0x15) is only found
+    /// after all of them, while other PIDs are found after fewer. Values below 1 are treated as 1.
+    pub max_sync_bytes: u8,
+    /// Accept a sync byte read back with one bit wrong, e.g. 0x54 or 0x57 for 0x55 from a sampling error at the edge
+    /// of a bit, as sync bytes of `read_frame` in very noisy environments. This trades strictness for robustness:
+    /// a stray byte one bit off 0x55 is taken as the sync byte too. Disabled by default, for an exact match.
+    pub fuzzy_sync: bool,
+    /// Drain stale bytes from the UART receive buffer before sending the header in `read_frame`.
+    /// Helps when a previous frame was aborted and left bytes behind, e.g. frames sent back-to-back quickly.
+    /// Only applies to the blocking `read_frame`, as an async UART read waits for bytes instead of returning.
+    pub clear_rx_before_read: bool,
+    /// Whether the header sent by `read_frame` is echoed back on RX before the slave response, as with the MCP2003A.
+    /// Set to false for transceivers or wiring where RX only carries the slave response,
+    /// so the first received bytes are read as the response data directly.
+    pub expect_header_echo: bool,
+    /// How many echoed bytes to skip before the slave response when `expect_header_echo` is set:
+    /// the 2 bytes of the header `[0x55, id]`, found by matching them, then any further echoed bytes,
+    /// for transceivers or wirings that echo more than the header. Values below 2 are treated as 2.
+    pub header_skip_bytes: u8,
+    /// How a read decides the response ended when fewer bytes than a complete frame were received.
+    pub frame_end_detection: FrameEndDetection,
+    /// Calculate the PID from the raw 6-bit ID given to `read_frame`, so reads can use raw IDs like
+    /// `send_frame_enhanced`. The header is sent and its echo matched with the calculated PID.
+    /// A PID given with this enabled is sent unchanged, as its lower 6 bits are the raw ID.
+    pub auto_pid: bool,
+}
+
+impl LinBusConfig {
+    /// Preset for a typical LIN 2.x automotive network at 19200 baud (powertrain and chassis nodes),
+    /// with the timings of the LIN 2.2A specification where it sets them:
+    /// - Bus speed of 19200 baud, the highest standard rate within the 1 to 20 kbit/s of the physical layer.
+    /// - Break of 13 bit periods, the minimum the master sends (section 2.3.1.1).
+    /// - Wakeup signal of 250 microseconds, the minimum slaves detect (section 2.6.2).
+    /// - Waits 100 ms after the wakeup before the first frame, the time the nodes have to be ready to receive
+    ///   after a wakeup (section 2.6.2).
+    /// - Response timeout of 14 bit periods per byte: a response may take up to 1.4 times its nominal
+    ///   10 bit periods per byte (section 2.3.2).
+    /// - Inter-frame space of 1 ms. This is a project choice leaving margin between frame slots. The specification
+    ///   only requires it to be non-negative (section 2.3.2).
+    pub fn automotive_19200() -> Self {
+        LinBusConfig {
+            speed: LinBusSpeed::Baud19200,
+            break_duration: LinBreakDuration::Minimum13Bits,
+            wakeup_duration: LinWakeupDuration::Minimum250Microseconds,
+            wakeup_settle: LinDelay::DelayMilliseconds(100),
+            read_device_response_timeout: LinReadDeviceResponseTimeout::PerByteBitPeriods(14),
+            inter_frame_space: LinInterFrameSpace::DelayMilliseconds(1),
+            ..LinBusConfig::default()
+        }
+    }
+
+    /// Preset for a comfort/body network at 9600 baud (seats, mirrors, climate controls), with the timings of
+    /// the LIN 2.2A specification like `automotive_19200`, except:
+    /// - Bus speed of 9600 baud, a common rate for comfort networks within the 1 to 20 kbit/s of the physical layer.
+    /// - Break of 13 + 1 bit periods. This is a project choice: the specification only sets the 13 bit minimum
+    ///   (section 2.3.1.1), the extra bit is margin for the slow software UARTs common in comfort nodes.
+    /// - Inter-frame space of 2 ms. This is a project choice, twice the 1 ms of `automotive_19200` for frames
+    ///   taking twice as long at half the bus speed. The specification only requires it to be non-negative
+    ///   (section 2.3.2).
+    pub fn comfort_9600() -> Self {
+        LinBusConfig {
+            speed: LinBusSpeed::Baud9600,
+            break_duration: LinBreakDuration::Minimum13BitsPlus(1),
+            inter_frame_space: LinInterFrameSpace::DelayMilliseconds(2),
+            ..LinBusConfig::automotive_19200()
+        }
+    }
+
+    /// Preset for a SAE J2602 network at 10400 baud, common in North American vehicles.
+    /// - Bus speed of 10.417 kbps, the only rate SAE J2602-1 allows.
+    /// - The other timings are those of `automotive_19200`, as SAE J2602-1 builds on LIN 2.0.
+    pub fn sae_j2602_10400() -> Self {
+        LinBusConfig {
+            speed: LinBusSpeed::Baud10400,
+            ..LinBusConfig::automotive_19200()
+        }
+    }
+
+    /// Get the time in nanoseconds the bus is held dominant for the break: the `break_duration`,
+    /// less the 1 bit break delimiter if `break_includes_delimiter` is set.
+    pub fn break_dominant_time_ns(&self) -> u32 {
+        let bit_period_ns = self.speed.get_bit_period_ns();
+        let break_duration_ns = self.break_duration.get_duration_ns(bit_period_ns);
+        if self.break_includes_delimiter {
+            break_duration_ns - bit_period_ns
+        } else {
+            break_duration_ns
+        }
+    }
+
+    /// Get the nominal time in nanoseconds for the break and the break delimiter on the bus.
+    pub fn nominal_break_time_ns(&self) -> u64 {
+        self.break_dominant_time_ns() as u64 + self.speed.get_bit_period_ns() as u64
+    }
+
+    /// Get the time in nanoseconds of a byte on the bus with the configured `uart_framing`,
+    /// 10 bit periods for 8N1.
+    pub fn byte_time_ns(&self) -> u64 {
+        self.uart_framing.bits_per_byte() as u64 * self.speed.get_bit_period_ns() as u64
+    }
+
+    /// Get the nominal time in nanoseconds for a frame header on the bus:
+    /// the break, the break delimiter, then the sync and PID bytes (10 bit periods each with 8N1).
+    pub fn nominal_header_time_ns(&self) -> u64 {
+        self.nominal_break_time_ns() + 2 * self.byte_time_ns()
+    }
+
+    /// Get the nominal time in nanoseconds for a complete frame with the given data length on the bus:
+    /// the header, then the data and checksum bytes (10 bit periods each with 8N1).
+    pub fn nominal_frame_time_ns(&self, data_len: usize) -> u64 {
+        self.nominal_header_time_ns() + (data_len as u64 + 1) * self.byte_time_ns()
+    }
+
+    /// Check the configured timings keep a frame with the given data length within the maximum frame time of
+    /// the LIN specification (LIN 2.2A section 2.3.2): the configured header must fit within `THeader_Maximum`,
+    /// and the header and the response timeout within `TFrame_Maximum`, 1.4 times the nominal frame time.
+    /// Returns the first constraint violated.
+    ///
+    /// - Note: The nominal times of the specification assume a 13 bit break, regardless of the configured break.
+    pub fn validate_frame_timing(&self, data_len: usize) -> Result<(), TimingError> {
+        if !(1..=MAX_DATA_LEN).contains(&data_len) {
+            return Err(TimingError::InvalidLength(data_len));
+        }
+        let bit_period_ns = self.speed.get_bit_period_ns();
+        let header_max_ns = 34 * 14 * bit_period_ns as u64 / 10;
+        let response_max_ns = (data_len as u64 + 1) * 10 * 14 * bit_period_ns as u64 / 10;
+
+        let header_ns = self.nominal_header_time_ns();
+        if header_ns > header_max_ns {
+            return Err(TimingError::HeaderTooLong {
+                header_ns,
+                max_ns: header_max_ns,
+            });
+        }
+
+        let frame_ns = header_ns
+            + self
+                .read_device_response_timeout
+                .get_duration_ns_framed(bit_period_ns, data_len, self.uart_framing);
+        let frame_max_ns = header_max_ns + response_max_ns;
+        if frame_ns > frame_max_ns {
+            return Err(TimingError::FrameTooLong {
+                frame_ns,
+                max_ns: frame_max_ns,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// LIN timing constraint violated by a configuration, from `LinBusConfig::validate_frame_timing`.
+/// Each contains the configured time and the maximum time in nanoseconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TimingError {
+    /// The data length must be between 1 and 8 bytes.
+    InvalidLength(usize),
+    /// The header (break, delimiter, sync, and PID) exceeds `THeader_Maximum`,
+    /// 1.4 times the nominal 34 bit periods, e.g. because the break is too long.
+    HeaderTooLong { header_ns: u64, max_ns: u64 },
+    /// The header and the response timeout exceed `TFrame_Maximum`, the sum of the maximum header time
+    /// and 1.4 times the nominal 10 bit periods per response byte, so a frame does not complete in time.
+    FrameTooLong { frame_ns: u64, max_ns: u64 },
 }
 
 impl Default for LinBusConfig {
     fn default() -> Self {
         LinBusConfig {
             speed: LinBusSpeed::Baud19200,
+            uart_framing: UartFraming::EIGHT_N_ONE,
             break_duration: LinBreakDuration::Minimum13Bits,
+            break_method: BreakMethod::BreakPin,
+            break_includes_delimiter: false,
             wakeup_duration: LinWakeupDuration::Minimum250Microseconds,
+            wakeup_method: WakeupMethod::Pulse,
+            wakeup_settle: LinDelay::DelayMilliseconds(100),
+            post_wakeup_break_frames: 0,
+            post_wakeup_break_extension: 0,
             read_device_response_timeout: LinReadDeviceResponseTimeout::DelayMilliseconds(2),
+            checksum_wait: LinDelay::None,
             inter_frame_space: LinInterFrameSpace::DelayMilliseconds(1),
+            inter_byte_space: LinDelay::None,
+            bus_idle_before_break: LinDelay::DelayMicroseconds(100),
+            flush_fallback: FlushFallback::Fail,
+            reset_idle: LinDelay::DelayMilliseconds(10),
+            sleep_verify: LinDelay::None,
+            enable_settle: LinDelay::DelayMicroseconds(250),
+            auto_enable: false,
+            break_byte_tolerance: 1,
+            max_sync_bytes: 1,
+            fuzzy_sync: false,
+            clear_rx_before_read: false,
+            expect_header_echo: true,
+            header_skip_bytes: 2,
+            frame_end_detection: FrameEndDetection::WouldBlock,
+            auto_pid: false,
         }
     }
 }
@@ -143,6 +544,7 @@ mod tests {
             wakeup_duration: LinWakeupDuration::Minimum250Microseconds,
             read_device_response_timeout: LinReadDeviceResponseTimeout::DelayMilliseconds(2),
             inter_frame_space: LinInterFrameSpace::DelayMilliseconds(1),
+            ..Default::default()
         };
 
         assert_eq!(config.break_duration.get_duration_ns(52_083), 677_079);
@@ -165,13 +567,28 @@ mod tests {
     fn test_default_config() {
         let config = LinBusConfig::default();
         assert_eq!(config.speed, LinBusSpeed::Baud19200);
+        assert_eq!(config.uart_framing, UartFraming::EIGHT_N_ONE);
         assert_eq!(config.break_duration, LinBreakDuration::Minimum13Bits);
+        assert_eq!(config.break_method, BreakMethod::BreakPin);
+        assert!(!config.break_includes_delimiter);
         assert_eq!(config.wakeup_duration, LinWakeupDuration::Minimum250Microseconds);
+        assert_eq!(config.wakeup_settle, LinDelay::DelayMilliseconds(100));
         assert_eq!(
             config.read_device_response_timeout,
             LinReadDeviceResponseTimeout::DelayMilliseconds(2)
         );
         assert_eq!(config.inter_frame_space, LinInterFrameSpace::DelayMilliseconds(1));
+        assert_eq!(config.inter_byte_space, LinDelay::None);
+        assert_eq!(config.flush_fallback, FlushFallback::Fail);
+        assert_eq!(config.reset_idle, LinDelay::DelayMilliseconds(10));
+        assert_eq!(config.enable_settle, LinDelay::DelayMicroseconds(250));
+        assert!(!config.auto_enable);
+        assert_eq!(config.break_byte_tolerance, 1);
+        assert_eq!(config.max_sync_bytes, 1);
+        assert!(!config.clear_rx_before_read);
+        assert!(config.expect_header_echo);
+        assert_eq!(config.header_skip_bytes, 2);
+        assert!(!config.auto_pid);
     }
 
     #[test]
@@ -201,16 +618,62 @@ mod tests {
         assert_eq!(wakeup.get_duration_ns(), 5_000_000);
     }
 
+    #[test]
+    fn test_wakeup_settle() {
+        let settle = LinDelay::None;
+        assert_eq!(settle.get_duration_ns(), 0);
+
+        let settle = LinDelay::DelayMicroseconds(100);
+        assert_eq!(settle.get_duration_ns(), 100_000);
+
+        let settle = LinDelay::DelayMilliseconds(100);
+        assert_eq!(settle.get_duration_ns(), 100_000_000);
+    }
+
+    #[test]
+    fn test_reset_idle() {
+        let idle = LinDelay::None;
+        assert_eq!(idle.get_duration_ns(), 0);
+
+        let idle = LinDelay::DelayMicroseconds(100);
+        assert_eq!(idle.get_duration_ns(), 100_000);
+
+        let idle = LinDelay::DelayMilliseconds(10);
+        assert_eq!(idle.get_duration_ns(), 10_000_000);
+    }
+
     #[test]
     fn test_read_device_response_timeout() {
         let timeout = LinReadDeviceResponseTimeout::None;
-        assert_eq!(timeout.get_duration_ns(), 0);
+        assert_eq!(timeout.get_duration_ns(52_083, 8), 0);
 
         let timeout = LinReadDeviceResponseTimeout::DelayMicroseconds(100);
-        assert_eq!(timeout.get_duration_ns(), 100_000);
+        assert_eq!(timeout.get_duration_ns(52_083, 8), 100_000);
 
         let timeout = LinReadDeviceResponseTimeout::DelayMilliseconds(5);
-        assert_eq!(timeout.get_duration_ns(), 5_000_000);
+        assert_eq!(timeout.get_duration_ns(52_083, 8), 5_000_000);
+    }
+
+    #[test]
+    fn test_read_device_response_timeout_per_byte() {
+        let timeout = LinReadDeviceResponseTimeout::PerByteBitPeriods(14);
+        // 2 data bytes and the checksum
+        assert_eq!(timeout.get_duration_ns(52_083, 2), 3 * 14 * 52_083);
+        // 8 data bytes and the checksum
+        assert_eq!(timeout.get_duration_ns(52_083, 8), 9 * 14 * 52_083);
+        assert_eq!(timeout.get_duration_ns(100_000, 8), 12_600_000);
+    }
+
+    #[test]
+    fn test_inter_byte_space() {
+        let space = LinDelay::None;
+        assert_eq!(space.get_duration_ns(), 0);
+
+        let space = LinDelay::DelayMicroseconds(100);
+        assert_eq!(space.get_duration_ns(), 100_000);
+
+        let space = LinDelay::DelayMilliseconds(1);
+        assert_eq!(space.get_duration_ns(), 1_000_000);
     }
 
     #[test]
@@ -223,5 +686,127 @@ mod tests {
 
         let space = LinInterFrameSpace::DelayMilliseconds(5);
         assert_eq!(space.get_duration_ns(), 5_000_000);
+
+        // Longer than the u32 nanoseconds of a single delay
+        let space = LinInterFrameSpace::DelayMilliseconds(5_000);
+        assert_eq!(space.get_duration_ns(), 5_000_000_000);
+    }
+
+    #[test]
+    fn test_nominal_frame_time() {
+        let config = LinBusConfig::default();
+        // Break (13 bits) + delimiter (1 bit)
+        assert_eq!(config.nominal_break_time_ns(), 677_079 + 52_083);
+        // Break + delimiter + sync and PID (20 bits)
+        assert_eq!(config.nominal_header_time_ns(), 677_079 + 52_083 + 1_041_660);
+        // Header + 8 data bytes and checksum (90 bits)
+        assert_eq!(config.nominal_frame_time_ns(8), 1_770_822 + 4_687_470);
+    }
+
+    #[test]
+    fn test_uart_framing() {
+        assert_eq!(UartFraming::default().bits_per_byte(), 10);
+        assert_eq!(UartFraming::EIGHT_E_ONE.bits_per_byte(), 11);
+        assert_eq!(UartFraming::EIGHT_N_TWO.bits_per_byte(), 11);
+
+        let config = LinBusConfig {
+            uart_framing: UartFraming::EIGHT_E_ONE,
+            ..LinBusConfig::default()
+        };
+        assert_eq!(config.byte_time_ns(), 572_913);
+        // Break + delimiter + sync and PID (22 bits)
+        assert_eq!(config.nominal_header_time_ns(), 677_079 + 52_083 + 2 * 572_913);
+        // Header + 8 data bytes and checksum (99 bits)
+        assert_eq!(
+            config.nominal_frame_time_ns(8),
+            config.nominal_header_time_ns() + 9 * 572_913
+        );
+
+        let config = LinBusConfig {
+            uart_framing: UartFraming::EIGHT_N_TWO,
+            ..LinBusConfig::default()
+        };
+        assert_eq!(config.nominal_frame_time_ns(1), 677_079 + 52_083 + 4 * 572_913);
+        // 14 bit periods per 10 bit byte are 15.4 bit periods per 11 bit byte
+        let timeout = LinReadDeviceResponseTimeout::PerByteBitPeriods(14);
+        assert_eq!(
+            timeout.get_duration_ns_framed(52_083, 1, config.uart_framing),
+            2 * 14 * 52_083 * 11 / 10
+        );
+        assert_eq!(timeout.get_duration_ns(52_083, 1), 2 * 14 * 52_083);
+    }
+
+    #[test]
+    fn test_validate_frame_timing() {
+        for data_len in 1..=8 {
+            assert_eq!(LinBusConfig::default().validate_frame_timing(data_len), Ok(()));
+            assert_eq!(LinBusConfig::automotive_19200().validate_frame_timing(data_len), Ok(()));
+        }
+        assert_eq!(
+            LinBusConfig::default().validate_frame_timing(0),
+            Err(TimingError::InvalidLength(0))
+        );
+        assert_eq!(
+            LinBusConfig::default().validate_frame_timing(9),
+            Err(TimingError::InvalidLength(9))
+        );
+
+        // A 26 bit break still fits in the 47.6 bit periods of the maximum header, a 27 bit break does not
+        let mut config = LinBusConfig {
+            break_duration: LinBreakDuration::Minimum13BitsPlus(13),
+            ..LinBusConfig::default()
+        };
+        assert_eq!(config.validate_frame_timing(8), Ok(()));
+        config.break_duration = LinBreakDuration::Minimum13BitsPlus(14);
+        assert_eq!(
+            config.validate_frame_timing(8),
+            Err(TimingError::HeaderTooLong {
+                header_ns: 48 * 52_083,
+                max_ns: 2_479_150,
+            })
+        );
+
+        // 15 ms is longer than any frame at 19200 baud may take
+        let config = LinBusConfig {
+            read_device_response_timeout: LinReadDeviceResponseTimeout::DelayMilliseconds(15),
+            ..LinBusConfig::default()
+        };
+        assert_eq!(
+            config.validate_frame_timing(8),
+            Err(TimingError::FrameTooLong {
+                frame_ns: 34 * 52_083 + 15_000_000,
+                max_ns: 2_479_150 + 6_562_458,
+            })
+        );
+    }
+
+    #[test]
+    fn test_presets() {
+        let config = LinBusConfig::automotive_19200();
+        assert_eq!(config.speed, LinBusSpeed::Baud19200);
+        assert_eq!(
+            config.read_device_response_timeout,
+            LinReadDeviceResponseTimeout::PerByteBitPeriods(14)
+        );
+
+        let config = LinBusConfig::comfort_9600();
+        assert_eq!(config.speed, LinBusSpeed::Baud9600);
+        assert_eq!(config.break_duration, LinBreakDuration::Minimum13BitsPlus(1));
+        assert_eq!(config.wakeup_settle, LinDelay::DelayMilliseconds(100));
+
+        assert_eq!(LinBusConfig::sae_j2602_10400().speed, LinBusSpeed::Baud10400);
+    }
+
+    #[test]
+    fn test_break_includes_delimiter() {
+        let mut config = LinBusConfig::default();
+        // 13 bits dominant, then the delimiter: 14 bits in total
+        assert_eq!(config.break_dominant_time_ns(), 677_079);
+        assert_eq!(config.nominal_break_time_ns(), 677_079 + 52_083);
+
+        // 12 bits dominant, then the delimiter: 13 bits in total
+        config.break_includes_delimiter = true;
+        assert_eq!(config.break_dominant_time_ns(), 677_079 - 52_083);
+        assert_eq!(config.nominal_break_time_ns(), 677_079);
     }
 }
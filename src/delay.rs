@@ -0,0 +1,71 @@
+//! Sharing one delay peripheral between driver instances.
+//!
+//! Each `Mcp2003a` takes ownership of its delay. On an MCU with two LIN buses but one delay peripheral,
+//! put the delay in a `RefCell` and give each driver a `SharedDelay` borrowing it:
+//!
+//! ```rust,ignore
+//! let delay = RefCell::new(delay);
+//! let mut lin1 = Mcp2003a::new(uart1, break_pin1, SharedDelay::new(&delay));
+//! let mut lin2 = Mcp2003a::new(uart2, break_pin2, SharedDelay::new(&delay));
+//! ```
+
+use core::cell::RefCell;
+
+use embedded_hal::delay::DelayNs;
+#[cfg(feature = "async")]
+use embedded_hal_async::delay::DelayNs as AsyncDelayNs;
+
+/// Delay borrowing a delay shared through a `RefCell`, so several drivers can use the same delay peripheral.
+///
+/// - Note: The inner delay is borrowed for the whole of each delay. With the async delay, the drivers sharing it
+///   must not wait at the same time (e.g. from concurrent tasks), or the second borrow panics.
+pub struct SharedDelay<'a, D> {
+    inner: &'a RefCell<D>,
+}
+
+impl<'a, D> SharedDelay<'a, D> {
+    /// Create a delay borrowing the given shared delay.
+    pub fn new(inner: &'a RefCell<D>) -> Self {
+        SharedDelay { inner }
+    }
+}
+
+impl<D: DelayNs> DelayNs for SharedDelay<'_, D> {
+    fn delay_ns(&mut self, ns: u32) {
+        self.inner.borrow_mut().delay_ns(ns);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<D: AsyncDelayNs> AsyncDelayNs for SharedDelay<'_, D> {
+    #[allow(clippy::await_holding_refcell_ref)]
+    async fn delay_ns(&mut self, ns: u32) {
+        self.inner.borrow_mut().delay_ns(ns).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct CountingDelay {
+        total_ns: u64,
+    }
+
+    impl DelayNs for CountingDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.total_ns += ns as u64;
+        }
+    }
+
+    #[test]
+    fn test_shared_delay() {
+        let delay = RefCell::new(CountingDelay { total_ns: 0 });
+        let mut first = SharedDelay::new(&delay);
+        let mut second = SharedDelay::new(&delay);
+
+        first.delay_us(10);
+        second.delay_ns(500);
+        assert_eq!(delay.borrow().total_ns, 10_500);
+    }
+}
@@ -0,0 +1,160 @@
+//! Diagnostics for validating the wiring and timing of a LIN bus during integration.
+//!
+//! `run_conformance` triggers each error condition the driver handles against a cooperative test slave,
+//! and checks the driver reports it. The test slave must be set up to answer the IDs of a `ConformanceSlave`:
+//!
+//! ```rust,ignore
+//! let report = mcp2003a.run_conformance(&ConformanceSlave {
+//!     valid_id: 0xC1,
+//!     partial_id: 0x42,
+//!     bad_checksum_id: 0x03,
+//!     silent_id: 0xC4,
+//!     response_len: 4,
+//!     checksum_mode: ChecksumMode::Enhanced,
+//! });
+//! assert!(report.all_passed());
+//! ```
+//!
+//! With the `transport` feature, `services` adds the standard diagnostic services over the LIN transport layer.
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+use embedded_hal_nb::serial::{Read as UartRead, Write as UartWrite};
+
+use crate::frame::{ChecksumMode, MAX_DATA_LEN};
+use crate::{Mcp2003a, Mcp2003aError};
+
+#[cfg(feature = "transport")]
+pub mod services;
+
+/// The IDs a cooperative test slave answers for `run_conformance`, each triggering one condition.
+/// The ids must be ready to send (i.e. the PID if needed for your LIN version).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConformanceSlave {
+    /// ID the slave answers with `response_len` data bytes and a valid checksum.
+    pub valid_id: u8,
+    /// ID the slave answers with fewer than `response_len` data bytes.
+    pub partial_id: u8,
+    /// ID the slave answers with `response_len` data bytes and an invalid checksum.
+    pub bad_checksum_id: u8,
+    /// ID no node on the bus answers.
+    pub silent_id: u8,
+    /// Number of data bytes (1 to 8) of a complete response.
+    pub response_len: usize,
+    /// Checksum mode of the responses of the slave.
+    pub checksum_mode: ChecksumMode,
+}
+
+/// Result of each check of `run_conformance`, true when the driver handled the condition as expected.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ConformanceReport {
+    /// A complete response with a valid checksum was read successfully.
+    pub valid_response: bool,
+    /// A response with missing data bytes was reported as `LinReadOnlyPartialResponse`.
+    pub partial_response: bool,
+    /// A response with an invalid checksum was reported as `LinReadInvalidChecksum`.
+    pub checksum_mismatch: bool,
+    /// A header nobody answered was reported as `LinReadDeviceTimeoutNoResponse`.
+    pub no_response: bool,
+}
+
+impl ConformanceReport {
+    /// Whether every check passed.
+    pub fn all_passed(&self) -> bool {
+        self.valid_response && self.partial_response && self.checksum_mismatch && self.no_response
+    }
+}
+
+impl<UART, GPIO, DELAY, E> Mcp2003a<UART, GPIO, DELAY>
+where
+    UART: UartRead<Error = E> + UartWrite<Error = E>,
+    E: embedded_hal_nb::serial::Error,
+    GPIO: OutputPin,
+    DELAY: DelayNs,
+{
+    /// Read a frame from each ID of the cooperative test slave in turn, checking the driver reports
+    /// the condition each ID triggers. Returns a pass or fail for each check.
+    ///
+    /// - Note: Panics if the `response_len` is not between 1 and 8.
+    pub fn run_conformance(&mut self, slave: &ConformanceSlave) -> ConformanceReport {
+        assert!(
+            (1..=MAX_DATA_LEN).contains(&slave.response_len),
+            "Response length must be between 1 and 8 bytes"
+        );
+        let mut buffer = [0u8; MAX_DATA_LEN];
+        let buffer = &mut buffer[..slave.response_len];
+        let valid_response = self
+            .read_frame_validated(slave.valid_id, buffer, slave.checksum_mode)
+            .is_ok();
+
+        let partial_response = matches!(
+            self.read_frame(slave.partial_id, buffer),
+            Err(Mcp2003aError::LinReadOnlyPartialResponse { .. })
+        );
+
+        let checksum_mismatch = matches!(
+            self.read_frame_validated(slave.bad_checksum_id, buffer, slave.checksum_mode),
+            Err(Mcp2003aError::LinReadInvalidChecksum(_))
+        );
+
+        let no_response = matches!(
+            self.read_frame(slave.silent_id, buffer),
+            Err(Mcp2003aError::LinReadDeviceTimeoutNoResponse)
+        );
+
+        ConformanceReport {
+            valid_response,
+            partial_response,
+            checksum_mismatch,
+            no_response,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::tests::mock_mcp2003a;
+
+    #[test]
+    fn test_run_conformance() {
+        let slave = ConformanceSlave {
+            valid_id: 0xC1,
+            partial_id: 0x42,
+            bad_checksum_id: 0x03,
+            silent_id: 0xC4,
+            response_len: 2,
+            checksum_mode: ChecksumMode::Enhanced,
+        };
+        let valid_checksum = ChecksumMode::Enhanced.get_checksum(0xC1, &[0x01, 0x02]);
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx = std::vec![
+            0x55,
+            0xC1,
+            0x01,
+            0x02,
+            valid_checksum, // Valid response
+            0x55,
+            0x42,
+            0x01, // Partial response
+            0x55,
+            0x03,
+            0x01,
+            0x02,
+            0x00, // Invalid checksum
+            0x55,
+            0xC4, // No response
+        ];
+        // The partial response ends before the next header
+        mcp2003a.uart.rx_would_block_after = std::vec![8];
+
+        let report = mcp2003a.run_conformance(&slave);
+        assert!(report.all_passed(), "{:?}", report);
+
+        // Nothing answers
+        let report = mcp2003a.run_conformance(&slave);
+        assert_eq!(report, ConformanceReport::default());
+    }
+}
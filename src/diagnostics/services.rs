@@ -0,0 +1,649 @@
+//! LIN diagnostic services over the transport layer, with the `transport` feature.
+//!
+//! Requests are sent to a slave node in master request frames (ID 0x3C), and the slave answers in slave response
+//! frames (ID 0x3D), both with the classic checksum. Each frame starts with the node address (NAD) and the
+//! protocol control information (PCI). Requests and responses of up to 6 bytes fit in a single frame,
+//! longer ones are segmented in a first frame and consecutive frames.
+//!
+//! ```rust,ignore
+//! let mut data = [0u8; 16];
+//! let len = mcp2003a.read_data_by_identifier(0x0A, 0xF190, &mut data)?;
+//! log::info!("VIN: {:?}", &data[..len]);
+//! ```
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+use embedded_hal_nb::serial::{Read as UartRead, Write as UartWrite};
+
+use crate::frame::ChecksumMode;
+use crate::{Mcp2003a, Mcp2003aError};
+
+/// NAD addressing all slave nodes at once.
+pub const NAD_BROADCAST: u8 = 0x7F;
+
+/// PID of the master request frame (ID 0x3C).
+const MASTER_REQUEST_PID: u8 = 0x3C;
+/// PID of the slave response frame (ID 0x3D).
+const SLAVE_RESPONSE_PID: u8 = 0x7D;
+/// Service ID of a negative response.
+const NEGATIVE_RESPONSE_SID: u8 = 0x7F;
+/// Largest request the transport layer can segment, in bytes: the 12-bit length of the first frame.
+const MAX_REQUEST_LEN: usize = 4095;
+/// Largest request or response the services handle, in bytes including the service ID.
+const MAX_PAYLOAD_LEN: usize = 128;
+/// Time a slave has to send its response after its first ResponsePending (0x78) negative response,
+/// the default P2* server time of ISO 14229-2 (5000 ms).
+const RESPONSE_PENDING_TIMEOUT_NS: u64 = 5_000_000_000;
+
+/// Errors of a diagnostic service.
+#[derive(Debug)]
+pub enum DiagnosticError<E> {
+    /// Sending the request or reading the response frames failed.
+    Lin(Mcp2003aError<E>),
+    /// The slave rejected the request with a negative response.
+    /// Contains the service ID of the request and the negative response code (NRC).
+    NegativeResponse { service: u8, code: NegativeResponseCode },
+    /// The response frames were not a valid response to the request, e.g. from another node,
+    /// out of sequence, or for another service or identifier.
+    UnexpectedResponse,
+    /// The request or response does not fit in the buffer. Contains the required length in bytes.
+    BufferTooSmall(usize),
+    /// The request is empty, or longer than the 4095 bytes the first frame can announce.
+    /// Contains the length of the request in bytes.
+    InvalidRequestLength(usize),
+}
+
+impl<E> From<Mcp2003aError<E>> for DiagnosticError<E> {
+    fn from(error: Mcp2003aError<E>) -> Self {
+        DiagnosticError::Lin(error)
+    }
+}
+
+/// Negative response code (NRC) of a negative response, with the common codes of UDS and LIN diagnostics named.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NegativeResponseCode {
+    /// 0x10: The request was rejected without a more specific code.
+    GeneralReject,
+    /// 0x11: The service ID is not supported by the slave.
+    ServiceNotSupported,
+    /// 0x12: The sub-function is not supported by the slave.
+    SubFunctionNotSupported,
+    /// 0x13: The length or format of the request is invalid.
+    IncorrectMessageLengthOrInvalidFormat,
+    /// 0x21: The slave is busy, the request can be repeated later.
+    BusyRepeatRequest,
+    /// 0x22: The slave is not in a state to perform the request.
+    ConditionsNotCorrect,
+    /// 0x24: The request was received out of the expected sequence.
+    RequestSequenceError,
+    /// 0x31: A parameter of the request, e.g. the data identifier, is out of range.
+    RequestOutOfRange,
+    /// 0x33: The request needs security access that was not granted.
+    SecurityAccessDenied,
+    /// 0x35: The security key sent was invalid.
+    InvalidKey,
+    /// 0x72: Writing to the memory of the slave failed.
+    GeneralProgrammingFailure,
+    /// 0x78: The request was received, but the response will come later.
+    ResponsePending,
+    /// Any other code, e.g. manufacturer specific. Contains the raw code.
+    Other(u8),
+}
+
+impl NegativeResponseCode {
+    /// Get the raw code sent on the bus.
+    pub fn code(&self) -> u8 {
+        match self {
+            NegativeResponseCode::GeneralReject => 0x10,
+            NegativeResponseCode::ServiceNotSupported => 0x11,
+            NegativeResponseCode::SubFunctionNotSupported => 0x12,
+            NegativeResponseCode::IncorrectMessageLengthOrInvalidFormat => 0x13,
+            NegativeResponseCode::BusyRepeatRequest => 0x21,
+            NegativeResponseCode::ConditionsNotCorrect => 0x22,
+            NegativeResponseCode::RequestSequenceError => 0x24,
+            NegativeResponseCode::RequestOutOfRange => 0x31,
+            NegativeResponseCode::SecurityAccessDenied => 0x33,
+            NegativeResponseCode::InvalidKey => 0x35,
+            NegativeResponseCode::GeneralProgrammingFailure => 0x72,
+            NegativeResponseCode::ResponsePending => 0x78,
+            NegativeResponseCode::Other(code) => *code,
+        }
+    }
+}
+
+impl From<u8> for NegativeResponseCode {
+    fn from(code: u8) -> Self {
+        match code {
+            0x10 => NegativeResponseCode::GeneralReject,
+            0x11 => NegativeResponseCode::ServiceNotSupported,
+            0x12 => NegativeResponseCode::SubFunctionNotSupported,
+            0x13 => NegativeResponseCode::IncorrectMessageLengthOrInvalidFormat,
+            0x21 => NegativeResponseCode::BusyRepeatRequest,
+            0x22 => NegativeResponseCode::ConditionsNotCorrect,
+            0x24 => NegativeResponseCode::RequestSequenceError,
+            0x31 => NegativeResponseCode::RequestOutOfRange,
+            0x33 => NegativeResponseCode::SecurityAccessDenied,
+            0x35 => NegativeResponseCode::InvalidKey,
+            0x72 => NegativeResponseCode::GeneralProgrammingFailure,
+            0x78 => NegativeResponseCode::ResponsePending,
+            code => NegativeResponseCode::Other(code),
+        }
+    }
+}
+
+/// A negative response (service ID 0x7F) of a slave, rejecting a request.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NegativeResponse {
+    /// The service ID of the rejected request.
+    pub service: u8,
+    /// The negative response code (NRC) giving the reason.
+    pub code: NegativeResponseCode,
+}
+
+impl NegativeResponse {
+    /// Parse a negative response from a response payload: the service ID 0x7F, the service ID of the request,
+    /// then the code. Returns `None` if the payload is not a negative response.
+    pub fn parse(response: &[u8]) -> Option<Self> {
+        match response {
+            [NEGATIVE_RESPONSE_SID, service, code, ..] => Some(NegativeResponse {
+                service: *service,
+                code: NegativeResponseCode::from(*code),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Parse a negative response from a slave response frame: the NAD, a single frame PCI, then the payload.
+    /// Returns `None` if the frame is not a single frame negative response.
+    pub fn parse_frame(frame: &[u8; 8]) -> Option<Self> {
+        let len = (frame[1] & 0x0F) as usize;
+        if frame[1] >> 4 != 0x0 || !(3..=6).contains(&len) {
+            return None;
+        }
+        Self::parse(&frame[2..2 + len])
+    }
+}
+
+/// A diagnostic trouble code (DTC) reported by a slave, from `read_dtc`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Dtc {
+    /// The 3 byte trouble code.
+    pub code: u32,
+    /// The status byte of the trouble code.
+    pub status: u8,
+}
+
+impl<UART, GPIO, DELAY, E> Mcp2003a<UART, GPIO, DELAY>
+where
+    UART: UartRead<Error = E> + UartWrite<Error = E>,
+    E: embedded_hal_nb::serial::Error,
+    GPIO: OutputPin,
+    DELAY: DelayNs,
+{
+    /// Send a master request frame (ID 0x3C) with the given 8 bytes, using the classic checksum.
+    pub fn send_master_request(&mut self, request: &[u8; 8]) -> Result<(), Mcp2003aError<E>> {
+        let checksum = ChecksumMode::Classic.get_checksum(MASTER_REQUEST_PID, request);
+        self.send_frame(MASTER_REQUEST_PID, request, checksum)?;
+        Ok(())
+    }
+
+    /// Read a slave response frame (ID 0x3D) into the given 8 bytes, validating the classic checksum.
+    /// Returns the negative response if the frame is a single frame negative response, else `None`.
+    ///
+    /// - Note: Returns `LinReadInvalidChecksum` if the checksum is not the classic checksum of the data.
+    pub fn read_slave_response(
+        &mut self,
+        response: &mut [u8; 8],
+    ) -> Result<Option<NegativeResponse>, Mcp2003aError<E>> {
+        self.read_frame_validated(SLAVE_RESPONSE_PID, response, ChecksumMode::Classic)?;
+        Ok(NegativeResponse::parse_frame(response))
+    }
+
+    /// Send a diagnostic request to the node with the given NAD: the service ID then its parameters.
+    /// The request is sent in a single frame if it has up to 6 bytes, else segmented in a first frame
+    /// and consecutive frames. Unused bytes of the last frame are filled with 0xFF.
+    ///
+    /// - Note: Returns `InvalidRequestLength` if the request is empty or longer than 4095 bytes, without sending it.
+    pub fn send_diagnostic_request(&mut self, nad: u8, request: &[u8]) -> Result<(), DiagnosticError<E>> {
+        if !(1..=MAX_REQUEST_LEN).contains(&request.len()) {
+            return Err(DiagnosticError::InvalidRequestLength(request.len()));
+        }
+
+        let mut frame = [0xFF; 8];
+        frame[0] = nad;
+        if request.len() <= 6 {
+            // Single frame
+            frame[1] = request.len() as u8;
+            frame[2..2 + request.len()].copy_from_slice(request);
+            return Ok(self.send_master_request(&frame)?);
+        }
+
+        // First frame, with the 12-bit length
+        frame[1] = 0x10 | (request.len() >> 8) as u8;
+        frame[2] = request.len() as u8;
+        frame[3..].copy_from_slice(&request[..5]);
+        self.send_master_request(&frame)?;
+
+        // Consecutive frames, with a 4-bit sequence number starting at 1
+        for (i, chunk) in request[5..].chunks(6).enumerate() {
+            let mut frame = [0xFF; 8];
+            frame[0] = nad;
+            frame[1] = 0x20 | ((i + 1) & 0x0F) as u8;
+            frame[2..2 + chunk.len()].copy_from_slice(chunk);
+            self.send_master_request(&frame)?;
+        }
+        Ok(())
+    }
+
+    /// Read a diagnostic response from the node with the given NAD into the buffer, reassembling a segmented
+    /// response. Returns the length of the response: the response service ID then its parameters.
+    ///
+    /// - Note: Responses from another node than `nad` are `UnexpectedResponse`, unless `nad` is `NAD_BROADCAST`.
+    /// - Note: Negative responses are returned as a response like any other, see `diagnostic_service`.
+    /// - Note: A segmented response longer than the buffer is read to its last consecutive frame,
+    ///   then returned as `BufferTooSmall`.
+    pub fn read_diagnostic_response(&mut self, nad: u8, buffer: &mut [u8]) -> Result<usize, DiagnosticError<E>> {
+        let mut frame = [0u8; 8];
+        self.read_slave_response(&mut frame)?;
+        if nad != NAD_BROADCAST && frame[0] != nad {
+            return Err(DiagnosticError::UnexpectedResponse);
+        }
+
+        match frame[1] >> 4 {
+            // Single frame
+            0x0 => {
+                let len = (frame[1] & 0x0F) as usize;
+                if !(1..=6).contains(&len) {
+                    return Err(DiagnosticError::UnexpectedResponse);
+                }
+                if buffer.len() < len {
+                    return Err(DiagnosticError::BufferTooSmall(len));
+                }
+                buffer[..len].copy_from_slice(&frame[2..2 + len]);
+                Ok(len)
+            }
+            // First frame, followed by consecutive frames
+            0x1 => {
+                let len = ((frame[1] & 0x0F) as usize) << 8 | frame[2] as usize;
+                if len <= 6 {
+                    return Err(DiagnosticError::UnexpectedResponse);
+                }
+                // A response too long for the buffer is still read to its end, so its consecutive frames
+                // are not mistaken for the next response
+                let fits = buffer.len() >= len;
+                if fits {
+                    buffer[..5].copy_from_slice(&frame[3..]);
+                }
+
+                let mut received = 5;
+                let mut sequence = 1;
+                while received < len {
+                    self.read_slave_response(&mut frame)?;
+                    if (nad != NAD_BROADCAST && frame[0] != nad) || frame[1] != 0x20 | sequence {
+                        return Err(DiagnosticError::UnexpectedResponse);
+                    }
+                    let chunk = (len - received).min(6);
+                    if fits {
+                        buffer[received..received + chunk].copy_from_slice(&frame[2..2 + chunk]);
+                    }
+                    received += chunk;
+                    sequence = (sequence + 1) & 0x0F;
+                }
+
+                if !fits {
+                    return Err(DiagnosticError::BufferTooSmall(len));
+                }
+                Ok(len)
+            }
+            _ => Err(DiagnosticError::UnexpectedResponse),
+        }
+    }
+
+    /// Send a diagnostic request to the node with the given NAD, then read its response into the buffer.
+    /// Returns the length of the positive response: the response service ID (the request service ID + 0x40)
+    /// then its parameters.
+    ///
+    /// - Note: A negative response is returned as `NegativeResponse` with its code.
+    /// - Note: Returns `InvalidRequestLength` if the request is empty or longer than 4095 bytes, without sending it.
+    /// - Note: After a ResponsePending (0x78) negative response, the slave response frame is polled again until
+    ///   the final response, for up to the P2* time of 5000 ms. A slave not answering within it is returned as
+    ///   `NegativeResponse` with the ResponsePending code.
+    pub fn diagnostic_service(
+        &mut self,
+        nad: u8,
+        request: &[u8],
+        response: &mut [u8],
+    ) -> Result<usize, DiagnosticError<E>> {
+        let service = match request.first() {
+            Some(service) => *service,
+            None => return Err(DiagnosticError::InvalidRequestLength(0)),
+        };
+        self.send_diagnostic_request(nad, request)?;
+
+        let mut pending_ns = None;
+        let len = loop {
+            match self.read_diagnostic_response(nad, response) {
+                Ok(len) => match NegativeResponse::parse(&response[..len]) {
+                    Some(negative) if negative.service == service => {
+                        if negative.code != NegativeResponseCode::ResponsePending {
+                            return Err(DiagnosticError::NegativeResponse {
+                                service,
+                                code: negative.code,
+                            });
+                        }
+                        pending_ns.get_or_insert(0);
+                    }
+                    _ => break len,
+                },
+                // A pending slave does not answer the headers polling it until its response is ready
+                Err(DiagnosticError::Lin(Mcp2003aError::LinReadDeviceTimeoutNoResponse)) if pending_ns.is_some() => {}
+                Err(e) => return Err(e),
+            }
+
+            let elapsed_ns = pending_ns.unwrap_or(0) + self.last_frame_duration_ns();
+            if elapsed_ns > RESPONSE_PENDING_TIMEOUT_NS {
+                return Err(DiagnosticError::NegativeResponse {
+                    service,
+                    code: NegativeResponseCode::ResponsePending,
+                });
+            }
+            pending_ns = Some(elapsed_ns);
+        };
+
+        if response[0] != service.wrapping_add(0x40) {
+            return Err(DiagnosticError::UnexpectedResponse);
+        }
+        Ok(len)
+    }
+
+    /// Read the data of the given data identifier (DID) from the node with the given NAD, with the
+    /// ReadDataByIdentifier service (0x22). Returns the length of the data written to `data`.
+    pub fn read_data_by_identifier(&mut self, nad: u8, id: u16, data: &mut [u8]) -> Result<usize, DiagnosticError<E>> {
+        let [id_high, id_low] = id.to_be_bytes();
+        let mut response = [0u8; MAX_PAYLOAD_LEN];
+        let len = self.diagnostic_service(nad, &[0x22, id_high, id_low], &mut response)?;
+        if len < 3 || response[1..3] != [id_high, id_low] {
+            return Err(DiagnosticError::UnexpectedResponse);
+        }
+
+        let value = &response[3..len];
+        if data.len() < value.len() {
+            return Err(DiagnosticError::BufferTooSmall(value.len()));
+        }
+        data[..value.len()].copy_from_slice(value);
+        Ok(value.len())
+    }
+
+    /// Write the data of the given data identifier (DID) to the node with the given NAD, with the
+    /// WriteDataByIdentifier service (0x2E).
+    pub fn write_data_by_identifier(&mut self, nad: u8, id: u16, data: &[u8]) -> Result<(), DiagnosticError<E>> {
+        let [id_high, id_low] = id.to_be_bytes();
+        let mut request = [0u8; MAX_PAYLOAD_LEN];
+        if data.len() + 3 > request.len() {
+            return Err(DiagnosticError::BufferTooSmall(data.len() + 3));
+        }
+        request[..3].copy_from_slice(&[0x2E, id_high, id_low]);
+        request[3..3 + data.len()].copy_from_slice(data);
+
+        let mut response = [0u8; 8];
+        let len = self.diagnostic_service(nad, &request[..3 + data.len()], &mut response)?;
+        if len < 3 || response[1..3] != [id_high, id_low] {
+            return Err(DiagnosticError::UnexpectedResponse);
+        }
+        Ok(())
+    }
+
+    /// Read the diagnostic trouble codes (DTCs) of the node with the given NAD, with the ReadDTCInformation
+    /// service (0x19) reporting the DTCs matching the status mask 0xFF. Returns the number of DTCs written to `dtcs`.
+    pub fn read_dtc(&mut self, nad: u8, dtcs: &mut [Dtc]) -> Result<usize, DiagnosticError<E>> {
+        let mut response = [0u8; MAX_PAYLOAD_LEN];
+        let len = self.diagnostic_service(nad, &[0x19, 0x02, 0xFF], &mut response)?;
+        // The sub-function and the status availability mask come before the DTC records
+        if len < 3 || response[1] != 0x02 || (len - 3) % 4 != 0 {
+            return Err(DiagnosticError::UnexpectedResponse);
+        }
+
+        let count = (len - 3) / 4;
+        if dtcs.len() < count {
+            return Err(DiagnosticError::BufferTooSmall(count));
+        }
+        for (dtc, record) in dtcs.iter_mut().zip(response[3..len].chunks(4)) {
+            *dtc = Dtc {
+                code: u32::from_be_bytes([0, record[0], record[1], record[2]]),
+                status: record[3],
+            };
+        }
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::tests::mock_mcp2003a;
+    use std::vec::Vec;
+
+    /// Raw bytes of slave response frames as read back: the header echo, the data, and the classic checksum.
+    fn slave_responses(frames: &[[u8; 8]]) -> Vec<u8> {
+        let mut rx = Vec::new();
+        for frame in frames.iter() {
+            rx.extend_from_slice(&[0x55, SLAVE_RESPONSE_PID]);
+            rx.extend_from_slice(frame);
+            rx.push(ChecksumMode::Classic.get_checksum(SLAVE_RESPONSE_PID, frame));
+        }
+        rx
+    }
+
+    #[test]
+    fn test_read_data_by_identifier() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx = slave_responses(&[[0x0A, 0x05, 0x62, 0xF1, 0x90, 0x12, 0x34, 0xFF]]);
+
+        let mut data = [0u8; 4];
+        assert_eq!(mcp2003a.read_data_by_identifier(0x0A, 0xF190, &mut data).unwrap(), 2);
+        assert_eq!(&data[..2], &[0x12, 0x34]);
+        let checksum = ChecksumMode::Classic.get_checksum(0x3C, &[0x0A, 0x03, 0x22, 0xF1, 0x90, 0xFF, 0xFF, 0xFF]);
+        assert_eq!(
+            &mcp2003a.uart.tx,
+            &[0x55, 0x3C, 0x0A, 0x03, 0x22, 0xF1, 0x90, 0xFF, 0xFF, 0xFF, checksum, 0x55, 0x7D]
+        );
+    }
+
+    #[test]
+    fn test_segmented_request_and_response() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx = slave_responses(&[
+            [0x0A, 0x10, 0x09, 0x62, 0xF1, 0x90, 0x01, 0x02],
+            [0x0A, 0x21, 0x03, 0x04, 0x05, 0x06, 0xFF, 0xFF],
+        ]);
+        let mut data = [0u8; 8];
+        assert_eq!(mcp2003a.read_data_by_identifier(0x0A, 0xF190, &mut data).unwrap(), 6);
+        assert_eq!(&data[..6], &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+
+        mcp2003a.uart.tx.clear();
+        mcp2003a.uart.rx = slave_responses(&[[0x0A, 0x03, 0x6E, 0x01, 0x02, 0xFF, 0xFF, 0xFF]]);
+        mcp2003a
+            .write_data_by_identifier(0x0A, 0x0102, &[0x11, 0x22, 0x33, 0x44])
+            .unwrap();
+        // First frame then one consecutive frame
+        assert_eq!(
+            &mcp2003a.uart.tx[2..10],
+            &[0x0A, 0x10, 0x07, 0x2E, 0x01, 0x02, 0x11, 0x22]
+        );
+        assert_eq!(
+            &mcp2003a.uart.tx[13..21],
+            &[0x0A, 0x21, 0x33, 0x44, 0xFF, 0xFF, 0xFF, 0xFF]
+        );
+
+        // A response too long for the buffer is still read to its end
+        mcp2003a.uart.rx = slave_responses(&[
+            [0x0A, 0x10, 0x09, 0x62, 0xF1, 0x90, 0x01, 0x02],
+            [0x0A, 0x21, 0x03, 0x04, 0x05, 0x06, 0xFF, 0xFF],
+        ]);
+        mcp2003a.send_diagnostic_request(0x0A, &[0x22, 0xF1, 0x90]).unwrap();
+        let mut response = [0u8; 8];
+        assert!(matches!(
+            mcp2003a.read_diagnostic_response(0x0A, &mut response),
+            Err(DiagnosticError::BufferTooSmall(9))
+        ));
+        assert!(mcp2003a.uart.rx.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_request_length() {
+        let mut mcp2003a = mock_mcp2003a();
+        assert!(matches!(
+            mcp2003a.send_diagnostic_request(0x0A, &[]),
+            Err(DiagnosticError::InvalidRequestLength(0))
+        ));
+        assert!(matches!(
+            mcp2003a.send_diagnostic_request(0x0A, &[0x2E; 4096]),
+            Err(DiagnosticError::InvalidRequestLength(4096))
+        ));
+        let mut response = [0u8; 8];
+        assert!(matches!(
+            mcp2003a.diagnostic_service(0x0A, &[], &mut response),
+            Err(DiagnosticError::InvalidRequestLength(0))
+        ));
+        // Nothing is sent
+        assert!(mcp2003a.uart.tx.is_empty());
+    }
+
+    #[test]
+    fn test_read_dtc() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx = slave_responses(&[
+            [0x0A, 0x10, 0x0B, 0x59, 0x02, 0xFF, 0x12, 0x34],
+            [0x0A, 0x21, 0x56, 0x09, 0xAB, 0xCD, 0xEF, 0x08],
+        ]);
+        let mut dtcs = [Dtc::default(); 4];
+        assert_eq!(mcp2003a.read_dtc(0x0A, &mut dtcs).unwrap(), 2);
+        assert_eq!(
+            dtcs[0],
+            Dtc {
+                code: 0x123456,
+                status: 0x09
+            }
+        );
+        assert_eq!(
+            dtcs[1],
+            Dtc {
+                code: 0xABCDEF,
+                status: 0x08
+            }
+        );
+    }
+
+    #[test]
+    fn test_negative_response_code() {
+        assert_eq!(
+            NegativeResponse::parse(&[0x7F, 0x22, 0x78]),
+            Some(NegativeResponse {
+                service: 0x22,
+                code: NegativeResponseCode::ResponsePending
+            })
+        );
+        assert_eq!(
+            NegativeResponse::parse(&[0x7F, 0x19, 0x11]).unwrap().code,
+            NegativeResponseCode::ServiceNotSupported
+        );
+        assert_eq!(NegativeResponseCode::from(0xF0), NegativeResponseCode::Other(0xF0));
+        for code in 0..=0xFF {
+            assert_eq!(NegativeResponseCode::from(code).code(), code);
+        }
+
+        // Positive responses, truncated payloads, and segmented frames are not negative responses
+        assert_eq!(NegativeResponse::parse(&[0x62, 0xF1, 0x90]), None);
+        assert_eq!(NegativeResponse::parse(&[0x7F, 0x22]), None);
+        assert_eq!(
+            NegativeResponse::parse_frame(&[0x0A, 0x10, 0x09, 0x7F, 0x22, 0x31, 0x00, 0x00]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_negative_response() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx = slave_responses(&[[0x0A, 0x03, 0x7F, 0x22, 0x31, 0xFF, 0xFF, 0xFF]]);
+        let mut data = [0u8; 4];
+        assert!(matches!(
+            mcp2003a.read_data_by_identifier(0x0A, 0xF190, &mut data),
+            Err(DiagnosticError::NegativeResponse {
+                service: 0x22,
+                code: NegativeResponseCode::RequestOutOfRange
+            })
+        ));
+
+        // The negative response is also returned by the frame read
+        let frame = [0x0A, 0x03, 0x7F, 0x2E, 0x33, 0xFF, 0xFF, 0xFF];
+        mcp2003a.uart.rx = slave_responses(&[frame]);
+        let mut response = [0u8; 8];
+        assert_eq!(
+            mcp2003a.read_slave_response(&mut response).unwrap(),
+            Some(NegativeResponse {
+                service: 0x2E,
+                code: NegativeResponseCode::SecurityAccessDenied
+            })
+        );
+        assert_eq!(response, frame);
+
+        // Response from another node
+        mcp2003a.uart.rx = slave_responses(&[[0x0B, 0x05, 0x62, 0xF1, 0x90, 0x12, 0x34, 0xFF]]);
+        assert!(matches!(
+            mcp2003a.read_data_by_identifier(0x0A, 0xF190, &mut data),
+            Err(DiagnosticError::UnexpectedResponse)
+        ));
+    }
+
+    #[test]
+    fn test_response_pending() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx = slave_responses(&[
+            [0x0A, 0x03, 0x7F, 0x22, 0x78, 0xFF, 0xFF, 0xFF],
+            [0x0A, 0x03, 0x7F, 0x22, 0x78, 0xFF, 0xFF, 0xFF],
+            [0x0A, 0x05, 0x62, 0xF1, 0x90, 0x12, 0x34, 0xFF],
+        ]);
+        let mut data = [0u8; 4];
+        assert_eq!(mcp2003a.read_data_by_identifier(0x0A, 0xF190, &mut data).unwrap(), 2);
+        assert_eq!(&data[..2], &[0x12, 0x34]);
+
+        // The slave does not answer the headers polling it while pending, until it has sent its final response
+        let mut rx = slave_responses(&[[0x0A, 0x03, 0x7F, 0x22, 0x78, 0xFF, 0xFF, 0xFF]]);
+        for _ in 0..3 {
+            rx.extend_from_slice(&[0x55, SLAVE_RESPONSE_PID]);
+        }
+        rx.extend(slave_responses(&[[0x0A, 0x05, 0x62, 0xF1, 0x90, 0x56, 0x78, 0xFF]]));
+        mcp2003a.uart.rx = rx;
+        // Full frames end at their checksum, only the silent headers pause the reads
+        mcp2003a.uart.rx_would_block_after = std::vec![13, 2, 2];
+        assert_eq!(mcp2003a.read_data_by_identifier(0x0A, 0xF190, &mut data).unwrap(), 2);
+        assert_eq!(&data[..2], &[0x56, 0x78]);
+
+        // A slave that never sends its final response is given up on after the P2* time
+        let mut rx = slave_responses(&[[0x0A, 0x03, 0x7F, 0x22, 0x78, 0xFF, 0xFF, 0xFF]]);
+        let polls = (RESPONSE_PENDING_TIMEOUT_NS / mcp2003a.last_frame_duration_ns()) as usize;
+        mcp2003a.uart.rx_would_block_after = std::vec![13];
+        for _ in 0..polls {
+            rx.extend_from_slice(&[0x55, SLAVE_RESPONSE_PID]);
+            mcp2003a.uart.rx_would_block_after.push(2);
+        }
+        mcp2003a.uart.rx = rx;
+        assert!(matches!(
+            mcp2003a.read_data_by_identifier(0x0A, 0xF190, &mut data),
+            Err(DiagnosticError::NegativeResponse {
+                service: 0x22,
+                code: NegativeResponseCode::ResponsePending
+            })
+        ));
+        assert!(mcp2003a.uart.rx.is_empty());
+
+        // Without a pending response, a slave not answering is still an error
+        mcp2003a.uart.rx = std::vec![0x55, SLAVE_RESPONSE_PID];
+        assert!(matches!(
+            mcp2003a.read_data_by_identifier(0x0A, 0xF190, &mut data),
+            Err(DiagnosticError::Lin(Mcp2003aError::LinReadDeviceTimeoutNoResponse))
+        ));
+    }
+}
@@ -0,0 +1,290 @@
+//! Break timing fixed at compile time, for the tightest loops of ultra-deterministic builds.
+//!
+//! `FixedTiming` wraps a configured driver with the baud rate and the break bit count as type parameters,
+//! so the break and the break delimiter are delayed by constants instead of being calculated from the configuration
+//! for each frame:
+//!
+//! ```rust,ignore
+//! let mcp2003a = Uninitialized::new(uart2_driver, break_pin_driver, delay).init(lin_bus_config);
+//! let mut mcp2003a: FixedTiming<_, _, _, 19200, 13> = FixedTiming::new(mcp2003a);
+//! mcp2003a.send_frame(0x01, &[0x02], 0x03)?;
+//! ```
+//!
+//! - Note: Only the break and the break delimiter are fixed. The bus idle time before the break, the inter-byte
+//!   space, and the inter-frame space are still read from the runtime configuration.
+//! - Note: The break is never extended after a wakeup, `post_wakeup_break_extension` is ignored. Frames sent
+//!   through `FixedTiming` do not count towards `post_wakeup_break_frames` of the inner driver either.
+//! - Note: The break is always generated with the break pin, as `BreakMethod::UartBreak` derives the break baud rate
+//!   at runtime. `new` sets the `break_method` of the runtime configuration to `BreakMethod::BreakPin` to match.
+//! - Note: The other operations are available through `inner_mut`, with the speed, break duration, and break method
+//!   of the runtime configuration matching the type parameters.
+//! - Note: With the `bench` feature, `bench_send_frame` counts the delays and bytes of a frame, to compare with the
+//!   runtime configured `Mcp2003a::bench_send_frame`.
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+use embedded_hal_nb::serial::{Read as UartRead, Write as UartWrite};
+
+#[cfg(feature = "async")]
+use embedded_hal_async::delay::DelayNs as AsyncDelayNs;
+#[cfg(feature = "async")]
+use embedded_io_async::Read as AsyncUartRead;
+#[cfg(feature = "async")]
+use embedded_io_async::Write as AsyncUartWrite;
+
+#[cfg(feature = "bench")]
+use crate::bench::{BenchCounts, CountingDelay, CountingUart};
+use crate::config::{BreakMethod, LinBreakDuration, LinBusSpeed};
+use crate::frame::MAX_FRAME_LEN;
+use crate::{delay_ns_u64, set_pin_level, validate_frame, Mcp2003a, Mcp2003aError};
+#[cfg(feature = "async")]
+use crate::{delay_ns_u64_async, DominantPin};
+
+/// A MCP2003A transceiver driver with the baud rate `BAUD` and the dominant bits of the break `BREAK_BITS`
+/// fixed at compile time.
+pub struct FixedTiming<UART, GPIO, DELAY, const BAUD: u32, const BREAK_BITS: u32> {
+    inner: Mcp2003a<UART, GPIO, DELAY>,
+}
+
+impl<UART, GPIO, DELAY, const BAUD: u32, const BREAK_BITS: u32> FixedTiming<UART, GPIO, DELAY, BAUD, BREAK_BITS> {
+    /// Bit period in nanoseconds at `BAUD`.
+    pub const BIT_PERIOD_NS: u32 = {
+        assert!(BAUD > 0, "Baud rate must not be zero");
+        1_000_000_000 / BAUD
+    };
+
+    /// Dominant time of the break in nanoseconds, `BREAK_BITS` bit periods.
+    pub const BREAK_NS: u32 = {
+        assert!(
+            BREAK_BITS >= 13 && BREAK_BITS <= 13 + u8::MAX as u32,
+            "Break must be at least 13 bits"
+        );
+        BREAK_BITS * Self::BIT_PERIOD_NS
+    };
+
+    /// Nominal time in nanoseconds of the break and the 1 bit break delimiter.
+    pub const NOMINAL_BREAK_NS: u64 = Self::BREAK_NS as u64 + Self::BIT_PERIOD_NS as u64;
+
+    /// Wrap the given driver, setting its configured speed and break duration to the type parameters
+    /// so the operations of the inner driver use the same timing, and its break method to the break pin.
+    ///
+    /// - Note: `break_includes_delimiter` is cleared, as `BREAK_BITS` only counts the dominant bits.
+    /// - Note: Fails to compile if `BREAK_BITS` is less than 13 or more than 268, or if `BAUD` is zero.
+    pub fn new(mut inner: Mcp2003a<UART, GPIO, DELAY>) -> Self {
+        let _ = Self::BREAK_NS;
+        inner.config.speed = LinBusSpeed::BaudBps(BAUD);
+        inner.config.break_duration = LinBreakDuration::Minimum13BitsPlus((BREAK_BITS - 13) as u8);
+        inner.config.break_includes_delimiter = false;
+        inner.config.break_method = BreakMethod::BreakPin;
+        FixedTiming { inner }
+    }
+
+    /// Get the wrapped driver.
+    pub fn inner(&self) -> &Mcp2003a<UART, GPIO, DELAY> {
+        &self.inner
+    }
+
+    /// Get the wrapped driver, for the operations without a fixed timing version.
+    pub fn inner_mut(&mut self) -> &mut Mcp2003a<UART, GPIO, DELAY> {
+        &mut self.inner
+    }
+
+    /// Release the wrapped driver.
+    pub fn into_inner(self) -> Mcp2003a<UART, GPIO, DELAY> {
+        self.inner
+    }
+}
+
+impl<UART, GPIO, DELAY, E, const BAUD: u32, const BREAK_BITS: u32> FixedTiming<UART, GPIO, DELAY, BAUD, BREAK_BITS>
+where
+    UART: UartRead<Error = E> + UartWrite<Error = E>,
+    E: embedded_hal_nb::serial::Error,
+    GPIO: OutputPin,
+    DELAY: DelayNs,
+{
+    /// Send a break of `BREAK_BITS` bit periods with the break pin, followed by the 1 bit break delimiter,
+    /// after waiting the configured `bus_idle_before_break`.
+    ///
+    /// - Note: The break is not extended after a wakeup, as its length is fixed at compile time.
+    pub fn send_break(&mut self) -> Result<(), Mcp2003aError<E>> {
+        self.inner.auto_enable()?;
+
+        // Let the bus settle after the previous transmission
+        delay_ns_u64(
+            &mut self.inner.delay,
+            self.inner.config.bus_idle_before_break.get_duration_ns(),
+        );
+
+        // Break for the duration computed at compile time
+        set_pin_level(&mut self.inner.break_pin, true)?;
+        self.inner.delay.delay_ns(Self::BREAK_NS);
+        set_pin_level(&mut self.inner.break_pin, false)?;
+
+        // Break delimiter is 1 bit time
+        self.inner.delay.delay_ns(Self::BIT_PERIOD_NS);
+
+        Ok(())
+    }
+
+    /// Send a frame on the LIN bus with the given ID, data, and checksum like `Mcp2003a::send_frame`,
+    /// with the break of `send_break`.
+    ///
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: You must calculate the checksum based on your application and LIN version.
+    /// - Note: Inter-frame space is applied after sending the frame.
+    pub fn send_frame(&mut self, id: u8, data: &[u8], checksum: u8) -> Result<[u8; MAX_FRAME_LEN], Mcp2003aError<E>> {
+        validate_frame(id, data, false)?;
+        self.send_break()?;
+        let frame = self.inner.send_frame_no_break(id, data, checksum)?;
+        self.inner.last_frame_duration_ns += Self::NOMINAL_BREAK_NS;
+        Ok(frame)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<UART, GPIO, DELAY, E, const BAUD: u32, const BREAK_BITS: u32> FixedTiming<UART, GPIO, DELAY, BAUD, BREAK_BITS>
+where
+    UART: AsyncUartRead<Error = E> + AsyncUartWrite<Error = E>,
+    GPIO: OutputPin,
+    DELAY: AsyncDelayNs,
+{
+    /// Send a break of `BREAK_BITS` bit periods with the break pin like `send_break`.
+    /// - Note: The break pin is driven back low if the future is dropped during the break.
+    /// - Note: The break is not extended after a wakeup, as its length is fixed at compile time.
+    /// - Note: This function is async to allow for the delay to be async.
+    pub async fn send_break_async(&mut self) -> Result<(), Mcp2003aError<E>> {
+        self.inner.auto_enable_async().await?;
+
+        // Let the bus settle after the previous transmission
+        delay_ns_u64_async(
+            &mut self.inner.delay,
+            self.inner.config.bus_idle_before_break.get_duration_ns(),
+        )
+        .await;
+
+        // Break for the duration computed at compile time, ended even if the future is dropped during it
+        let break_pin = DominantPin::set_high(&mut self.inner.break_pin)?;
+        self.inner.delay.delay_ns(Self::BREAK_NS).await;
+        break_pin.release()?;
+
+        // Break delimiter is 1 bit time
+        self.inner.delay.delay_ns(Self::BIT_PERIOD_NS).await;
+
+        Ok(())
+    }
+
+    /// Send a frame on the LIN bus like `send_frame`, with the break of `send_break_async`.
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: Inter-frame space is applied after sending the frame.
+    /// - Note: This function is async to allow for the delay and serial write to be async.
+    pub async fn send_frame_async(
+        &mut self,
+        id: u8,
+        data: &[u8],
+        checksum: u8,
+    ) -> Result<[u8; MAX_FRAME_LEN], Mcp2003aError<E>> {
+        validate_frame(id, data, false)?;
+        self.send_break_async().await?;
+        let frame = self.inner.send_frame_no_break_async(id, data, checksum).await?;
+        self.inner.last_frame_duration_ns += Self::NOMINAL_BREAK_NS;
+        Ok(frame)
+    }
+}
+
+#[cfg(feature = "bench")]
+impl<UART, GPIO, DELAY, E, const BAUD: u32, const BREAK_BITS: u32>
+    FixedTiming<CountingUart<UART>, GPIO, CountingDelay<DELAY>, BAUD, BREAK_BITS>
+where
+    UART: UartRead<Error = E> + UartWrite<Error = E>,
+    E: embedded_hal_nb::serial::Error,
+    GPIO: OutputPin,
+    DELAY: DelayNs,
+{
+    /// Send a frame like `send_frame`, returning its result and the counts of the call alone,
+    /// like `Mcp2003a::bench_send_frame` for the runtime configured break.
+    pub fn bench_send_frame(
+        &mut self,
+        id: u8,
+        data: &[u8],
+        checksum: u8,
+    ) -> (Result<[u8; MAX_FRAME_LEN], Mcp2003aError<E>>, BenchCounts) {
+        self.inner.reset_bench_counts();
+        let result = self.send_frame(id, data, checksum);
+        (result, self.inner.bench_counts())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "async")]
+    use crate::tests::block_on;
+    use crate::tests::mock_mcp2003a;
+
+    #[test]
+    fn test_fixed_timing() {
+        assert_eq!(FixedTiming::<(), (), (), 19200, 13>::BIT_PERIOD_NS, 52_083);
+        assert_eq!(FixedTiming::<(), (), (), 19200, 13>::BREAK_NS, 677_079);
+        assert_eq!(FixedTiming::<(), (), (), 9600, 14>::BREAK_NS, 1_458_324);
+
+        // Same bytes and delays as the runtime configured driver
+        let mut runtime = mock_mcp2003a();
+        runtime.send_frame(0x80, &[0x01], 0x7D).unwrap();
+
+        let mut fixed: FixedTiming<_, _, _, 19200, 13> = FixedTiming::new(mock_mcp2003a());
+        let frame = fixed.send_frame(0x80, &[0x01], 0x7D).unwrap();
+        assert_eq!(frame[..4], [0x55, 0x80, 0x01, 0x7D]);
+        assert_eq!(fixed.inner().uart.tx, runtime.uart.tx);
+        assert_eq!(fixed.inner().delay.delays_ns, runtime.delay.delays_ns);
+        assert_eq!(fixed.inner().last_frame_duration_ns(), runtime.last_frame_duration_ns());
+        assert_eq!(fixed.inner().break_pin.levels, runtime.break_pin.levels);
+
+        #[cfg(feature = "async")]
+        {
+            let mut runtime = mock_mcp2003a();
+            block_on(runtime.send_frame_async(0x80, &[0x01], 0x7D)).unwrap();
+
+            let mut fixed: FixedTiming<_, _, _, 19200, 13> = FixedTiming::new(mock_mcp2003a());
+            block_on(fixed.send_frame_async(0x80, &[0x01], 0x7D)).unwrap();
+            assert_eq!(fixed.inner().uart.tx, runtime.uart.tx);
+            assert_eq!(fixed.inner().delay.delays_ns, runtime.delay.delays_ns);
+            assert_eq!(fixed.inner().last_frame_duration_ns(), runtime.last_frame_duration_ns());
+            assert_eq!(fixed.inner().break_pin.levels, runtime.break_pin.levels);
+        }
+    }
+
+    #[test]
+    fn test_fixed_timing_break_method() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.set_break_method(BreakMethod::UartBreak);
+        let mut fixed: FixedTiming<_, _, _, 19200, 13> = FixedTiming::new(mcp2003a);
+        assert_eq!(fixed.inner().config.break_method, BreakMethod::BreakPin);
+
+        // The inner driver breaks with the pin too
+        fixed.inner_mut().send_frame(0x80, &[0x01], 0x7D).unwrap();
+        assert_eq!(fixed.inner().uart.tx, [0x55, 0x80, 0x01, 0x7D]);
+        assert_eq!(fixed.inner().break_pin.levels, [true, false]);
+    }
+
+    #[cfg(feature = "bench")]
+    #[test]
+    fn test_fixed_timing_bench() {
+        use crate::config::LinBusConfig;
+        use crate::tests::{MockDelay, MockPin, MockUart};
+        use crate::typestate::Uninitialized;
+
+        let counting = || {
+            Uninitialized::new(
+                CountingUart::new(MockUart::default()),
+                MockPin::default(),
+                CountingDelay::new(MockDelay::default()),
+            )
+            .init(LinBusConfig::default())
+        };
+        let (_, runtime) = counting().bench_send_frame(0x80, &[0x01], 0x7D);
+        let mut fixed: FixedTiming<_, _, _, 19200, 13> = FixedTiming::new(counting());
+        let (result, counts) = fixed.bench_send_frame(0x80, &[0x01], 0x7D);
+        result.unwrap();
+        assert_eq!(counts, runtime);
+    }
+}
@@ -0,0 +1,657 @@
+/// Sync byte sent after the break of every frame. In bits, this is "10101010", for the slaves to measure the baud rate.
+pub const SYNC_BYTE: u8 = 0x55;
+
+/// Maximum number of data bytes in a LIN frame.
+pub const MAX_DATA_LEN: usize = 8;
+
+/// Maximum length of a LIN frame after the break: the sync byte, the PID, up to 8 data bytes, and the checksum.
+pub const MAX_FRAME_LEN: usize = MAX_DATA_LEN + 3;
+
+/// PID of the master request frame (ID 0x3C) carrying the go-to-sleep command.
+pub const GO_TO_SLEEP_PID: u8 = 0x3C;
+
+/// Data of the go-to-sleep command: 0x00 then 0xFF, telling all slaves to enter sleep (LIN 2.2A section 2.6.3).
+pub const GO_TO_SLEEP_DATA: [u8; MAX_DATA_LEN] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+
+/// LIN Checksum Mode, depending on the LIN version of the frame.
+/// - Classic checksum (LIN 1.x) is calculated over the data bytes only.
+/// - Enhanced checksum (LIN 2.x) is calculated over the PID and the data bytes.
+///
+/// - Auto accepts either checksum when validating a received frame, trying enhanced then classic,
+///   for mixed-version networks where the version of each frame is not known ahead of time.
+/// - Custom calculates the checksum with the given function of the PID and data bytes, for legacy or vendor
+///   devices with a nonstandard checksum (e.g. a sum without the inversion). The function may ignore the PID.
+///
+/// - Note: Diagnostic frames (IDs 0x3C and 0x3D) always use the classic checksum, even on LIN 2.x.
+#[derive(Clone, Copy, Debug)]
+pub enum ChecksumMode {
+    Classic,
+    Enhanced,
+    Auto,
+    Custom(fn(u8, &[u8]) -> u8),
+}
+
+impl PartialEq for ChecksumMode {
+    /// Custom modes are equal when their functions have the same address, which the compiler does not
+    /// guarantee for the same function, e.g. across codegen units.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ChecksumMode::Custom(a), ChecksumMode::Custom(b)) => core::ptr::fn_addr_eq(*a, *b),
+            (a, b) => core::mem::discriminant(a) == core::mem::discriminant(b),
+        }
+    }
+}
+
+impl ChecksumMode {
+    /// Get the checksum for the given PID and data bytes.
+    /// The checksum is the inverted eight bit sum with carry of the bytes covered by the mode.
+    ///
+    /// - Note: Auto calculates the enhanced checksum, as a frame can only be sent with one of them.
+    /// - Note: Custom returns the checksum of its function instead.
+    pub fn get_checksum(&self, pid: u8, data: &[u8]) -> u8 {
+        let mut sum: u16 = match self {
+            ChecksumMode::Classic => 0,
+            ChecksumMode::Enhanced | ChecksumMode::Auto => pid as u16,
+            ChecksumMode::Custom(checksum) => return checksum(pid, data),
+        };
+        for byte in data.iter() {
+            sum += *byte as u16;
+            // Carry is added back into the sum
+            if sum > 0xFF {
+                sum -= 0xFF;
+            }
+        }
+        !(sum as u8)
+    }
+
+    /// Get the checksum mode matching the received checksum for the given PID and data bytes,
+    /// or `None` if the checksum is invalid for this mode.
+    /// Auto tries the enhanced checksum then the classic checksum, returning the one that matched.
+    pub fn validate(&self, pid: u8, data: &[u8], checksum: u8) -> Option<ChecksumMode> {
+        match self {
+            ChecksumMode::Auto => ChecksumMode::Enhanced
+                .validate(pid, data, checksum)
+                .or_else(|| ChecksumMode::Classic.validate(pid, data, checksum)),
+            mode if mode.get_checksum(pid, data) == checksum => Some(*mode),
+            _ => None,
+        }
+    }
+}
+
+/// Get the protected identifier (PID) for the given 6-bit LIN ID.
+/// The two most significant bits are the parity bits P0 and P1 per the LIN specification:
+/// - P0 = ID0 ^ ID1 ^ ID2 ^ ID4
+/// - P1 = !(ID1 ^ ID3 ^ ID4 ^ ID5)
+///
+/// - Note: Only the lower 6 bits of the id are used.
+pub fn get_pid(id: u8) -> u8 {
+    let id = id & 0x3F;
+    let bit = |n: u8| (id >> n) & 1;
+    let p0 = bit(0) ^ bit(1) ^ bit(2) ^ bit(4);
+    let p1 = !(bit(1) ^ bit(3) ^ bit(4) ^ bit(5)) & 1;
+    id | (p0 << 6) | (p1 << 7)
+}
+
+/// Get the header sent after the break for the given 6-bit LIN ID: the 0x55 sync byte and the PID.
+///
+/// - Note: Only the lower 6 bits of the id are used.
+pub fn build_header(raw_id: u8) -> [u8; 2] {
+    [SYNC_BYTE, get_pid(raw_id)]
+}
+
+/// Errors when validating a LIN header with `validate_header`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HeaderError {
+    /// Fewer than the 2 bytes of a header were given. Contains the number of bytes given.
+    TooShort(usize),
+    /// The first byte is not the 0x55 sync byte. Contains the first byte.
+    WrongSync(u8),
+    /// The parity bits of the PID do not match its ID. Contains the received PID.
+    InvalidParity(u8),
+}
+
+/// Validate a LIN header: the 0x55 sync byte, then a PID with parity bits matching its ID.
+/// Returns the 6-bit LIN ID of the PID.
+///
+/// - Note: The bytes must start at the sync byte, without the break. Bytes after the PID are ignored.
+pub fn validate_header(bytes: &[u8]) -> Result<u8, HeaderError> {
+    let (sync, pid) = match bytes {
+        [sync, pid, ..] => (*sync, *pid),
+        _ => return Err(HeaderError::TooShort(bytes.len())),
+    };
+    if sync != SYNC_BYTE {
+        return Err(HeaderError::WrongSync(sync));
+    }
+    if get_pid(pid) != pid {
+        return Err(HeaderError::InvalidParity(pid));
+    }
+    Ok(pid & 0x3F)
+}
+
+/// Errors when building a LIN frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FrameError {
+    /// The raw LIN ID does not fit in 6 bits (0x00 to 0x3F).
+    InvalidId(u8),
+    /// No data bytes were added to the frame, LIN frames carry 1 to 8 data bytes.
+    NoData,
+    /// More than 8 data bytes were added to the frame.
+    DataTooLong,
+    /// The data length cannot be encoded in a LIN 1.3 ID, only 2, 4, or 8 bytes can be.
+    UnsupportedLength(usize),
+    /// The parity bits of the PID do not match its ID, from `validate_frame`. Contains the PID.
+    InvalidParity(u8),
+    /// The data length differs from the length defined in the frame table, from `send_frame_auto`,
+    /// or from the length of a `PublishSubscribe` helper.
+    LengthMismatch { expected: usize, actual: usize },
+}
+
+/// A 6-bit LIN ID, used to calculate the PID sent on the LIN bus.
+///
+/// In LIN 2.x, the ID is any value from 0x00 to 0x3F and the data length of each frame is defined
+/// by the LIN description file. In LIN 1.3, bits 4 and 5 of the ID encode the data length instead,
+/// leaving bits 0 to 3 to identify the frame:
+///
+/// | ID5 | ID4 | Data length |
+/// |-----|-----|-------------|
+/// | 0   | 0/1 | 2 bytes     |
+/// | 1   | 0   | 4 bytes     |
+/// | 1   | 1   | 8 bytes     |
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinId {
+    id: u8,
+}
+
+impl LinId {
+    /// Create a LIN 2.x ID from the given raw 6-bit ID (0x00 to 0x3F).
+    pub fn new(raw_id: u8) -> Result<Self, FrameError> {
+        if raw_id > 0x3F {
+            return Err(FrameError::InvalidId(raw_id));
+        }
+        Ok(LinId { id: raw_id })
+    }
+
+    /// Create a LIN 1.3 ID from the given raw 4-bit ID (0x00 to 0x0F) and data length (2, 4, or 8),
+    /// setting the length bits 4 and 5 of the ID for legacy LIN 1.3 slaves.
+    pub fn with_length(raw_id: u8, data_len: usize) -> Result<Self, FrameError> {
+        if raw_id > 0x0F {
+            return Err(FrameError::InvalidId(raw_id));
+        }
+        let length_bits = match data_len {
+            2 => 0b00,
+            4 => 0b10,
+            8 => 0b11,
+            _ => return Err(FrameError::UnsupportedLength(data_len)),
+        };
+        Ok(LinId {
+            id: raw_id | (length_bits << 4),
+        })
+    }
+
+    /// Get the 6-bit ID, including the length bits for a LIN 1.3 ID.
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    /// Get the protected identifier (PID) to send on the LIN bus.
+    pub fn pid(&self) -> u8 {
+        get_pid(self.id)
+    }
+}
+
+/// The expected data length and checksum mode of a frame in a `FrameTable`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameDefinition {
+    /// Number of data bytes (1 to 8) of the frame.
+    pub len: u8,
+    /// Checksum mode of the frame.
+    pub checksum_mode: ChecksumMode,
+}
+
+/// The frames of a LIN network, mapping each 6-bit LIN ID to the data length and checksum mode of its frame
+/// as defined by the LIN description file, so `read_frame_auto` does not need them on each call.
+/// It can be built in a `static`, to be shared with `set_frame_table`:
+///
+/// ```rust,ignore
+/// static FRAMES: FrameTable = FrameTable::new()
+///     .with_frame(0x10, 2, ChecksumMode::Enhanced)
+///     .with_frame(0x3D, 8, ChecksumMode::Classic);
+///
+/// mcp2003a.set_frame_table(&FRAMES);
+/// let frame = mcp2003a.read_frame_auto(0x10)?;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameTable {
+    frames: [Option<FrameDefinition>; 64],
+}
+
+impl FrameTable {
+    /// Create an empty frame table.
+    pub const fn new() -> Self {
+        FrameTable { frames: [None; 64] }
+    }
+
+    /// Add the frame with the given raw 6-bit LIN ID, data length, and checksum mode to the table,
+    /// replacing any frame already defined for the ID.
+    ///
+    /// - Note: Panics if the raw id is above 0x3F or the data length is not between 1 and 8 bytes,
+    ///   which fails to compile when building the table in a `static` or `const`.
+    pub const fn with_frame(mut self, raw_id: u8, len: usize, checksum_mode: ChecksumMode) -> Self {
+        assert!(raw_id <= 0x3F, "Raw LIN ID must be between 0x00 and 0x3F");
+        assert!(
+            len >= 1 && len <= MAX_DATA_LEN,
+            "Data length must be between 1 and 8 bytes"
+        );
+        self.frames[raw_id as usize] = Some(FrameDefinition {
+            len: len as u8,
+            checksum_mode,
+        });
+        self
+    }
+
+    /// Get the definition of the frame with the given raw 6-bit LIN ID, or `None` if it is not in the table.
+    pub fn get(&self, raw_id: u8) -> Option<FrameDefinition> {
+        self.frames.get(raw_id as usize).copied().flatten()
+    }
+}
+
+impl Default for FrameTable {
+    fn default() -> Self {
+        FrameTable::new()
+    }
+}
+
+/// A validated LIN frame with a computed PID and checksum, ready to be sent on the LIN bus.
+/// Built with a `FrameBuilder`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinFrame {
+    pid: u8,
+    data: [u8; MAX_DATA_LEN],
+    data_len: usize,
+    checksum: u8,
+}
+
+impl LinFrame {
+    /// Get the protected identifier (PID) of the frame.
+    pub fn pid(&self) -> u8 {
+        self.pid
+    }
+
+    /// Get the data bytes of the frame.
+    pub fn data(&self) -> &[u8] {
+        &self.data[..self.data_len]
+    }
+
+    /// Get the checksum of the frame.
+    pub fn checksum(&self) -> u8 {
+        self.checksum
+    }
+}
+
+/// Builder for a `LinFrame` that enforces the LIN frame constraints before anything is sent:
+/// - The raw ID must fit in 6 bits.
+/// - The data must be between 1 and 8 bytes.
+///
+/// ```rust,ignore
+/// let frame = FrameBuilder::new(0x00)
+///     .push(0x01)?
+///     .extend(&[0x02, 0x03])?
+///     .build(ChecksumMode::Enhanced)?;
+/// mcp2003a.send_wire(&frame)?;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameBuilder {
+    raw_id: u8,
+    data: [u8; MAX_DATA_LEN],
+    data_len: usize,
+}
+
+impl FrameBuilder {
+    /// Start building a frame for the given raw 6-bit LIN ID (not the PID).
+    pub fn new(raw_id: u8) -> Self {
+        FrameBuilder {
+            raw_id,
+            data: [0; 8],
+            data_len: 0,
+        }
+    }
+
+    /// Add a data byte to the frame, rejecting it if the frame already has 8 data bytes.
+    pub fn push(mut self, byte: u8) -> Result<Self, FrameError> {
+        if self.data_len >= 8 {
+            return Err(FrameError::DataTooLong);
+        }
+        self.data[self.data_len] = byte;
+        self.data_len += 1;
+        Ok(self)
+    }
+
+    /// Add data bytes to the frame, rejecting them if the frame would exceed 8 data bytes.
+    pub fn extend(mut self, bytes: &[u8]) -> Result<Self, FrameError> {
+        if self.data_len + bytes.len() > 8 {
+            return Err(FrameError::DataTooLong);
+        }
+        self.data[self.data_len..self.data_len + bytes.len()].copy_from_slice(bytes);
+        self.data_len += bytes.len();
+        Ok(self)
+    }
+
+    /// Build the frame, calculating the PID and the checksum with the given checksum mode.
+    pub fn build(self, mode: ChecksumMode) -> Result<LinFrame, FrameError> {
+        if self.raw_id > 0x3F {
+            return Err(FrameError::InvalidId(self.raw_id));
+        }
+        if self.data_len == 0 {
+            return Err(FrameError::NoData);
+        }
+        let pid = get_pid(self.raw_id);
+        Ok(LinFrame {
+            pid,
+            data: self.data,
+            data_len: self.data_len,
+            checksum: mode.get_checksum(pid, &self.data[..self.data_len]),
+        })
+    }
+}
+
+/// Errors when parsing a raw LIN frame with `parse_response`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParseError {
+    /// The expected data length must be between 1 and 8 bytes.
+    InvalidLength(usize),
+    /// The raw bytes are empty, so there is no sync byte.
+    MissingSync,
+    /// The first byte is not the 0x55 sync byte. Contains the first byte.
+    WrongSync(u8),
+    /// There is no PID byte after the sync byte.
+    MissingPid,
+    /// The PID byte is not the expected PID. Contains the received PID.
+    WrongPid(u8),
+    /// Fewer data bytes than expected were received. Contains the number of data bytes received.
+    MissingData(usize),
+    /// All data bytes were received, but not the checksum after them.
+    MissingChecksum,
+    /// The checksum matches neither the classic nor the enhanced checksum of the data. Contains the checksum.
+    InvalidChecksum(u8),
+}
+
+/// The fields of a LIN frame parsed from raw bytes with `parse_response`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParsedFrame {
+    pid: u8,
+    data: [u8; MAX_DATA_LEN],
+    data_len: usize,
+    checksum: u8,
+    checksum_mode: ChecksumMode,
+}
+
+impl ParsedFrame {
+    /// Create a parsed frame from its fields.
+    pub(crate) fn new(pid: u8, data: &[u8], checksum: u8, checksum_mode: ChecksumMode) -> Self {
+        let mut frame_data = [0; MAX_DATA_LEN];
+        frame_data[..data.len()].copy_from_slice(data);
+        ParsedFrame {
+            pid,
+            data: frame_data,
+            data_len: data.len(),
+            checksum,
+            checksum_mode,
+        }
+    }
+
+    /// Get the protected identifier (PID) of the frame.
+    pub fn pid(&self) -> u8 {
+        self.pid
+    }
+
+    /// Get the data bytes of the frame.
+    pub fn data(&self) -> &[u8] {
+        &self.data[..self.data_len]
+    }
+
+    /// Get the checksum of the frame.
+    pub fn checksum(&self) -> u8 {
+        self.checksum
+    }
+
+    /// Get the checksum mode the checksum matched, enhanced if it matches both.
+    pub fn checksum_mode(&self) -> ChecksumMode {
+        self.checksum_mode
+    }
+}
+
+/// Parse a LIN frame from raw bytes, e.g. captured with a logic analyzer or buffered from a UART, into its fields:
+/// the 0x55 sync byte, the PID, `expected_len` data bytes, and the checksum.
+/// The sync byte and PID are validated, as well as the checksum with either the classic or enhanced checksum.
+///
+/// - Note: The id must be the PID, as it is compared to the received PID and the enhanced checksum covers it.
+/// - Note: The raw bytes must start at the sync byte, without the break. Bytes after the checksum are ignored.
+pub fn parse_response(raw: &[u8], id: u8, expected_len: usize) -> Result<ParsedFrame, ParseError> {
+    if !(1..=MAX_DATA_LEN).contains(&expected_len) {
+        return Err(ParseError::InvalidLength(expected_len));
+    }
+    match raw.first() {
+        None => return Err(ParseError::MissingSync),
+        Some(&SYNC_BYTE) => (),
+        Some(byte) => return Err(ParseError::WrongSync(*byte)),
+    }
+    match raw.get(1) {
+        None => return Err(ParseError::MissingPid),
+        Some(pid) if *pid == id => (),
+        Some(pid) => return Err(ParseError::WrongPid(*pid)),
+    }
+
+    let received = &raw[2..];
+    if received.len() < expected_len {
+        return Err(ParseError::MissingData(received.len()));
+    }
+    let checksum = match received.get(expected_len) {
+        Some(checksum) => *checksum,
+        None => return Err(ParseError::MissingChecksum),
+    };
+
+    let data = &received[..expected_len];
+    match ChecksumMode::Auto.validate(id, data, checksum) {
+        Some(checksum_mode) => Ok(ParsedFrame::new(id, data, checksum, checksum_mode)),
+        None => Err(ParseError::InvalidChecksum(checksum)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pid() {
+        assert_eq!(get_pid(0x00), 0x80);
+        assert_eq!(get_pid(0x01), 0xC1);
+        assert_eq!(get_pid(0x3C), 0x3C);
+        assert_eq!(get_pid(0x3D), 0x7D);
+    }
+
+    #[test]
+    fn test_header() {
+        assert_eq!(build_header(0x01), [0x55, 0xC1]);
+        assert_eq!(build_header(0x3D), [0x55, 0x7D]);
+        for id in 0..=0x3F {
+            assert_eq!(validate_header(&build_header(id)), Ok(id));
+        }
+        assert_eq!(validate_header(&[0x55, 0xC1, 0x01]), Ok(0x01));
+
+        assert_eq!(validate_header(&[]), Err(HeaderError::TooShort(0)));
+        assert_eq!(validate_header(&[0x55]), Err(HeaderError::TooShort(1)));
+        assert_eq!(validate_header(&[0x54, 0xC1]), Err(HeaderError::WrongSync(0x54)));
+        assert_eq!(validate_header(&[0x00, 0xC1]), Err(HeaderError::WrongSync(0x00)));
+        // PID of 0x01 with P0 flipped, and with P1 flipped
+        assert_eq!(validate_header(&[0x55, 0x81]), Err(HeaderError::InvalidParity(0x81)));
+        assert_eq!(validate_header(&[0x55, 0x41]), Err(HeaderError::InvalidParity(0x41)));
+    }
+
+    #[test]
+    fn test_classic_checksum() {
+        let data = [0x00, 0xF0, 0x0A, 0x00, 0x00, 0x00, 0x00, 0x08];
+        assert_eq!(ChecksumMode::Classic.get_checksum(0x80, &data), 0xFC);
+    }
+
+    #[test]
+    fn test_enhanced_checksum() {
+        let data = [0x00, 0xF0, 0x0A, 0x00, 0x00, 0x00, 0x00, 0x08];
+        assert_eq!(ChecksumMode::Enhanced.get_checksum(0x80, &data), 0x7C);
+    }
+
+    #[test]
+    fn test_checksum_carry() {
+        // 0xFF + 0x02 = 0x101 -> carry -> 0x02, inverted -> 0xFD
+        assert_eq!(ChecksumMode::Classic.get_checksum(0x00, &[0xFF, 0x02]), 0xFD);
+    }
+
+    #[test]
+    fn test_checksum_validate() {
+        let data = [0x00, 0xF0, 0x0A, 0x00, 0x00, 0x00, 0x00, 0x08];
+        assert_eq!(
+            ChecksumMode::Classic.validate(0x80, &data, 0xFC),
+            Some(ChecksumMode::Classic)
+        );
+        assert_eq!(ChecksumMode::Classic.validate(0x80, &data, 0x7C), None);
+        assert_eq!(
+            ChecksumMode::Auto.validate(0x80, &data, 0x7C),
+            Some(ChecksumMode::Enhanced)
+        );
+        assert_eq!(
+            ChecksumMode::Auto.validate(0x80, &data, 0xFC),
+            Some(ChecksumMode::Classic)
+        );
+        assert_eq!(ChecksumMode::Auto.validate(0x80, &data, 0x00), None);
+    }
+
+    #[test]
+    fn test_checksum_custom() {
+        // Additive checksum without the inversion, as sent by some legacy devices
+        fn additive(_pid: u8, data: &[u8]) -> u8 {
+            data.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte))
+        }
+        let mode = ChecksumMode::Custom(additive);
+        assert_eq!(mode.get_checksum(0x80, &[0xF0, 0x20]), 0x10);
+        assert_eq!(mode.validate(0x80, &[0xF0, 0x20], 0x10), Some(mode));
+        assert_eq!(mode.validate(0x80, &[0xF0, 0x20], 0xEF), None);
+        assert_ne!(mode, ChecksumMode::Classic);
+    }
+
+    #[test]
+    fn test_frame_builder() {
+        let frame = FrameBuilder::new(0x00)
+            .push(0x00)
+            .unwrap()
+            .extend(&[0xF0, 0x0A, 0x00, 0x00, 0x00, 0x00, 0x08])
+            .unwrap()
+            .build(ChecksumMode::Enhanced)
+            .unwrap();
+        assert_eq!(frame.pid(), 0x80);
+        assert_eq!(frame.data(), &[0x00, 0xF0, 0x0A, 0x00, 0x00, 0x00, 0x00, 0x08]);
+        assert_eq!(frame.checksum(), 0x7C);
+    }
+
+    #[test]
+    fn test_frame_builder_over_length() {
+        let builder = FrameBuilder::new(0x01).extend(&[0; 8]).unwrap();
+        assert_eq!(builder.push(0x00), Err(FrameError::DataTooLong));
+        assert_eq!(FrameBuilder::new(0x01).extend(&[0; 9]), Err(FrameError::DataTooLong));
+        assert_eq!(
+            FrameBuilder::new(0x01).extend(&[0; 5]).unwrap().extend(&[0; 4]),
+            Err(FrameError::DataTooLong)
+        );
+    }
+
+    #[test]
+    fn test_frame_builder_invalid() {
+        assert_eq!(
+            FrameBuilder::new(0x01).build(ChecksumMode::Classic),
+            Err(FrameError::NoData)
+        );
+        assert_eq!(
+            FrameBuilder::new(0x40).push(0x00).unwrap().build(ChecksumMode::Classic),
+            Err(FrameError::InvalidId(0x40))
+        );
+    }
+
+    #[test]
+    fn test_lin_id() {
+        assert_eq!(LinId::new(0x01).unwrap().pid(), 0xC1);
+        assert_eq!(LinId::new(0x40), Err(FrameError::InvalidId(0x40)));
+    }
+
+    #[test]
+    fn test_lin_id_with_length() {
+        assert_eq!(LinId::with_length(0x01, 2).unwrap().id(), 0x01);
+        assert_eq!(LinId::with_length(0x01, 4).unwrap().id(), 0x21);
+        assert_eq!(LinId::with_length(0x01, 8).unwrap().id(), 0x31);
+        assert_eq!(LinId::with_length(0x01, 8).unwrap().pid(), get_pid(0x31));
+        assert_eq!(LinId::with_length(0x01, 3), Err(FrameError::UnsupportedLength(3)));
+        assert_eq!(LinId::with_length(0x10, 2), Err(FrameError::InvalidId(0x10)));
+    }
+
+    #[test]
+    fn test_frame_table() {
+        const FRAMES: FrameTable = FrameTable::new()
+            .with_frame(0x10, 2, ChecksumMode::Enhanced)
+            .with_frame(0x3D, 8, ChecksumMode::Classic);
+        assert_eq!(
+            FRAMES.get(0x10),
+            Some(FrameDefinition {
+                len: 2,
+                checksum_mode: ChecksumMode::Enhanced
+            })
+        );
+        assert_eq!(FRAMES.get(0x3D).unwrap().len, 8);
+        assert_eq!(FRAMES.get(0x11), None);
+        assert_eq!(FRAMES.get(0x40), None);
+
+        // A frame is replaced when defined again
+        let frames = FRAMES.with_frame(0x10, 4, ChecksumMode::Classic);
+        assert_eq!(frames.get(0x10).unwrap().len, 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_frame_table_invalid_length() {
+        let _ = FrameTable::new().with_frame(0x10, 9, ChecksumMode::Enhanced);
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let checksum = ChecksumMode::Enhanced.get_checksum(0xC1, &[0x01, 0x02]);
+        let frame = parse_response(&[0x55, 0xC1, 0x01, 0x02, checksum, 0xFF], 0xC1, 2).unwrap();
+        assert_eq!(frame.pid(), 0xC1);
+        assert_eq!(frame.data(), &[0x01, 0x02]);
+        assert_eq!(frame.checksum(), checksum);
+        assert_eq!(frame.checksum_mode(), ChecksumMode::Enhanced);
+
+        let checksum = ChecksumMode::Classic.get_checksum(0xC1, &[0x01, 0x02]);
+        let frame = parse_response(&[0x55, 0xC1, 0x01, 0x02, checksum], 0xC1, 2).unwrap();
+        assert_eq!(frame.checksum_mode(), ChecksumMode::Classic);
+    }
+
+    #[test]
+    fn test_parse_response_malformed() {
+        assert_eq!(parse_response(&[], 0xC1, 2), Err(ParseError::MissingSync));
+        assert_eq!(parse_response(&[0x00, 0x55], 0xC1, 2), Err(ParseError::WrongSync(0x00)));
+        assert_eq!(parse_response(&[0x55], 0xC1, 2), Err(ParseError::MissingPid));
+        assert_eq!(parse_response(&[0x55, 0x80], 0xC1, 2), Err(ParseError::WrongPid(0x80)));
+        assert_eq!(
+            parse_response(&[0x55, 0xC1, 0x01], 0xC1, 2),
+            Err(ParseError::MissingData(1))
+        );
+        assert_eq!(
+            parse_response(&[0x55, 0xC1, 0x01, 0x02], 0xC1, 2),
+            Err(ParseError::MissingChecksum)
+        );
+        assert_eq!(
+            parse_response(&[0x55, 0xC1, 0x01, 0x02, 0x00], 0xC1, 2),
+            Err(ParseError::InvalidChecksum(0x00))
+        );
+        assert_eq!(
+            parse_response(&[0x55, 0xC1], 0xC1, 9),
+            Err(ParseError::InvalidLength(9))
+        );
+    }
+}
@@ -0,0 +1,81 @@
+/// Application-specific integrity check of a frame's data, run after the frame was read successfully.
+/// Some proprietary payloads embed their own CRC or counter in the data on top of the LIN checksum.
+///
+/// ```rust,ignore
+/// let checksum = mcp2003a.read_frame_checked(0xC1, &mut buffer, &Crc8::sae_j1850())?;
+/// ```
+pub trait IntegrityCheck {
+    /// Check the data of a frame, returning true if it is valid.
+    fn check(&self, data: &[u8]) -> bool;
+}
+
+/// CRC-8 integrity check, where the last data byte is the CRC of the data bytes before it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Crc8 {
+    /// Generator polynomial, without the implicit x^8 term.
+    pub polynomial: u8,
+    /// Initial value of the CRC register.
+    pub initial: u8,
+    /// Value XORed with the CRC register to get the final CRC.
+    pub final_xor: u8,
+}
+
+impl Crc8 {
+    /// Create a CRC-8 with the given polynomial, initial value, and final XOR value.
+    pub fn new(polynomial: u8, initial: u8, final_xor: u8) -> Self {
+        Crc8 {
+            polynomial,
+            initial,
+            final_xor,
+        }
+    }
+
+    /// CRC-8 SAE J1850 (polynomial 0x1D, initial 0xFF, final XOR 0xFF), common in automotive payloads.
+    pub fn sae_j1850() -> Self {
+        Crc8::new(0x1D, 0xFF, 0xFF)
+    }
+
+    /// Calculate the CRC of the given bytes.
+    pub fn calculate(&self, data: &[u8]) -> u8 {
+        let mut crc = self.initial;
+        for byte in data.iter() {
+            crc ^= *byte;
+            for _ in 0..8 {
+                crc = if crc & 0x80 != 0 {
+                    (crc << 1) ^ self.polynomial
+                } else {
+                    crc << 1
+                };
+            }
+        }
+        crc ^ self.final_xor
+    }
+}
+
+impl IntegrityCheck for Crc8 {
+    fn check(&self, data: &[u8]) -> bool {
+        match data.split_last() {
+            Some((crc, payload)) => *crc == self.calculate(payload),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc8_sae_j1850() {
+        assert_eq!(Crc8::sae_j1850().calculate(b"123456789"), 0x4B);
+    }
+
+    #[test]
+    fn test_crc8_check() {
+        let crc8 = Crc8::sae_j1850();
+        let crc = crc8.calculate(&[0x01, 0x02, 0x03]);
+        assert!(crc8.check(&[0x01, 0x02, 0x03, crc]));
+        assert!(!crc8.check(&[0x01, 0x02, 0x03, crc ^ 0x01]));
+        assert!(!crc8.check(&[]));
+    }
+}
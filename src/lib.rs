@@ -60,8 +60,9 @@
 //!
 //! - `embedded-hal = "1.0.0"` - Embedded HAL traits for GPIO, UART, and Delay drivers.
 //! - `embedded-hal-nb = "1.0.0"` - Additional non-blocking traits using `nb` crate underneath.
+//! - `embedded-io = "0.6.1"` - Blocking UART traits, for HALs without `embedded-hal-nb` through `serial::BlockingSerial`.
 //!
-//! Async:
+//! Async, with the default `async` feature (disable it for a blocking-only build without these dependencies):
 //!
 //! - `embedded-hal-async = "1.0.0"` - Async traits for async GPIO, and Delay drivers.
 //! - `embedded-io-async = "0.6.1"` - Async traits for async UART drivers.
@@ -83,14 +84,17 @@
 //!    wakeup_duration: LinWakeupDuration::Minimum250Microseconds, // Test for your application
 //!    read_device_response_timeout: LinReadDeviceResponseTimeout::DelayMilliseconds(15), // Test for your application
 //!    inter_frame_space: LinInterFrameSpace::DelayMilliseconds(1), // Test for your application
+//!    ..LinBusConfig::default()
 //! };
 //! mcp2003a.init(lin_bus_config);
 //! ```
 //!
+//! Or start from a preset with typical timings, e.g. `LinBusConfig::automotive_19200()`, and adjust it for your network.
+//!
 //! Now you can use the `mcp2003a` instance to send and receive LIN frames.
 //!
 //! ```rust,ignore
-//! mcp2003a.send_wakeup();
+//! mcp2003a.send_wakeup().unwrap();
 //!
 //! // Works for different LIN versions, you calculate id and checksum based on your application
 //! mcp2003a.send_frame(0x01, &[0x02, 0x03], 0x05).unwrap();
@@ -105,23 +109,61 @@
 //! ```rust,ignore
 //! mcp2003a.send_frame_async(0x01, &[0x02, 0x03], 0x05).await.unwrap();
 //! ```
+//!
+//! The async methods are cancel safe in that dropping their future (e.g. the losing branch of a `select!`) never leaves the bus held dominant: the break and wakeup pins are driven back low when the future is dropped. The frame itself may be cut short, a `UartBreak` may leave the UART at the break baud rate, and a `FramingError` break may leave the UART TX held in break, so call `abort` after cancelling to restore the pins, the UART, and the bus speed before the next frame.
 
 #![no_std]
 
+use core::mem::MaybeUninit;
+
 use embedded_hal::delay::DelayNs;
-use embedded_hal::digital::OutputPin;
+use embedded_hal::digital::{InputPin, OutputPin};
 use embedded_hal_nb::{
     nb::block,
     serial::{Read as UartRead, Write as UartWrite},
 };
 
+#[cfg(feature = "async")]
 use embedded_hal_async::delay::DelayNs as AsyncDelayNs;
+#[cfg(feature = "async")]
 use embedded_io_async::Read as AsyncUartRead;
+#[cfg(feature = "async")]
+use embedded_io_async::ReadReady as AsyncUartReadReady;
+#[cfg(feature = "async")]
 use embedded_io_async::Write as AsyncUartWrite;
 
+#[cfg(feature = "bench")]
+pub mod bench;
+
+pub mod capabilities;
+
+pub mod capture;
+
 pub mod config;
 use config::*;
 
+pub mod frame;
+use frame::*;
+
+pub mod delay;
+
+pub mod fixed_timing;
+
+pub mod diagnostics;
+
+pub mod integrity;
+use integrity::*;
+
+pub mod publish_subscribe;
+
+pub mod schedule;
+
+pub mod serial;
+
+pub mod signal;
+
+pub mod typestate;
+
 #[derive(Debug)]
 pub enum Mcp2003aError<E> {
     /// Some serial error occurred.
@@ -136,445 +178,5730 @@ pub enum Mcp2003aError<E> {
     /// Sync byte was not read back, likely indicating the bus is not active.
     SyncByteNotReceivedBack,
 
+    /// Bytes were read back, but none of them was the sync byte, likely indicating the UART baud rate
+    /// does not match the LIN bus speed (e.g. the sync byte is read back as 0xAA or 0xFE).
+    /// Contains the first byte read back. Check your UART configuration.
+    PossibleBaudMismatch(u8),
+
     /// Sync byte was read back, but the ID byte was not received.
     IdByteNotReceivedBack,
 
     /// Sync and ID bytes were read back (indicating the bus is active), but no data was received.
     LinReadDeviceTimeoutNoResponse,
 
-    /// Partial response with the number of bytes received.
+    /// Partial response with the number of bytes received, which remain valid in the buffer.
+    /// The rest of the buffer is zeroed.
+    /// `saw_checksum` is true when the last received byte is a valid classic or enhanced checksum
+    /// of the bytes before it, meaning the device sent a complete but shorter frame than expected.
     /// Consider increasing the `read_device_response_timeout`, or you may not have
     /// specified the correct number of bytes to read when defining the buffer.
-    LinReadOnlyPartialResponse(usize),
+    LinReadOnlyPartialResponse { received: usize, saw_checksum: bool },
 
     /// Data bytes were received, but the checksum was not received after the data.
     /// You may not have specified the correct number of bytes to read when defining the buffer.
     LinReadNoChecksumReceived,
 
-    /// Not used by this library, but implementers can use this to indicate the checksum was invalid.
+    /// The checksum received did not match the data for the checksum mode, from `read_frame_validated`.
+    /// Implementers can also use this to indicate the checksum was invalid. Contains the received checksum.
     LinReadInvalidChecksum(u8),
-}
 
-/// MCP2003A LIN Transceiver
-pub struct Mcp2003a<UART, GPIO, DELAY> {
-    uart: UART,
-    break_pin: GPIO,
-    delay: DELAY,
-    config: LinBusConfig,
+    /// The requested break is shorter than 11 bit periods, the minimum a LIN slave detects as a break.
+    /// Contains the minimum break duration in microseconds at the configured bus speed.
+    BreakTooShort(u32),
+
+    /// The configured wakeup duration is longer than the 5 millisecond maximum of the LIN specification.
+    /// Contains the configured duration in nanoseconds.
+    WakeupTooLong(u32),
+
+    /// The scratch buffer given to `send_frame_scratch` cannot hold the frame.
+    /// Contains the required length: the sync, ID, data, and checksum bytes.
+    ScratchBufferTooSmall(usize),
+
+    /// Driving the break, wakeup, or enable pin failed, on GPIO drivers where pin operations are fallible.
+    /// - Note: Also returned when reading the sense pin of `measure_break` fails.
+    GpioError,
+
+    /// The frame was read successfully, but its data failed the application's `IntegrityCheck`.
+    /// Contains the checksum received after the data.
+    IntegrityCheckFailed(u8),
+
+    /// The raw ID given to `read_frame_auto` or `send_frame_auto` is not defined in the frame table,
+    /// or no table was set.
+    /// Contains the raw ID.
+    FrameNotInTable(u8),
+
+    /// The baud rate the UART is configured with differs from the configured LIN bus speed, from
+    /// `assert_baud_matches`. Contains both baud rates in bits per second.
+    BaudRateMismatch { configured: u32, actual: u32 },
+
+    /// A frame was sent or read with a driver created by `Mcp2003a::new` before `init` configured
+    /// the timings of the bus.
+    NotInitialized,
+
+    /// The frame to send is invalid, from `validate_frame`: it has no data or more than 8 data bytes,
+    /// or its PID has invalid parity bits. Also returned for a raw ID that does not fit in 6 bits,
+    /// or data whose length differs from the frame table.
+    InvalidFrame(FrameError),
+
+    /// A node kept the bus active after the go-to-sleep command of `go_to_sleep`.
+    /// Contains the number of bytes (and line errors, with a blocking UART) received during the configured
+    /// `sleep_verify` time.
+    BusActiveAfterSleep(usize),
+
+    /// The output slices given to `poll_ids` or `scan_bus` have fewer entries than there are IDs.
+    /// Contains the number of entries needed.
+    OutputTooSmall(usize),
+
+    /// The echo of a frame sent by `send_frame_with_backoff` differed from the frame on every attempt,
+    /// as another node transmitted at the same time.
+    ArbitrationLost,
 }
 
-impl<UART, GPIO, DELAY, E> Mcp2003a<UART, GPIO, DELAY>
-where
-    UART: UartRead<Error = E> + UartWrite<Error = E>,
-    GPIO: OutputPin,
-    DELAY: DelayNs,
-{
-    /// Create a new MCP2003A transceiver instance.
-    ///
-    /// # Arguments
+impl<E> Mcp2003aError<E> {
+    /// Whether the error is a transient condition that retrying the same operation may resolve, e.g. a slave
+    /// not responding or a frame corrupted by noise, rather than a structural one that retrying repeats,
+    /// e.g. a wrong configuration, a missing frame table entry, or a failed pin.
     ///
-    /// * `uart` - UART interface for data communication to and from the transceiver.
-    /// * `break_pin` - GPIO pin for the break signal.
-    /// * `delay` - Delay implementation for break signal timing.
-    /// * `config` - Configuration for the LIN bus speed and break duration.
-    pub fn new(uart: UART, break_pin: GPIO, delay: DELAY) -> Self {
-        Mcp2003a {
-            uart,
-            break_pin,
-            delay,
-            config: LinBusConfig::default(),
+    /// - Note: Serial errors are retryable, as the line errors of a LIN bus (framing, noise, overrun) are transient.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Mcp2003aError::UartError(_)
+            | Mcp2003aError::AsyncUartError(_)
+            | Mcp2003aError::UartWriteNotReady
+            | Mcp2003aError::SyncByteNotReceivedBack
+            | Mcp2003aError::IdByteNotReceivedBack
+            | Mcp2003aError::LinReadDeviceTimeoutNoResponse
+            | Mcp2003aError::LinReadOnlyPartialResponse { .. }
+            | Mcp2003aError::LinReadNoChecksumReceived
+            | Mcp2003aError::LinReadInvalidChecksum(_)
+            | Mcp2003aError::IntegrityCheckFailed(_)
+            | Mcp2003aError::BusActiveAfterSleep(_)
+            | Mcp2003aError::ArbitrationLost => true,
+            Mcp2003aError::PossibleBaudMismatch(_)
+            | Mcp2003aError::BreakTooShort(_)
+            | Mcp2003aError::WakeupTooLong(_)
+            | Mcp2003aError::ScratchBufferTooSmall(_)
+            | Mcp2003aError::GpioError
+            | Mcp2003aError::FrameNotInTable(_)
+            | Mcp2003aError::BaudRateMismatch { .. }
+            | Mcp2003aError::NotInitialized
+            | Mcp2003aError::InvalidFrame(_)
+            | Mcp2003aError::OutputTooSmall(_) => false,
         }
     }
+}
 
-    /// Initialize the MCP2003A transceiver with the given LIN bus configuration.
-    pub fn init(&mut self, config: LinBusConfig) {
-        self.config = config;
+/// Delay for the given nanoseconds, which may be longer than the `u32` nanoseconds of a single `delay_ns`
+/// (about 4.3 seconds), with as many `delay_ns` calls as needed instead of truncating the duration.
+/// Nothing is delayed for 0 nanoseconds, e.g. a `LinDelay::None` wait.
+fn delay_ns_u64<DELAY: DelayNs>(delay: &mut DELAY, ns: u64) {
+    let mut remaining = ns;
+    while remaining > 0 {
+        let chunk = remaining.min(u32::MAX as u64);
+        delay.delay_ns(chunk as u32);
+        remaining -= chunk;
     }
+}
 
-    /// Send a break signal on the LIN bus, pausing execution for at least 730 microseconds (13 bits).
-    fn send_break(&mut self) {
-        // Calculate the duration of the break signal
-        let bit_period_ns = self.config.speed.get_bit_period_ns();
-        let break_duration_ns = self.config.break_duration.get_duration_ns(bit_period_ns);
+/// Delay for the given nanoseconds like `delay_ns_u64`, with an async delay.
+#[cfg(feature = "async")]
+async fn delay_ns_u64_async<DELAY: AsyncDelayNs>(delay: &mut DELAY, ns: u64) {
+    let mut remaining = ns;
+    while remaining > 0 {
+        let chunk = remaining.min(u32::MAX as u64);
+        delay.delay_ns(chunk as u32).await;
+        remaining -= chunk;
+    }
+}
 
-        // Start the break
-        self.break_pin.set_high().unwrap();
+/// Get the kind of an error of the async UART, whose error type is only known to implement `Error`
+/// through the `ErrorType` of the UART.
+#[cfg(feature = "async")]
+fn async_error_kind<UART: embedded_io_async::ErrorType>(error: &UART::Error) -> embedded_io_async::ErrorKind {
+    embedded_io_async::Error::kind(error)
+}
 
-        // Break for the duration based on baud rate
-        self.delay.delay_ns(break_duration_ns);
+/// Drive the pin to the given level. A pin error is returned as `GpioError`.
+fn set_pin_level<PIN: OutputPin, E>(pin: &mut PIN, high: bool) -> Result<(), Mcp2003aError<E>> {
+    let result = if high { pin.set_high() } else { pin.set_low() };
+    result.map_err(|_| Mcp2003aError::GpioError)
+}
 
-        // End the break
-        self.break_pin.set_low().unwrap();
+/// A pin driven high (dominant) by an async method, driven back low when dropped so a cancelled future
+/// never leaves the bus held dominant.
+#[cfg(feature = "async")]
+struct DominantPin<'a, PIN: OutputPin> {
+    pin: &'a mut PIN,
+}
 
-        // Break delimiter is 1 bit time
-        self.delay.delay_ns(bit_period_ns);
+#[cfg(feature = "async")]
+impl<'a, PIN: OutputPin> DominantPin<'a, PIN> {
+    /// Drive the pin high until `release` or the guard is dropped.
+    fn set_high<E>(pin: &'a mut PIN) -> Result<Self, Mcp2003aError<E>> {
+        set_pin_level(pin, true)?;
+        Ok(DominantPin { pin })
     }
 
-    /// Send a wakeup signal on the LIN bus, pausing execution for at least 250 microseconds.
-    ///
-    /// - Note: there is an additional delay of the configured wakeup duration after the wakeup signal
-    /// to ensure the bus devices are ready to receive frames after activation.
-    pub fn send_wakeup(&mut self) {
-        // Calculate the duration of the wakeup signal
-        let wakeup_duration_ns = self.config.wakeup_duration.get_duration_ns();
-
-        // Ensure the wakeup duration is less than 5 milliseconds
-        assert!(
-            wakeup_duration_ns <= 5_000_000,
-            "Wakeup duration must be less than 5 milliseconds"
-        );
-
-        // Start the wakeup signal
-        self.break_pin.set_high().unwrap();
+    /// Drive the pin back low, returning the pin error unlike dropping the guard.
+    fn release<E>(self) -> Result<(), Mcp2003aError<E>> {
+        let mut guard = core::mem::ManuallyDrop::new(self);
+        set_pin_level(guard.pin, false)
+    }
+}
 
-        // Wakeup for the duration
-        self.delay.delay_ns(wakeup_duration_ns);
+#[cfg(feature = "async")]
+impl<PIN: OutputPin> Drop for DominantPin<'_, PIN> {
+    fn drop(&mut self) {
+        // Only reached when the future was cancelled, there is no caller left to report an error to
+        let _ = self.pin.set_low();
+    }
+}
 
-        // End the wakeup signal
-        self.break_pin.set_low().unwrap();
+impl<E> From<embedded_hal_nb::nb::Error<E>> for Mcp2003aError<E> {
+    /// Wrap an error of the blocking UART, so it can be propagated with `?`.
+    fn from(e: embedded_hal_nb::nb::Error<E>) -> Self {
+        Mcp2003aError::UartError(e)
+    }
+}
 
-        // Delay after wakeup signal
-        self.delay.delay_ns(wakeup_duration_ns);
+impl<E> From<E> for Mcp2003aError<E> {
+    /// Wrap an error of the async UART, so it can be propagated with `?`.
+    fn from(e: E) -> Self {
+        Mcp2003aError::AsyncUartError(e)
     }
+}
 
-    /// Send a frame on the LIN bus with the given ID, data, and checksum.
-    /// The data length must be between 0 and 8 bytes.
-    ///
-    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
-    /// - Note: You must calculate the checksum based on your application and LIN version.
-    /// - Note: Inter-frame space is applied after sending the frame.
-    pub fn send_frame(&mut self, id: u8, data: &[u8], checksum: u8) -> Result<[u8; 11], Mcp2003aError<E>> {
-        // Calculate the length of the data
-        assert!(
-            1 <= data.len() && data.len() <= 8,
-            "Data length must be between 1 and 8 bytes"
-        );
-        let data_len = data.len();
+/// Validate a frame before sending it, e.g. to reject user input of a command parser early, without a driver.
+/// The data must have 1 to 8 bytes. With `check_parity`, the id must be a PID whose parity bits match its ID.
+///
+/// - Note: The id is a byte sent as given (i.e. the PID if needed for your LIN version), so any value is in range.
+///   Without `check_parity`, only the data length is checked, as `send_frame` does.
+pub fn validate_frame<E>(id: u8, data: &[u8], check_parity: bool) -> Result<(), Mcp2003aError<E>> {
+    if data.is_empty() {
+        return Err(Mcp2003aError::InvalidFrame(FrameError::NoData));
+    }
+    if data.len() > MAX_DATA_LEN {
+        return Err(Mcp2003aError::InvalidFrame(FrameError::DataTooLong));
+    }
+    if check_parity && get_pid(id) != id {
+        return Err(Mcp2003aError::InvalidFrame(FrameError::InvalidParity(id)));
+    }
+    Ok(())
+}
 
-        // Calculate the frame
-        let mut frame = [0; 11];
+/// Write the bytes to the start of a possibly uninitialized buffer, initializing that prefix of it.
+fn write_uninit(buffer: &mut [MaybeUninit<u8>], bytes: &[u8]) {
+    for (slot, byte) in buffer.iter_mut().zip(bytes) {
+        slot.write(*byte);
+    }
+}
 
-        // This is the constant value to lead every frame with per the LIN specification.
-        // In bits, this is "10101010" or "0x55" in hex.
-        frame[0] = 0x55;
+/// Check whether the last byte of a partial response is a valid checksum of the bytes before it,
+/// with either the classic or enhanced checksum.
+fn is_checksum_of_partial_response(pid: u8, received: &[u8]) -> bool {
+    match received.split_last() {
+        Some((last, data)) if !data.is_empty() => {
+            *last == ChecksumMode::Classic.get_checksum(pid, data)
+                || *last == ChecksumMode::Enhanced.get_checksum(pid, data)
+        }
+        _ => false,
+    }
+}
 
-        frame[1] = id;
-        frame[2..2 + data_len].copy_from_slice(data);
-        frame[2 + data_len] = checksum;
+/// Predicate of `read_frame_until`, returning true once the data received so far is a complete response.
+type FrameComplete<'a> = &'a dyn Fn(&[u8]) -> bool;
 
-        // Send the break signal
-        self.send_break();
+/// Progress of reading a frame byte by byte after sending its header, shared by the blocking and async reads:
+/// skips break bytes, finds the header echo `[0x55, id]`, then fills the buffer with the data and takes the checksum.
+struct ResponseReader<'a> {
+    id: u8,
+    buffer: &'a mut [u8],
+    sync_byte_received: bool,
+    sync_bytes_received: u8,
+    max_sync_bytes: u8,
+    fuzzy_sync: bool,
+    id_byte_received: bool,
+    echo_bytes_to_skip: u8,
+    data_bytes_received: usize,
+    checksum: Option<u8>,
+    first_byte: Option<u8>,
+    break_bytes_skipped: u8,
+    break_byte_tolerance: u8,
+    /// Number of bytes left to read before the read ends, with `FrameEndDetection::ByteCount`.
+    bytes_left: Option<usize>,
+    /// Predicate ending the data bytes early once it returns true for the data received so far.
+    done: Option<FrameComplete<'a>>,
+}
 
-        // Write the frame to the UART
-        for byte in frame.iter() {
-            match self.uart.write(*byte) {
-                Ok(_) => (),
-                Err(e) => return Err(Mcp2003aError::UartError(e)),
-            }
+impl<'a> ResponseReader<'a> {
+    fn new(id: u8, buffer: &'a mut [u8], config: &LinBusConfig) -> Self {
+        let buffer_len = buffer.len();
+        ResponseReader {
+            id,
+            buffer,
+            // Without the header echo, the first received bytes are the response
+            sync_byte_received: !config.expect_header_echo,
+            sync_bytes_received: 0,
+            max_sync_bytes: config.max_sync_bytes.max(1),
+            fuzzy_sync: config.fuzzy_sync,
+            id_byte_received: !config.expect_header_echo,
+            echo_bytes_to_skip: if config.expect_header_echo {
+                config.header_skip_bytes.saturating_sub(2)
+            } else {
+                0
+            },
+            data_bytes_received: 0,
+            checksum: None,
+            first_byte: None,
+            break_bytes_skipped: 0,
+            break_byte_tolerance: config.break_byte_tolerance,
+            bytes_left: match config.frame_end_detection {
+                FrameEndDetection::WouldBlock => None,
+                FrameEndDetection::ByteCount => {
+                    let header_len = if config.expect_header_echo {
+                        config.header_skip_bytes.max(2) as usize
+                    } else {
+                        0
+                    };
+                    Some(header_len + buffer_len + 1)
+                }
+            },
+            done: None,
         }
+    }
 
-        // Ensures that none of the previously written words are still buffered
-        match block!(self.uart.flush()) {
-            Ok(_) => (),
-            Err(_) => return Err(Mcp2003aError::UartWriteNotReady),
+    /// End the data bytes as soon as the given predicate returns true for the data received so far,
+    /// so the next byte is read as the checksum.
+    fn until(mut self, done: FrameComplete<'a>) -> Self {
+        self.done = Some(done);
+        self
+    }
+
+    /// Read the bytes after the header as the response, without skipping further echoed bytes.
+    fn skip_no_echo_bytes(&mut self) {
+        if let Some(bytes_left) = self.bytes_left.as_mut() {
+            *bytes_left -= self.echo_bytes_to_skip as usize;
         }
+        self.echo_bytes_to_skip = 0;
+    }
 
-        // Inter-frame space delay
-        self.delay.delay_ns(self.config.inter_frame_space.get_duration_ns());
+    /// Whether the byte is the sync byte, or one bit off it with `fuzzy_sync` configured.
+    fn is_sync(&self, byte: u8) -> bool {
+        byte == SYNC_BYTE || (self.fuzzy_sync && (byte ^ SYNC_BYTE).count_ones() == 1)
+    }
 
-        Ok(frame)
+    /// Skip a break read back before the sync byte, if the tolerance allows. Returns whether it was skipped.
+    fn skip_break(&mut self) -> bool {
+        if self.sync_byte_received || self.break_bytes_skipped >= self.break_byte_tolerance {
+            return false;
+        }
+        self.break_bytes_skipped += 1;
+        true
     }
 
-    /// Read a frame from the LIN bus with the given ID into the buffer.
-    /// Fills the buffer and returns the checksum is received after the data.
-    ///
-    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
-    /// - Note: Inter-frame space is applied after reading the frame.
-    /// - Note: Assumes your buffer is the size of the data you expect to receive.
-    /// - Note: You must decide how to validate the checksum based on your application and LIN version.
-    pub fn read_frame(&mut self, id: u8, buffer: &mut [u8]) -> Result<u8, Mcp2003aError<E>> {
-        // Inter-frame space delay
-        self.delay.delay_ns(self.config.inter_frame_space.get_duration_ns());
+    /// Handle a received byte. Returns true once the whole frame was read.
+    fn push(&mut self, byte: u8) -> bool {
+        // The break may be read back as a 0x00 byte before the sync byte
+        if byte == 0x00 && self.skip_break() {
+            return false;
+        }
 
-        // Send the break signal to notify the device of the start of a frame
-        self.send_break();
+        self.first_byte.get_or_insert(byte);
 
-        // Write the header to UART
-        let header = [0x55, id];
-        for byte in header.iter() {
-            match self.uart.write(*byte) {
-                Ok(_) => (),
-                Err(e) => return Err(Mcp2003aError::UartError(e)),
-            }
+        if let Some(bytes_left) = self.bytes_left.as_mut() {
+            *bytes_left = bytes_left.saturating_sub(1);
         }
 
-        // Delay to ensure the header has time to be received and responded to by the device
-        self.delay
-            .delay_ns(self.config.read_device_response_timeout.get_duration_ns());
-
-        // Read the response from the device
-        // NOTE: The mcp2003a will replay the header back to you when you read.
-        let mut len = 0;
-        let mut sync_byte_received = false;
-        let mut id_byte_received = false;
-        let mut data_bytes_received = 0;
-        let mut checksum_received = false;
-        let mut checksum = 0;
+        // While there are some bytes in the uart buffer,
+        // keep skipping until we find the header [0x55, id]
 
-        loop {
-            match self.uart.read() {
-                Ok(byte) => {
-                    // While there are some bytes in the uart buffer,
-                    // keep skipping until we find the header [0x55, id]
-
-                    // Check for the sync byte
-                    if !sync_byte_received {
-                        if byte == 0x55 {
-                            sync_byte_received = true;
-                        }
-                    }
-                    // Check for the id byte
-                    else if !id_byte_received {
-                        if byte == id {
-                            id_byte_received = true;
-                        } else {
-                            sync_byte_received = false;
-                        }
-                    }
-                    // Read the data bytes up until the provided buffer length
-                    else if data_bytes_received < buffer.len() {
-                        buffer[len] = byte;
-                        len += 1;
-                        data_bytes_received += 1;
-                    }
-                    // After the data bytes, read the checksum
-                    else if !checksum_received {
-                        checksum = byte;
-                        checksum_received = true;
-                        // We've read the whole frame
-                        break;
-                    }
-                }
-                Err(embedded_hal_nb::nb::Error::WouldBlock) => {
-                    // If we get a WouldBlock error, we've read all the bytes in the buffer
-                    break;
+        // Check for the sync byte
+        if !self.sync_byte_received {
+            self.sync_byte_received = self.is_sync(byte);
+            self.sync_bytes_received = self.sync_byte_received as u8;
+        }
+        // Check for the id byte
+        else if !self.id_byte_received {
+            if byte == SYNC_BYTE && self.sync_bytes_received < self.max_sync_bytes {
+                // Repeated sync byte
+                self.sync_bytes_received += 1;
+            } else if byte == self.id {
+                self.id_byte_received = true;
+            } else {
+                // False sync: the byte may be the real sync byte, so resynchronize on it
+                self.sync_byte_received = self.is_sync(byte);
+                self.sync_bytes_received = self.sync_byte_received as u8;
+            }
+        }
+        // Skip any further echoed bytes after the header
+        else if self.echo_bytes_to_skip > 0 {
+            self.echo_bytes_to_skip -= 1;
+        }
+        // Read the data bytes up until the provided buffer length
+        else if self.data_bytes_received < self.buffer.len() {
+            self.buffer[self.data_bytes_received] = byte;
+            self.data_bytes_received += 1;
+            let received = self.data_bytes_received;
+            if self.done.is_some_and(|done| done(&self.buffer[..received])) {
+                // The response is shorter than the buffer, so the tail is no longer expected
+                if let Some(bytes_left) = self.bytes_left.as_mut() {
+                    *bytes_left -= self.buffer.len() - received;
                 }
-                Err(e) => return Err(Mcp2003aError::UartError(e)),
+                let buffer = core::mem::take(&mut self.buffer);
+                buffer[received..].fill(0);
+                self.buffer = &mut buffer[..received];
             }
         }
+        // After the data bytes, read the checksum
+        else {
+            self.checksum = Some(byte);
+        }
+        self.checksum.is_some() || self.bytes_left == Some(0)
+    }
 
-        // Inter-frame space delay
-        self.delay.delay_ns(self.config.inter_frame_space.get_duration_ns());
+    /// Whether all the data bytes were read and only the checksum is missing.
+    fn awaiting_checksum(&self) -> bool {
+        !self.buffer.is_empty() && self.data_bytes_received == self.buffer.len() && self.checksum.is_none()
+    }
 
-        if !sync_byte_received {
+    /// Get the checksum of the frame, or the error describing how far the read got.
+    /// The unused tail of the buffer is zeroed, so stale bytes of a previous frame are not mistaken for data.
+    fn finish<E>(self) -> Result<u8, Mcp2003aError<E>> {
+        self.buffer[self.data_bytes_received..].fill(0);
+        if !self.sync_byte_received {
+            if let Some(byte) = self.first_byte {
+                return Err(Mcp2003aError::PossibleBaudMismatch(byte));
+            }
             return Err(Mcp2003aError::SyncByteNotReceivedBack);
         }
-        if !id_byte_received {
+        if !self.id_byte_received {
             return Err(Mcp2003aError::IdByteNotReceivedBack);
         }
-        if data_bytes_received == 0 {
+        if self.data_bytes_received == 0 && (!self.buffer.is_empty() || self.checksum.is_none()) {
             return Err(Mcp2003aError::LinReadDeviceTimeoutNoResponse);
         }
-        if data_bytes_received < buffer.len() {
-            return Err(Mcp2003aError::LinReadOnlyPartialResponse(data_bytes_received));
-        }
-        if !checksum_received {
-            return Err(Mcp2003aError::LinReadNoChecksumReceived);
+        if self.data_bytes_received < self.buffer.len() {
+            return Err(Mcp2003aError::LinReadOnlyPartialResponse {
+                received: self.data_bytes_received,
+                saw_checksum: is_checksum_of_partial_response(self.id, &self.buffer[..self.data_bytes_received]),
+            });
         }
+        self.checksum.ok_or(Mcp2003aError::LinReadNoChecksumReceived)
+    }
+}
 
-        Ok(checksum)
+/// Get the data length of a response polled with `poll_ids`, from the result of reading it into an 8 byte row.
+/// Responses shorter than 8 bytes are accepted when their last byte is a valid checksum, which is then cleared
+/// from the row with the rest of its unused bytes. Serial errors are returned, any other error means no response.
+fn polled_response_len<E>(
+    id: u8,
+    row: &mut [u8; MAX_DATA_LEN],
+    result: Result<u8, Mcp2003aError<E>>,
+) -> Result<Option<u8>, Mcp2003aError<E>> {
+    match result {
+        Ok(checksum) => {
+            let mut received = [0u8; 9];
+            received[..8].copy_from_slice(row);
+            received[8] = checksum;
+            if is_checksum_of_partial_response(id, &received) {
+                Ok(Some(8))
+            } else {
+                Ok(None)
+            }
+        }
+        Err(Mcp2003aError::LinReadOnlyPartialResponse {
+            received,
+            saw_checksum: true,
+        }) => {
+            row[received - 1..].fill(0);
+            Ok(Some(received as u8 - 1))
+        }
+        Err(e @ Mcp2003aError::UartError(_))
+        | Err(e @ Mcp2003aError::AsyncUartError(_))
+        | Err(e @ Mcp2003aError::UartWriteNotReady) => Err(e),
+        Err(_) => Ok(None),
     }
 }
 
-impl<UART, GPIO, DELAY, E> Mcp2003a<UART, GPIO, DELAY>
-where
-    UART: AsyncUartRead<Error = E> + AsyncUartWrite<Error = E>,
-    GPIO: OutputPin,
-    DELAY: AsyncDelayNs,
-{
-    /// Send a break signal on the LIN bus, pausing execution for at least 730 microseconds (13 bits).
-    async fn send_break_async(&mut self) {
-        // Calculate the duration of the break signal
-        let bit_period_ns = self.config.speed.get_bit_period_ns();
-        let break_duration_ns = self.config.break_duration.get_duration_ns(bit_period_ns);
+/// Get the data length and checksum of a response read with `read_frame_min_len`, from the result of reading it
+/// into the buffer. A response shorter than the buffer with at least `min_data_len` data bytes is accepted when its
+/// last byte is a valid checksum, which is then cleared from the buffer. Any other error is returned.
+fn short_response<E>(
+    pid: u8,
+    buffer: &mut [u8],
+    min_data_len: usize,
+    result: Result<u8, Mcp2003aError<E>>,
+) -> Result<(usize, u8), Mcp2003aError<E>> {
+    let received = match result {
+        Ok(checksum) => return Ok((buffer.len(), checksum)),
+        Err(Mcp2003aError::LinReadOnlyPartialResponse {
+            received,
+            saw_checksum: true,
+        }) if received > min_data_len => received,
+        // One byte short, the checksum filled the buffer
+        Err(Mcp2003aError::LinReadNoChecksumReceived)
+            if buffer.len() > min_data_len && is_checksum_of_partial_response(pid, buffer) =>
+        {
+            buffer.len()
+        }
+        Err(e) => return Err(e),
+    };
+    let checksum = buffer[received - 1];
+    buffer[received - 1] = 0;
+    Ok((received - 1, checksum))
+}
 
-        // Start the break
-        self.break_pin.set_high().unwrap();
+/// Function changing the baud rate of the UART to the given baud rate, registered with `set_baud_switcher`.
+///
+/// The driver does not own the baud configuration of the UART, so this is how `BreakMethod::UartBreak` drops
+/// the UART to a lower baud rate for the 0x00 break byte, then restores the LIN bus speed. It must only return
+/// once the new baud rate is in effect, and should not fail, as there is no way to report an error from it.
+///
+/// ```rust,ignore
+/// mcp2003a.set_baud_switcher(|uart, baud| uart.change_baudrate(baud.Hz()).unwrap());
+/// ```
+pub type BaudSwitcher<UART> = fn(&mut UART, u32);
 
-        // Break for the duration based on baud rate
-        self.delay.delay_ns(break_duration_ns).await;
+/// Function holding the UART TX line low (in break) when given true, and releasing it when given false,
+/// registered with `set_tx_break_control` for `BreakMethod::FramingError`.
+///
+/// The driver does not own the UART, so this is how it reaches the break control of the UART, which embedded-hal
+/// does not expose. It must only return once the line is held or released, and should not fail.
+///
+/// ```rust,ignore
+/// mcp2003a.set_tx_break_control(|uart, hold| if hold { uart.start_break() } else { uart.stop_break() });
+/// ```
+pub type TxBreakControl<UART> = fn(&mut UART, bool);
 
-        // End the break
-        self.break_pin.set_low().unwrap();
+/// Fixed-size record of the raw bytes of a frame, for `last_tx` and `last_rx`.
+/// Bytes past the capacity are dropped.
+#[cfg(feature = "last-frame")]
+#[derive(Clone, Copy, Debug, Default)]
+struct RawFrame {
+    bytes: [u8; 16],
+    len: usize,
+}
 
-        // Break delimiter is 1 bit time
-        self.delay.delay_ns(bit_period_ns).await;
+#[cfg(feature = "last-frame")]
+impl RawFrame {
+    fn clear(&mut self) {
+        self.len = 0;
     }
 
-    /// Send a wakeup signal on the LIN bus, pausing execution for at least 250 microseconds.
-    /// - Note: there is an additional delay of the configured wakeup duration after the wakeup signal
-    /// to ensure the bus devices are ready to receive frames after activation.
-    /// - Note: This function is async to allow for the delay to be async.
-    pub async fn send_wakeup_async(&mut self) {
-        // Calculate the duration of the wakeup signal
-        let wakeup_duration_ns = self.config.wakeup_duration.get_duration_ns();
-
-        // Ensure the wakeup duration is less than 5 milliseconds
-        assert!(
-            wakeup_duration_ns <= 5_000_000,
-            "Wakeup duration must be less than 5 milliseconds"
-        );
+    fn extend(&mut self, bytes: &[u8]) {
+        for byte in bytes.iter() {
+            if self.len < self.bytes.len() {
+                self.bytes[self.len] = *byte;
+                self.len += 1;
+            }
+        }
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
 
-        // Start the wakeup signal
-        self.break_pin.set_high().unwrap();
+/// MCP2003A LIN Transceiver
+pub struct Mcp2003a<UART, GPIO, DELAY> {
+    uart: UART,
+    break_pin: GPIO,
+    wakeup_pin: Option<GPIO>,
+    enable_pin: Option<GPIO>,
+    enabled: bool,
+    delay: DELAY,
+    config: LinBusConfig,
+    last_frame_duration_ns: u64,
+    schedule_jitter_ns: u64,
+    schedule_overruns: u32,
+    /// Number of frames left to send or read with the extended break after `send_wakeup`.
+    post_wakeup_breaks: u8,
+    /// Whether `init` configured the timings, false for a driver created by `new` until then.
+    initialized: bool,
+    baud_switcher: Option<BaudSwitcher<UART>>,
+    tx_break_control: Option<TxBreakControl<UART>>,
+    frame_table: Option<&'static FrameTable>,
+    name: Option<&'static str>,
+    #[cfg(feature = "last-frame")]
+    last_tx: RawFrame,
+    #[cfg(feature = "last-frame")]
+    last_rx: RawFrame,
+}
 
-        // Wakeup for the duration
-        self.delay.delay_ns(wakeup_duration_ns).await;
+impl<UART, GPIO, DELAY> Mcp2003a<UART, GPIO, DELAY> {
+    /// Create the driver from its pins and configuration, with the rest of its state reset, for the constructors
+    /// of `Mcp2003a` and `Uninitialized::init`. `initialized` is whether `config` holds the timings of the bus.
+    fn from_parts(
+        uart: UART,
+        break_pin: GPIO,
+        wakeup_pin: Option<GPIO>,
+        enable_pin: Option<GPIO>,
+        delay: DELAY,
+        config: LinBusConfig,
+        initialized: bool,
+    ) -> Self {
+        Mcp2003a {
+            uart,
+            break_pin,
+            wakeup_pin,
+            enable_pin,
+            enabled: false,
+            delay,
+            config,
+            last_frame_duration_ns: 0,
+            schedule_jitter_ns: 0,
+            schedule_overruns: 0,
+            post_wakeup_breaks: 0,
+            initialized,
+            baud_switcher: None,
+            tx_break_control: None,
+            frame_table: None,
+            name: None,
+            #[cfg(feature = "last-frame")]
+            last_tx: RawFrame::default(),
+            #[cfg(feature = "last-frame")]
+            last_rx: RawFrame::default(),
+        }
+    }
 
-        // End the wakeup signal
-        self.break_pin.set_low().unwrap();
+    /// Get the pin driving the wakeup signal: the separate wakeup pin if there is one, else the break pin.
+    fn wakeup_pin(&mut self) -> &mut GPIO {
+        match self.wakeup_pin {
+            Some(ref mut pin) => pin,
+            None => &mut self.break_pin,
+        }
+    }
 
-        // Delay after wakeup signal
-        self.delay.delay_ns(wakeup_duration_ns).await;
+    /// Get the time in nanoseconds the last `send_frame` or `read_frame` (or their async versions) spent,
+    /// for schedulers and watchdog feeders to account for the blocking time of the call.
+    ///
+    /// - Note: This is calculated from the configured timings (break, bytes at the bus speed, and delays),
+    ///   not measured, as there is no hardware timer available to the driver.
+    pub fn last_frame_duration_ns(&self) -> u64 {
+        self.last_frame_duration_ns
     }
 
-    /// Send a frame on the LIN bus with the given ID, data, and checksum.
-    /// The data length must be between 0 and 8 bytes.
-    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
-    /// - Note: You must calculate the checksum based on your application and LIN version.
-    /// - Note: Inter-frame space is applied after sending the frame.
-    /// - Note: This function is async to allow for the delay and serial write to be async.
-    pub async fn send_frame_async(&mut self, id: u8, data: &[u8], checksum: u8) -> Result<[u8; 11], Mcp2003aError<E>> {
-        // Calculate the length of the data
-        assert!(
-            1 <= data.len() && data.len() <= 8,
-            "Data length must be between 1 and 8 bytes"
-        );
-        let data_len = data.len();
+    /// Get the worst-case time in nanoseconds a `read_frame` can block with the current configuration,
+    /// to size watchdog windows around LIN calls: the break, header, response timeout,
+    /// a full 8 byte response with its checksum, and both inter-frame spaces.
+    pub fn max_transaction_time_ns(&self) -> u64 {
+        self.config.nominal_frame_time_ns(8)
+            + self.response_timeout_ns(self.config.read_device_response_timeout, 8)
+            + 2 * self.config.inter_frame_space.get_duration_ns()
+    }
 
-        // Calculate the frame
-        let mut frame = [0; 11];
+    /// Check the baud rate the UART was configured with matches the configured LIN bus speed, as the UART
+    /// is configured outside the driver and a mismatch only shows later as reads failing.
+    /// Call it once at startup with the baud rate given to your UART driver.
+    ///
+    /// - Note: Returns `BaudRateMismatch` if the baud rates differ.
+    pub fn assert_baud_matches<E>(&self, actual_baud: u32) -> Result<(), Mcp2003aError<E>> {
+        let configured = self.config.speed.get_baud_rate();
+        if actual_baud != configured {
+            return Err(Mcp2003aError::BaudRateMismatch {
+                configured,
+                actual: actual_baud,
+            });
+        }
+        Ok(())
+    }
 
-        // This is the constant value to lead every frame with per the LIN specification.
-        // In bits, this is "10101010" or "0x55" in hex.
-        frame[0] = 0x55;
+    /// Check `init` configured the timings before a frame is sent or read.
+    fn ensure_initialized<E>(&self) -> Result<(), Mcp2003aError<E>> {
+        if !self.initialized {
+            return Err(Mcp2003aError::NotInitialized);
+        }
+        Ok(())
+    }
 
-        frame[1] = id;
-        frame[2..2 + data_len].copy_from_slice(data);
-        frame[2 + data_len] = checksum;
+    /// Get the raw bytes written for the last frame sent, or the header of the last frame read, without the break.
+    /// For debugging without an external logger.
+    ///
+    /// - Note: Only available with the `last-frame` feature. Up to 16 bytes are kept.
+    #[cfg(feature = "last-frame")]
+    pub fn last_tx(&self) -> &[u8] {
+        self.last_tx.as_slice()
+    }
 
-        // Send the break signal
-        self.send_break_async().await;
+    /// Get the raw bytes received while reading the last frame, including the header echo and any break byte.
+    /// For debugging without an external logger.
+    ///
+    /// - Note: Only available with the `last-frame` feature. Up to 16 bytes are kept.
+    #[cfg(feature = "last-frame")]
+    pub fn last_rx(&self) -> &[u8] {
+        self.last_rx.as_slice()
+    }
 
-        // Write the frame to the UART
-        match self.uart.write(&frame).await {
-            Ok(_) => (),
-            Err(e) => return Err(Mcp2003aError::AsyncUartError(e)),
+    /// Record the raw bytes written for a frame, with the `last-frame` feature.
+    #[allow(unused_variables)]
+    fn record_tx(&mut self, bytes: &[u8]) {
+        #[cfg(feature = "last-frame")]
+        {
+            self.last_tx.clear();
+            self.last_tx.extend(bytes);
         }
+    }
 
-        // Inter-frame space delay
-        self.delay
-            .delay_ns(self.config.inter_frame_space.get_duration_ns())
-            .await;
+    /// Record raw bytes received while reading a frame, with the `last-frame` feature.
+    /// The record is cleared when `start` is true, at the start of a read.
+    #[allow(unused_variables)]
+    fn record_rx(&mut self, bytes: &[u8], start: bool) {
+        #[cfg(feature = "last-frame")]
+        {
+            if start {
+                self.last_rx.clear();
+            }
+            self.last_rx.extend(bytes);
+        }
+    }
 
-        Ok(frame)
+    /// Register the function changing the baud rate of the UART, so `BreakMethod::UartBreak` can lower the baud
+    /// rate for the break byte and restore the LIN bus speed after it, instead of the application doing it.
+    pub fn set_baud_switcher(&mut self, switcher: BaudSwitcher<UART>) {
+        self.baud_switcher = Some(switcher);
     }
 
-    /// Read a frame from the LIN bus with the given ID into the buffer.
-    /// Fills the buffer and returns the checksum is received after the data.
-    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
-    /// - Note: Inter-frame space is applied after reading the frame.
-    /// - Note: Assumes your buffer is the size of the data you expect to receive.
-    /// - Note: You must decide how to validate the checksum based on your application and LIN version.
-    /// - Note: This function is async to allow for the delay and serial read to be async.
-    pub async fn read_frame_async(&mut self, id: u8, buffer: &mut [u8]) -> Result<u8, Mcp2003aError<E>> {
-        // Inter-frame space delay
-        self.delay
-            .delay_ns(self.config.inter_frame_space.get_duration_ns())
-            .await;
+    /// Register the function holding the UART TX line in break, so `BreakMethod::FramingError` can send the break
+    /// with the UART itself, suppressing the stop bit, instead of with the break pin.
+    pub fn set_tx_break_control(&mut self, control: TxBreakControl<UART>) {
+        self.tx_break_control = Some(control);
+    }
 
-        // Send the break signal to notify the device of the start of a frame
-        self.send_break_async().await;
+    /// Set the frame table of the network, so `read_frame_auto` looks up the data length and checksum mode
+    /// of each frame from its ID.
+    pub fn set_frame_table(&mut self, table: &'static FrameTable) {
+        self.frame_table = Some(table);
+    }
 
-        // Write the header to UART
-        let header = [0x55, id];
-        match self.uart.write(&header).await {
-            Ok(_) => (),
-            Err(e) => return Err(Mcp2003aError::AsyncUartError(e)),
-        }
+    /// Set the name of this driver instance, e.g. the bus it drives, to tell the logs of gateways
+    /// with multiple LIN buses apart. It is reported by `name` and `capabilities`.
+    pub fn set_name(&mut self, name: &'static str) {
+        self.name = Some(name);
+    }
 
-        // Delay to ensure the header has time to be received and responded to by the device
-        self.delay
-            .delay_ns(self.config.read_device_response_timeout.get_duration_ns())
-            .await;
+    /// Get the name of this driver instance set with `set_name`, if any.
+    pub fn name(&self) -> Option<&'static str> {
+        self.name
+    }
 
-        // Read the response from the device
-        // NOTE: The mcp2003a will replay the header back to you when you read.
-        let mut len = 0;
-        let mut sync_byte_received = false;
-        let mut id_byte_received = false;
-        let mut data_bytes_received = 0;
-        let mut checksum_received = false;
-        let checksum;
+    /// Get the definition of the frame with the given raw ID from the frame table.
+    fn frame_definition<E>(&self, raw_id: u8) -> Result<FrameDefinition, Mcp2003aError<E>> {
+        self.frame_table
+            .and_then(|table| table.get(raw_id))
+            .ok_or(Mcp2003aError::FrameNotInTable(raw_id))
+    }
+
+    /// Get the highest baud rate at which the 9 dominant bit periods of a 0x00 byte last the given break time.
+    fn break_baud_rate(&self, break_duration_ns: u32) -> u32 {
+        (9 * 1_000_000_000 / break_duration_ns as u64) as u32
+    }
+
+    /// Get the break duration of the next frame: the configured `break_duration`, extended by
+    /// `post_wakeup_break_extension` for the first `post_wakeup_break_frames` frames after `send_wakeup`.
+    fn frame_break_duration(&mut self) -> LinBreakDuration {
+        if self.post_wakeup_breaks == 0 {
+            return self.config.break_duration;
+        }
+        self.post_wakeup_breaks -= 1;
+        self.config
+            .break_duration
+            .extended(self.config.post_wakeup_break_extension)
+    }
+
+    /// Get the ID byte of the header to read a frame with the given id, the PID of it with `auto_pid` configured.
+    fn header_id(&self, id: u8) -> u8 {
+        if self.config.auto_pid {
+            get_pid(id)
+        } else {
+            id
+        }
+    }
+
+    /// Calculate the given response timeout for a response with the given number of data bytes.
+    fn response_timeout_ns(&self, timeout: LinReadDeviceResponseTimeout, data_len: usize) -> u64 {
+        timeout.get_duration_ns_framed(
+            self.config.speed.get_bit_period_ns(),
+            data_len,
+            self.config.uart_framing,
+        )
+    }
+
+    /// Calculate the time spent sending a frame with the given data length without the break,
+    /// including the inter-frame space.
+    fn send_frame_duration_ns(&self, data_len: usize) -> u64 {
+        self.config.nominal_frame_time_ns(data_len) - self.config.nominal_break_time_ns()
+            + (data_len as u64 + 2) * self.config.inter_byte_space.get_duration_ns()
+            + self.config.inter_frame_space.get_duration_ns()
+    }
+
+    /// Calculate the time spent reading a frame: the header, the response timeout, and both inter-frame spaces.
+    fn read_frame_duration_ns(&self, response_timeout_ns: u64, inter_frame_space_ns: u64) -> u64 {
+        self.config.nominal_header_time_ns() + response_timeout_ns + 2 * inter_frame_space_ns
+    }
+}
+
+impl<UART, GPIO, DELAY, E> Mcp2003a<UART, GPIO, DELAY>
+where
+    UART: UartRead<Error = E> + UartWrite<Error = E>,
+    E: embedded_hal_nb::serial::Error,
+    GPIO: OutputPin,
+    DELAY: DelayNs,
+{
+    /// Create a new MCP2003A transceiver instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `uart` - UART interface for data communication to and from the transceiver.
+    /// * `break_pin` - GPIO pin for the break signal.
+    /// * `delay` - Delay implementation for break signal timing.
+    /// * `config` - Configuration for the LIN bus speed and break duration.
+    ///
+    /// - Note: The driver starts with the default `LinBusConfig` until `init` is called, and sending or reading
+    ///   frames before returns `NotInitialized`. See `typestate::Uninitialized` to have the configuration
+    ///   enforced at compile time instead.
+    /// - Note: Only available with the `unchecked-init` feature (enabled by default).
+    #[cfg(feature = "unchecked-init")]
+    pub fn new(uart: UART, break_pin: GPIO, delay: DELAY) -> Self {
+        Self::from_parts(uart, break_pin, None, None, delay, LinBusConfig::default(), false)
+    }
+
+    /// Create a new MCP2003A transceiver instance, for boards where the wakeup signal is driven by
+    /// a dedicated control line instead of the break pin.
+    ///
+    /// # Arguments
+    ///
+    /// * `uart` - UART interface for data communication to and from the transceiver.
+    /// * `break_pin` - GPIO pin for the break signal.
+    /// * `wakeup_pin` - GPIO pin for the wakeup signal, or `None` to use the break pin.
+    /// * `delay` - Delay implementation for break signal timing.
+    ///
+    /// - Note: Only available with the `unchecked-init` feature (enabled by default).
+    #[cfg(feature = "unchecked-init")]
+    pub fn new_with_wakeup_pin(uart: UART, break_pin: GPIO, wakeup_pin: Option<GPIO>, delay: DELAY) -> Self {
+        Self::from_parts(uart, break_pin, wakeup_pin, None, delay, LinBusConfig::default(), false)
+    }
+
+    /// Create a new MCP2003A transceiver instance, for boards gating the transceiver with an enable pin,
+    /// e.g. to power it down between bursts of frames with `disable`.
+    ///
+    /// # Arguments
+    ///
+    /// * `uart` - UART interface for data communication to and from the transceiver.
+    /// * `break_pin` - GPIO pin for the break signal.
+    /// * `enable_pin` - GPIO pin enabling the transceiver when high.
+    /// * `delay` - Delay implementation for break signal timing.
+    ///
+    /// - Note: The transceiver is not enabled until `enable` is called, or the first frame with `auto_enable`.
+    /// - Note: Only available with the `unchecked-init` feature (enabled by default).
+    #[cfg(feature = "unchecked-init")]
+    pub fn new_with_enable(uart: UART, break_pin: GPIO, enable_pin: GPIO, delay: DELAY) -> Self {
+        Self::from_parts(
+            uart,
+            break_pin,
+            None,
+            Some(enable_pin),
+            delay,
+            LinBusConfig::default(),
+            false,
+        )
+    }
+
+    /// Initialize the MCP2003A transceiver with the given LIN bus configuration.
+    pub fn init(&mut self, config: LinBusConfig) {
+        self.config = config;
+        self.initialized = true;
+    }
+
+    /// Drain the UART receive buffer, reading and discarding bytes until none are left.
+    /// Stale bytes from a previously aborted frame would otherwise be read as part of the next frame.
+    pub fn clear_rx(&mut self) -> Result<(), Mcp2003aError<E>> {
+        self.rx_pending().map(|_| ())
+    }
 
+    /// Count the bytes pending in the UART receive buffer, e.g. to tell whether stale bytes were left behind
+    /// before a read. Returns how many bytes were pending.
+    ///
+    /// - Note: This is destructive: embedded-hal-nb has no way to count the received bytes without reading them,
+    ///   so the pending bytes are drained and discarded like `clear_rx`.
+    pub fn rx_pending(&mut self) -> Result<usize, Mcp2003aError<E>> {
+        let mut pending = 0;
         loop {
-            match self.uart.read(buffer).await {
-                Ok(len_read) => {
-                    // While there are some bytes in the uart buffer,
-                    // keep skipping until we find the header [0x55, id]
-
-                    // Check for the sync byte
-                    if !sync_byte_received {
-                        if buffer[0] == 0x55 {
-                            sync_byte_received = true;
-                        }
-                    }
-                    // Check for the id byte
-                    else if !id_byte_received {
-                        if buffer[1] == id {
-                            id_byte_received = true;
-                        } else {
-                            sync_byte_received = false;
-                        }
-                    }
-                    // Read the data bytes up until the provided buffer length
-                    else if data_bytes_received < buffer.len() {
-                        len += len_read;
-                        data_bytes_received += len_read;
-                    }
-                    // After the data bytes, read the checksum
-                    else if !checksum_received {
-                        checksum = buffer[len - 1];
-                        checksum_received = true;
-                        // We've read the whole frame
-                        break;
-                    }
+            match self.uart.read() {
+                Ok(_) => pending += 1,
+                Err(embedded_hal_nb::nb::Error::WouldBlock) => return Ok(pending),
+                Err(e) => return Err(Mcp2003aError::UartError(e)),
+            }
+        }
+    }
+
+    /// Flush the UART so none of the written bytes are still buffered. If the flush fails and the
+    /// `flush_fallback` allows it, wait the given nominal time of the written bytes instead.
+    fn flush_uart(&mut self, fallback_ns: u64) -> Result<(), Mcp2003aError<E>> {
+        match block!(self.uart.flush()) {
+            Ok(_) => Ok(()),
+            Err(_) if self.config.flush_fallback == FlushFallback::WaitByteTime => {
+                delay_ns_u64(&mut self.delay, fallback_ns);
+                Ok(())
+            }
+            Err(_) => Err(Mcp2003aError::UartWriteNotReady),
+        }
+    }
+
+    /// Write the bytes of a frame to the UART, waiting the configured `inter_byte_space` between them,
+    /// then flush the UART so none of the bytes are still buffered.
+    fn write_frame_bytes(&mut self, bytes: &[u8]) -> Result<(), Mcp2003aError<E>> {
+        let byte_time_ns = self.config.byte_time_ns();
+        let inter_byte_space_ns = self.config.inter_byte_space.get_duration_ns();
+        for (i, byte) in bytes.iter().enumerate() {
+            // Let the previous byte leave the UART before spacing the next one
+            if i > 0 && inter_byte_space_ns > 0 {
+                self.flush_uart(byte_time_ns)?;
+                delay_ns_u64(&mut self.delay, inter_byte_space_ns);
+            }
+            self.uart.write(*byte)?;
+        }
+
+        // Ensures that none of the previously written words are still buffered
+        let unflushed = if inter_byte_space_ns > 0 { 1 } else { bytes.len() as u64 };
+        self.flush_uart(unflushed * byte_time_ns)
+    }
+
+    /// Enable the transceiver by driving its enable pin high, then wait the configured `enable_settle`
+    /// if it was disabled. Does nothing without an enable pin.
+    pub fn enable(&mut self) -> Result<(), Mcp2003aError<E>> {
+        if let Some(ref mut pin) = self.enable_pin {
+            set_pin_level(pin, true)?;
+            if !self.enabled {
+                self.enabled = true;
+                delay_ns_u64(&mut self.delay, self.config.enable_settle.get_duration_ns());
+            }
+        }
+        Ok(())
+    }
+
+    /// Disable the transceiver by driving its enable pin low, e.g. to save energy between bursts of frames.
+    /// Does nothing without an enable pin.
+    pub fn disable(&mut self) -> Result<(), Mcp2003aError<E>> {
+        if let Some(ref mut pin) = self.enable_pin {
+            set_pin_level(pin, false)?;
+            self.enabled = false;
+        }
+        Ok(())
+    }
+
+    /// Enable the transceiver before using the bus if `auto_enable` is configured and it is not enabled yet.
+    fn auto_enable(&mut self) -> Result<(), Mcp2003aError<E>> {
+        if self.config.auto_enable && !self.enabled {
+            self.enable()?;
+        }
+        Ok(())
+    }
+
+    /// Send a break signal on the LIN bus, pausing execution for at least 730 microseconds (13 bits).
+    /// The break of the given duration is generated with the configured `break_method`, after waiting the configured
+    /// `bus_idle_before_break`.
+    fn send_break(&mut self, break_duration: LinBreakDuration) -> Result<(), Mcp2003aError<E>> {
+        self.ensure_initialized()?;
+        self.auto_enable()?;
+
+        // Let the bus settle after the previous transmission
+        delay_ns_u64(&mut self.delay, self.config.bus_idle_before_break.get_duration_ns());
+
+        // Calculate the duration of the break signal
+        let bit_period_ns = self.config.speed.get_bit_period_ns();
+        let break_duration_ns = LinBusConfig {
+            break_duration,
+            ..self.config
+        }
+        .break_dominant_time_ns();
+
+        match (self.config.break_method, self.tx_break_control) {
+            (BreakMethod::FramingError, Some(hold_tx)) => {
+                // Hold the TX line low past the stop bit, a framing error for the slaves
+                hold_tx(&mut self.uart, true);
+                self.delay.delay_ns(break_duration_ns);
+                hold_tx(&mut self.uart, false);
+            }
+            (BreakMethod::BreakPin | BreakMethod::FramingError, _) => {
+                // Start the break
+                set_pin_level(&mut self.break_pin, true)?;
+
+                // Break for the duration based on baud rate
+                self.delay.delay_ns(break_duration_ns);
+
+                // End the break
+                set_pin_level(&mut self.break_pin, false)?;
+            }
+            (BreakMethod::UartBreak, _) => {
+                // Lower the baud rate so the byte lasts the break duration
+                let break_baud_rate = self.break_baud_rate(break_duration_ns);
+                if let Some(switch_baud) = self.baud_switcher {
+                    switch_baud(&mut self.uart, break_baud_rate);
                 }
-                Err(e) => return Err(Mcp2003aError::AsyncUartError(e)),
+
+                // The 0x00 byte holds the bus dominant for the start bit and 8 data bits
+                self.uart.write(0x00)?;
+                let flushed = self.flush_uart(
+                    self.config.uart_framing.bits_per_byte() as u64 * 1_000_000_000 / break_baud_rate as u64,
+                );
+
+                // Restore the LIN bus speed, even if the flush failed
+                if let Some(switch_baud) = self.baud_switcher {
+                    switch_baud(&mut self.uart, self.config.speed.get_baud_rate());
+                }
+                flushed?;
             }
         }
 
-        // Inter-frame space delay
-        self.delay
-            .delay_ns(self.config.inter_frame_space.get_duration_ns())
-            .await;
+        // Break delimiter is 1 bit time
+        self.delay.delay_ns(bit_period_ns);
 
-        if !sync_byte_received {
-            return Err(Mcp2003aError::SyncByteNotReceivedBack);
+        Ok(())
+    }
+
+    /// Send a break signal of the given duration in microseconds on the LIN bus with the break pin,
+    /// followed by the standard 1 bit break delimiter. For bring-up, testing, and slaves with unusual
+    /// break requirements outside the `LinBreakDuration` options.
+    ///
+    /// - Note: Returns `BreakTooShort` if the duration is less than 11 bit periods at the configured bus speed,
+    ///   as a shorter dominant period is read by the slaves as a data byte instead of a break.
+    pub fn send_break_us(&mut self, duration_us: u32) -> Result<(), Mcp2003aError<E>> {
+        let bit_period_ns = self.config.speed.get_bit_period_ns();
+        let min_duration_us = (11 * bit_period_ns as u64).div_ceil(1_000) as u32;
+        if duration_us < min_duration_us {
+            return Err(Mcp2003aError::BreakTooShort(min_duration_us));
         }
-        if !id_byte_received {
-            return Err(Mcp2003aError::IdByteNotReceivedBack);
+
+        // Break for the requested duration
+        set_pin_level(&mut self.break_pin, true)?;
+        self.delay.delay_us(duration_us);
+        set_pin_level(&mut self.break_pin, false)?;
+
+        // Break delimiter is 1 bit time
+        self.delay.delay_ns(bit_period_ns);
+
+        Ok(())
+    }
+
+    /// Send only the break, the break delimiter, and the sync byte 0x55, e.g. to calibrate the break timing
+    /// on a scope during bring-up, triggering on the break and checking the bit times of the sync byte.
+    ///
+    /// - Note: This is a diagnostic primitive, not a complete frame: slaves see a header without an ID.
+    /// - Note: No inter-frame space is applied.
+    /// - Note: The configured `break_duration` is always sent, without the extension after `send_wakeup`,
+    ///   so the break on the scope is the one being calibrated. It does not count as a frame after the wakeup.
+    pub fn send_break_sync(&mut self) -> Result<(), Mcp2003aError<E>> {
+        self.send_break(self.config.break_duration)?;
+        self.write_frame_bytes(&[SYNC_BYTE])?;
+        self.record_tx(&[SYNC_BYTE]);
+        Ok(())
+    }
+
+    /// Drive a break with the break pin and measure how long the bus actually stayed dominant, in microseconds,
+    /// by sampling a pin that reads the bus back (e.g. the RXD line of the transceiver, which is low while dominant).
+    /// For bring-up, to verify the break timing against a scope.
+    ///
+    /// - Note: The bus is sampled every microsecond with `delay_us(1)`, for up to twice the configured break
+    ///   duration. Each sample also takes the time to read the pin, so the result underestimates the real
+    ///   dominant time by that overhead, and is only as accurate as the `DelayNs` implementation.
+    /// - Note: The break pin is held for the dominant time of the break, less the break delimiter if
+    ///   `break_includes_delimiter` is set, like the break of a frame.
+    /// - Note: The standard 1 bit break delimiter is applied after the measurement.
+    pub fn measure_break<SENSE: InputPin>(&mut self, sense: &mut SENSE) -> Result<u32, Mcp2003aError<E>> {
+        self.ensure_initialized()?;
+        self.auto_enable()?;
+
+        let bit_period_ns = self.config.speed.get_bit_period_ns();
+        let break_duration_us = self.config.break_dominant_time_ns().div_ceil(1_000);
+
+        // Start the break, sampling the bus for the configured duration
+        set_pin_level(&mut self.break_pin, true)?;
+        let mut dominant_us = 0;
+        let mut sensed = Ok(());
+        for _ in 0..break_duration_us {
+            match sense.is_low() {
+                Ok(dominant) => dominant_us += dominant as u32,
+                Err(_) => {
+                    sensed = Err(Mcp2003aError::GpioError);
+                    break;
+                }
+            }
+            self.delay.delay_us(1);
         }
-        if data_bytes_received == 0 {
-            return Err(Mcp2003aError::LinReadDeviceTimeoutNoResponse);
+
+        // End the break, even if reading the sense pin failed
+        set_pin_level(&mut self.break_pin, false)?;
+        sensed?;
+
+        // Keep sampling until the bus is released
+        for _ in 0..break_duration_us {
+            match sense.is_low() {
+                Ok(true) => dominant_us += 1,
+                Ok(false) => break,
+                Err(_) => return Err(Mcp2003aError::GpioError),
+            }
+            self.delay.delay_us(1);
+        }
+
+        // Break delimiter is 1 bit time
+        self.delay.delay_ns(bit_period_ns);
+
+        Ok(dominant_us)
+    }
+
+    /// Change how the break signal is generated without reconstructing the driver,
+    /// e.g. to fall back to another method when a device misbehaves with the configured one.
+    pub fn set_break_method(&mut self, method: BreakMethod) {
+        self.config.break_method = method;
+    }
+
+    /// Send a wakeup signal on the LIN bus, pausing execution for at least 250 microseconds.
+    /// The wakeup is driven on the separate wakeup pin if one was given, else on the break pin.
+    ///
+    /// - Note: there is an additional delay of the configured wakeup settle time after the wakeup signal
+    ///   to ensure the bus devices are ready to receive frames after activation.
+    /// - Note: Returns `WakeupTooLong` if the configured wakeup duration is longer than 5 milliseconds.
+    /// - Note: With the `FrameTraffic` wakeup methods, headers without an ID are sent after the pulse
+    ///   or instead of it, before the settle time.
+    /// - Note: The break of the next `post_wakeup_break_frames` frames is extended by `post_wakeup_break_extension`.
+    pub fn send_wakeup(&mut self) -> Result<(), Mcp2003aError<E>> {
+        self.auto_enable()?;
+
+        let method = self.config.wakeup_method;
+        if method.pulse() {
+            // Calculate the duration of the wakeup signal
+            let wakeup_duration_ns = self.config.wakeup_duration.get_duration_ns();
+
+            // Ensure the wakeup duration is less than 5 milliseconds
+            if wakeup_duration_ns > 5_000_000 {
+                return Err(Mcp2003aError::WakeupTooLong(wakeup_duration_ns));
+            }
+
+            // Start the wakeup signal
+            set_pin_level(self.wakeup_pin(), true)?;
+
+            // Wakeup for the duration
+            self.delay.delay_ns(wakeup_duration_ns);
+
+            // End the wakeup signal
+            set_pin_level(self.wakeup_pin(), false)?;
         }
-        if data_bytes_received < buffer.len() {
-            return Err(Mcp2003aError::LinReadOnlyPartialResponse(data_bytes_received));
+
+        // Generate bus traffic for the slaves waking on it
+        for _ in 0..method.traffic_headers() {
+            self.send_break_sync()?;
+            delay_ns_u64(&mut self.delay, self.config.inter_frame_space.get_duration_ns());
+        }
+
+        // Let the bus devices settle after the wakeup signal
+        delay_ns_u64(&mut self.delay, self.config.wakeup_settle.get_duration_ns());
+
+        // Extend the break of the first frames after the wakeup, if configured
+        self.post_wakeup_breaks = self.config.post_wakeup_break_frames;
+
+        Ok(())
+    }
+
+    /// Reset the LIN bus to a known idle state, to recover from a confused bus state after errors:
+    /// 1. Drain the UART receive buffer of any stale bytes.
+    /// 2. Hold the bus recessive (break pin low) for the configured `reset_idle` time,
+    ///    so the bus devices drop any partially received frame.
+    /// 3. If `wakeup` is true, send a wakeup signal, including its settle time.
+    pub fn reset_bus(&mut self, wakeup: bool) -> Result<(), Mcp2003aError<E>> {
+        self.clear_rx()?;
+
+        // Hold the bus recessive
+        set_pin_level(&mut self.break_pin, false)?;
+        delay_ns_u64(&mut self.delay, self.config.reset_idle.get_duration_ns());
+
+        if wakeup {
+            self.send_wakeup()?;
         }
-        if !checksum_received {
-            return Err(Mcp2003aError::LinReadNoChecksumReceived);
+
+        Ok(())
+    }
+
+    /// Send a wakeup signal only if the bus appears asleep, so an already active bus is not disrupted by a needless
+    /// wakeup pulse. The bus is probed with a single read of the UART, without waiting: a received byte or a line
+    /// error (e.g. a framing error) is bus activity. Returns whether the wakeup was sent.
+    ///
+    /// - Note: Only bytes already received are seen, so a bus quiet between two frames appears asleep.
+    /// - Note: Stale bytes in the UART receive buffer are bus activity too, use `clear_rx` first if there may be some.
+    /// - Note: The byte that signaled the activity is consumed.
+    pub fn ensure_awake(&mut self) -> Result<bool, Mcp2003aError<E>> {
+        if self.poll_bus_activity()? {
+            return Ok(false);
         }
+        self.send_wakeup()?;
+        Ok(true)
+    }
 
-        Ok(checksum)
+    /// Send the go-to-sleep command: a master request frame (ID 0x3C) with the data 0x00 0xFF 0xFF 0xFF 0xFF 0xFF
+    /// 0xFF 0xFF and the classic checksum, telling all slaves to enter sleep.
+    ///
+    /// - Note: This does not wait for the slaves to go to sleep, use `go_to_sleep` to verify the bus went quiet.
+    pub fn send_sleep_command(&mut self) -> Result<(), Mcp2003aError<E>> {
+        let checksum = ChecksumMode::Classic.get_checksum(GO_TO_SLEEP_PID, &GO_TO_SLEEP_DATA);
+        self.send_frame(GO_TO_SLEEP_PID, &GO_TO_SLEEP_DATA, checksum)?;
+        Ok(())
+    }
+
+    /// Send the go-to-sleep command like `send_sleep_command`, then verify the bus stays quiet for the configured
+    /// `sleep_verify` time, for a confirmed transition to sleep.
+    ///
+    /// - Note: The echo of the command is drained first, then any byte or line error received during the
+    ///   `sleep_verify` time is a node keeping the bus active, returned as `BusActiveAfterSleep`.
+    /// - Note: With `sleep_verify` set to `None`, the command is sent without verifying.
+    pub fn go_to_sleep(&mut self) -> Result<(), Mcp2003aError<E>> {
+        self.send_sleep_command()?;
+
+        let verify_ns = self.config.sleep_verify.get_duration_ns();
+        if verify_ns == 0 {
+            return Ok(());
+        }
+
+        // Drop the echo of the command, so only bytes sent after it are bus activity
+        while self.poll_bus_activity()? {}
+        delay_ns_u64(&mut self.delay, verify_ns);
+
+        // Line errors, e.g. the framing error of a break, are bus activity like the bytes
+        let mut received = 0;
+        while self.poll_bus_activity()? {
+            received += 1;
+        }
+        match received {
+            0 => Ok(()),
+            received => Err(Mcp2003aError::BusActiveAfterSleep(received)),
+        }
+    }
+
+    /// Park the driver until bus activity resumes, for low-power slave nodes: the break and wakeup pins are driven
+    /// low (bus recessive), then the UART is polled every millisecond until a byte is received.
+    /// The wakeup signal of another node, or the break of a frame, is received as a 0x00 byte or a framing error.
+    ///
+    /// - Note: The driver has no access to interrupts, so this polls with the delay. To sleep the MCU between polls
+    ///   instead, configure the UART RX pin as a wakeup source and use `sleep_until_wakeup_with`.
+    /// - Note: The byte that signaled the activity is consumed.
+    pub fn sleep_until_wakeup(&mut self) -> Result<(), Mcp2003aError<E>> {
+        self.park_pins()?;
+        while !self.poll_bus_activity()? {
+            self.delay.delay_ms(1);
+        }
+        Ok(())
+    }
+
+    /// Park the driver until bus activity resumes like `sleep_until_wakeup`, calling `sleep` between polls of
+    /// the UART instead of delaying, e.g. to enter a low-power mode of the MCU until its wakeup source fires.
+    ///
+    /// - Note: `sleep` must return when the UART may have received a byte, e.g. on the RX pin or UART interrupt.
+    /// - Note: The byte that signaled the activity is consumed.
+    pub fn sleep_until_wakeup_with(&mut self, mut sleep: impl FnMut()) -> Result<(), Mcp2003aError<E>> {
+        self.park_pins()?;
+        while !self.poll_bus_activity()? {
+            sleep();
+        }
+        Ok(())
+    }
+
+    /// Drive the break and wakeup pins low, leaving the bus recessive while the driver is parked.
+    fn park_pins(&mut self) -> Result<(), Mcp2003aError<E>> {
+        set_pin_level(&mut self.break_pin, false)?;
+        if let Some(ref mut pin) = self.wakeup_pin {
+            set_pin_level(pin, false)?;
+        }
+        Ok(())
+    }
+
+    /// Read a byte from the UART, returning whether there was bus activity: a byte or a line error such as
+    /// a framing error. Errors of the UART itself are returned.
+    fn poll_bus_activity(&mut self) -> Result<bool, Mcp2003aError<E>> {
+        match self.uart.read() {
+            Ok(_) => Ok(true),
+            Err(embedded_hal_nb::nb::Error::WouldBlock) => Ok(false),
+            Err(embedded_hal_nb::nb::Error::Other(e)) if e.kind() != embedded_hal_nb::serial::ErrorKind::Other => {
+                Ok(true)
+            }
+            Err(e) => Err(Mcp2003aError::UartError(e)),
+        }
+    }
+
+    /// Send a frame on the LIN bus with the given ID, data, and checksum.
+    /// The data length must be between 1 and 8 bytes, else `InvalidFrame` is returned.
+    ///
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: You must calculate the checksum based on your application and LIN version.
+    /// - Note: Only the sync byte, id, data, and checksum are written, the returned frame is padded with zeros.
+    /// - Note: Inter-frame space is applied after sending the frame.
+    pub fn send_frame(&mut self, id: u8, data: &[u8], checksum: u8) -> Result<[u8; MAX_FRAME_LEN], Mcp2003aError<E>> {
+        let break_duration = self.frame_break_duration();
+        self.send_frame_with_break(id, data, checksum, break_duration)
+    }
+
+    /// Send a frame on the LIN bus like `send_frame`, with the given break duration instead of the configured one
+    /// for this frame only, e.g. for slaves wanting a longer break for the first frame after a wakeup.
+    ///
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: Inter-frame space is applied after sending the frame.
+    pub fn send_frame_with_break(
+        &mut self,
+        id: u8,
+        data: &[u8],
+        checksum: u8,
+        break_duration: LinBreakDuration,
+    ) -> Result<[u8; MAX_FRAME_LEN], Mcp2003aError<E>> {
+        validate_frame(id, data, false)?;
+
+        // Send the break signal
+        self.send_break(break_duration)?;
+
+        // Send the rest of the frame
+        let frame = self.send_frame_no_break(id, data, checksum)?;
+
+        self.last_frame_duration_ns += LinBusConfig {
+            break_duration,
+            ..self.config
+        }
+        .nominal_break_time_ns();
+        Ok(frame)
+    }
+
+    /// Send a frame on the LIN bus like `send_frame`, assembling it in the given scratch buffer instead of
+    /// a fixed 11 byte array, to support proprietary frames with more than 8 data bytes without allocating.
+    /// Returns the length of the frame written from the start of the scratch buffer.
+    ///
+    /// - Note: Returns `ScratchBufferTooSmall` if the scratch buffer is shorter than `data.len() + 3`,
+    ///   and `InvalidFrame` if there is no data.
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: You must calculate the checksum based on your application and LIN version.
+    /// - Note: Inter-frame space is applied after sending the frame.
+    pub fn send_frame_scratch(
+        &mut self,
+        id: u8,
+        data: &[u8],
+        checksum: u8,
+        scratch: &mut [u8],
+    ) -> Result<usize, Mcp2003aError<E>> {
+        if data.is_empty() {
+            return Err(Mcp2003aError::InvalidFrame(FrameError::NoData));
+        }
+        let frame_len = data.len() + 3;
+        if scratch.len() < frame_len {
+            return Err(Mcp2003aError::ScratchBufferTooSmall(frame_len));
+        }
+
+        // Assemble the frame in the scratch buffer
+        scratch[0] = SYNC_BYTE;
+        scratch[1] = id;
+        scratch[2..2 + data.len()].copy_from_slice(data);
+        scratch[2 + data.len()] = checksum;
+
+        // Send the break signal
+        let break_duration = self.frame_break_duration();
+        self.send_break(break_duration)?;
+
+        self.write_frame(&scratch[..frame_len])?;
+        self.last_frame_duration_ns += LinBusConfig {
+            break_duration,
+            ..self.config
+        }
+        .nominal_break_time_ns();
+        Ok(frame_len)
+    }
+
+    /// Write the assembled frame after the break: the sync, ID, data, and checksum bytes, then apply the
+    /// inter-frame space. `last_frame_duration_ns` is set to the time of the frame without the break.
+    fn write_frame(&mut self, frame: &[u8]) -> Result<(), Mcp2003aError<E>> {
+        // Write the frame to the UART
+        self.write_frame_bytes(frame)?;
+        self.record_tx(frame);
+
+        // Inter-frame space delay
+        delay_ns_u64(&mut self.delay, self.config.inter_frame_space.get_duration_ns());
+
+        self.last_frame_duration_ns = self.send_frame_duration_ns(frame.len() - 3);
+        Ok(())
+    }
+
+    /// Send a frame on the LIN bus with the given ID, data, and checksum, without sending a break first.
+    /// Only the sync, ID, data, and checksum bytes are written.
+    ///
+    /// - Warning: A LIN frame must start with a break, so omitting it violates standard LIN framing.
+    ///   Only use this when the break was already sent, or for custom protocols that do not use one.
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: You must calculate the checksum based on your application and LIN version.
+    /// - Note: Inter-frame space is applied after sending the frame.
+    pub fn send_frame_no_break(
+        &mut self,
+        id: u8,
+        data: &[u8],
+        checksum: u8,
+    ) -> Result<[u8; MAX_FRAME_LEN], Mcp2003aError<E>> {
+        validate_frame(id, data, false)?;
+        let data_len = data.len();
+        self.ensure_initialized()?;
+
+        // Calculate the frame
+        let mut frame = [0; MAX_FRAME_LEN];
+
+        // This is the constant value to lead every frame with per the LIN specification.
+        // In bits, this is "10101010" or "0x55" in hex.
+        frame[0] = SYNC_BYTE;
+
+        frame[1] = id;
+        frame[2..2 + data_len].copy_from_slice(data);
+        frame[2 + data_len] = checksum;
+
+        self.write_frame(&frame[..data_len + 3])?;
+        Ok(frame)
+    }
+
+    /// Send a frame on the LIN bus like `send_frame`, then count the bytes the transceiver echoed back.
+    /// Returns how many of the frame bytes (sync, ID, data, and checksum) were echoed, so a count less than
+    /// `data.len() + 3` means the transceiver or the bus dropped bytes.
+    ///
+    /// - Note: This only counts the echoed bytes, it does not compare them to the sent frame.
+    ///   It is a lightweight confirmation for high-rate senders.
+    /// - Note: Up to `break_byte_tolerance` break bytes read back before the frame are not counted.
+    /// - Note: Stale bytes in the UART receive buffer are counted too, use `clear_rx` first if there may be some.
+    pub fn send_frame_confirmed(&mut self, id: u8, data: &[u8], checksum: u8) -> Result<usize, Mcp2003aError<E>> {
+        self.send_frame(id, data, checksum)?;
+
+        let expected = data.len() + 3;
+        let mut echoed = 0;
+        let mut break_bytes_skipped = 0;
+        while echoed < expected {
+            match self.uart.read() {
+                // The break may be read back as a 0x00 byte before the sync byte
+                Ok(0x00) if echoed == 0 && break_bytes_skipped < self.config.break_byte_tolerance => {
+                    break_bytes_skipped += 1;
+                }
+                Ok(_) => echoed += 1,
+                Err(embedded_hal_nb::nb::Error::WouldBlock) => break,
+                // The break may be read back as a framing error before the sync byte
+                Err(embedded_hal_nb::nb::Error::Other(e))
+                    if echoed == 0
+                        && e.kind() == embedded_hal_nb::serial::ErrorKind::FrameFormat
+                        && break_bytes_skipped < self.config.break_byte_tolerance =>
+                {
+                    break_bytes_skipped += 1;
+                }
+                Err(e) => return Err(Mcp2003aError::UartError(e)),
+            }
+        }
+
+        Ok(echoed)
+    }
+
+    /// Publish a frame on the LIN bus, where the master provides the data, then read back the echo of the whole
+    /// frame to confirm it appeared on the bus unchanged. Returns true if the echoed sync, ID, data, and checksum
+    /// bytes all match the sent frame, false if any is missing or different.
+    /// To read a frame where a slave provides the data (subscribe), use `read_frame` instead.
+    ///
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: Requires the transceiver to echo the bus, as with the MCP2003A and `expect_header_echo`.
+    /// - Note: Up to `break_byte_tolerance` break bytes read back before the frame are skipped.
+    /// - Note: Stale bytes in the UART receive buffer are read first, use `clear_rx` first if there may be some.
+    pub fn publish_frame(&mut self, id: u8, data: &[u8], checksum: u8) -> Result<bool, Mcp2003aError<E>> {
+        self.send_frame(id, data, checksum)?;
+        self.echo_matches(id, data, checksum)
+    }
+
+    /// Read back the echo of a frame just sent, returning whether its sync, ID, data, and checksum bytes
+    /// all match the frame.
+    fn echo_matches(&mut self, id: u8, data: &[u8], checksum: u8) -> Result<bool, Mcp2003aError<E>> {
+        // The echo is read like a response to the header, with the data bytes provided by the master
+        let mut echo = [0u8; MAX_DATA_LEN];
+        let mut reader = ResponseReader::new(id, &mut echo[..data.len()], &self.config);
+        // The bytes after the header are the echo of the published data, not extra echoed bytes to skip
+        reader.skip_no_echo_bytes();
+        loop {
+            match self.uart.read() {
+                Ok(byte) => {
+                    if reader.push(byte) {
+                        break;
+                    }
+                }
+                Err(embedded_hal_nb::nb::Error::WouldBlock) => break,
+                // The break may be read back as a framing error before the sync byte
+                Err(embedded_hal_nb::nb::Error::Other(e))
+                    if e.kind() == embedded_hal_nb::serial::ErrorKind::FrameFormat && reader.skip_break() => {}
+                Err(e) => return Err(Mcp2003aError::UartError(e)),
+            }
+        }
+
+        match reader.finish::<E>() {
+            Ok(echoed_checksum) => Ok(echoed_checksum == checksum && &echo[..data.len()] == data),
+            Err(_) => Ok(false),
+        }
+    }
+
+    /// Send a LIN 2.x frame on the LIN bus with the given raw ID and data, using the enhanced checksum.
+    /// The PID is calculated from the raw ID, then the enhanced checksum is calculated over the PID and data.
+    ///
+    /// - Note: The raw id must be the 6-bit LIN ID (0x00 to 0x3F), not the PID, or `InvalidId` is returned.
+    /// - Note: Diagnostic frames (0x3C and 0x3D) use the classic checksum, use `send_frame` for those.
+    /// - Note: Inter-frame space is applied after sending the frame.
+    pub fn send_frame_enhanced(&mut self, raw_id: u8, data: &[u8]) -> Result<[u8; MAX_FRAME_LEN], Mcp2003aError<E>> {
+        if raw_id > 0x3F {
+            return Err(Mcp2003aError::InvalidFrame(FrameError::InvalidId(raw_id)));
+        }
+        let pid = get_pid(raw_id);
+        let checksum = ChecksumMode::Enhanced.get_checksum(pid, data);
+        self.send_frame(pid, data, checksum)
+    }
+
+    /// Send the frame with the given raw 6-bit LIN ID and data, with the checksum mode defined for it in the
+    /// frame table set with `set_frame_table`. The PID is calculated from the raw ID, then the checksum with
+    /// the mode of the table, including `ChecksumMode::Custom` for nonstandard checksums.
+    ///
+    /// - Note: Returns `FrameNotInTable` if the ID is not in the frame table, or no table was set.
+    /// - Note: Returns `LengthMismatch` if the data length differs from the length defined in the frame table.
+    /// - Note: Inter-frame space is applied after sending the frame.
+    pub fn send_frame_auto(&mut self, raw_id: u8, data: &[u8]) -> Result<[u8; MAX_FRAME_LEN], Mcp2003aError<E>> {
+        let definition = self.frame_definition(raw_id)?;
+        if data.len() != definition.len as usize {
+            return Err(Mcp2003aError::InvalidFrame(FrameError::LengthMismatch {
+                expected: definition.len as usize,
+                actual: data.len(),
+            }));
+        }
+        let pid = get_pid(raw_id);
+        let checksum = definition.checksum_mode.get_checksum(pid, data);
+        self.send_frame(pid, data, checksum)
+    }
+
+    /// Send a frame built with a `FrameBuilder` on the LIN bus.
+    /// The PID and checksum were already calculated and validated when the frame was built.
+    ///
+    /// - Note: Inter-frame space is applied after sending the frame.
+    pub fn send_wire(&mut self, frame: &LinFrame) -> Result<[u8; MAX_FRAME_LEN], Mcp2003aError<E>> {
+        self.send_frame(frame.pid(), frame.data(), frame.checksum())
+    }
+
+    /// Send a frame on the LIN bus, retrying after a backoff delay if the transmit fails or another node
+    /// transmitted at the same time. Up to 3 attempts are made, waiting `backoff_units * attempt` bit periods
+    /// before each retry.
+    ///
+    /// - Note: Standard LIN is single-master, so contention does not happen on a compliant bus.
+    ///   This is only intended for nonstandard topologies where more than one node may transmit headers.
+    /// - Note: Lost arbitration is detected from the echo of the frame like `publish_frame`, when
+    ///   `expect_header_echo` is enabled. Returns `ArbitrationLost` if the echo still differs after the last attempt.
+    /// - Note: Only retryable errors are retried, see `Mcp2003aError::is_retryable`.
+    /// - Note: There is no RNG in `no-std`, so the backoff is deterministic. Give each node a different
+    ///   `backoff_units` value (e.g. derived from its node address) so their retries do not collide again.
+    pub fn send_frame_with_backoff(
+        &mut self,
+        id: u8,
+        data: &[u8],
+        checksum: u8,
+        backoff_units: u16,
+    ) -> Result<[u8; MAX_FRAME_LEN], Mcp2003aError<E>> {
+        let bit_period_ns = self.config.speed.get_bit_period_ns() as u64;
+        let mut attempt = 1;
+        loop {
+            let result = self.send_frame(id, data, checksum).and_then(|frame| {
+                // The echo differs from the frame when another node transmitted at the same time
+                if !self.config.expect_header_echo || self.echo_matches(id, data, checksum)? {
+                    Ok(frame)
+                } else {
+                    Err(Mcp2003aError::ArbitrationLost)
+                }
+            });
+            match result {
+                Ok(frame) => return Ok(frame),
+                Err(e) if attempt >= 3 || !e.is_retryable() => return Err(e),
+                Err(_) => {
+                    // Back off for a node-specific number of bit periods before retrying
+                    delay_ns_u64(&mut self.delay, backoff_units as u64 * attempt * bit_period_ns);
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Send the same frame the given number of times like `send_frame`, for fire-and-forget commands on noisy buses
+    /// where the master cannot confirm the slave received them.
+    ///
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: Inter-frame space is applied after each repetition.
+    /// - Note: The first error stops the repetitions and is returned. Nothing is sent if `times` is 0.
+    /// - Note: `last_frame_duration_ns` is the total of all the repetitions sent.
+    pub fn send_frame_repeated(
+        &mut self,
+        id: u8,
+        data: &[u8],
+        checksum: u8,
+        times: u8,
+    ) -> Result<(), Mcp2003aError<E>> {
+        let mut duration_ns = 0;
+        for _ in 0..times {
+            self.send_frame(id, data, checksum)?;
+            duration_ns += self.last_frame_duration_ns;
+        }
+        self.last_frame_duration_ns = duration_ns;
+        Ok(())
+    }
+
+    /// Send each of the given frames in turn as `(id, data, checksum)`, like `send_frame`, in a single call for
+    /// masters sending many frames per schedule cycle. Whether each frame was sent is set at its index in `sent`,
+    /// and the number of frames sent is returned.
+    ///
+    /// - Note: The ids must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: A frame failing to send does not stop the batch, its slot is simply lost like in a schedule.
+    ///   Pin errors stop the batch and are returned, as no break can be sent after them.
+    /// - Note: Each frame is still flushed before the next break, as the break must not start while bytes
+    ///   of the previous frame are on the bus. The inter-frame space is applied after each frame.
+    /// - Note: `last_frame_duration_ns` is the total of all the frames sent.
+    /// - Note: Panics if `sent` is shorter than `frames`.
+    pub fn send_frames(&mut self, frames: &[(u8, &[u8], u8)], sent: &mut [bool]) -> Result<usize, Mcp2003aError<E>> {
+        assert!(sent.len() >= frames.len(), "There must be a result for each frame");
+        let mut count = 0;
+        let mut duration_ns = 0;
+        for (i, (id, data, checksum)) in frames.iter().enumerate() {
+            sent[i] = match self.send_frame(*id, data, *checksum) {
+                Ok(_) => true,
+                Err(Mcp2003aError::GpioError) => {
+                    self.last_frame_duration_ns = duration_ns;
+                    return Err(Mcp2003aError::GpioError);
+                }
+                Err(_) => false,
+            };
+            if sent[i] {
+                count += 1;
+                duration_ns += self.last_frame_duration_ns;
+            }
+        }
+
+        self.last_frame_duration_ns = duration_ns;
+        Ok(count)
+    }
+
+    /// Send a request frame on the LIN bus, then read the response frame into the buffer with the same ID,
+    /// for the common request/response pattern of polling a slave. Returns the checksum of the response.
+    ///
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: With `auto_pid` configured, the id can be the raw 6-bit ID and the PID is sent for both headers.
+    /// - Note: You must calculate the request checksum and validate the response checksum.
+    /// - Note: The inter-frame space is applied once between the request and the response, and after the response.
+    /// - Note: The UART receive buffer is drained after the request, to drop its echo.
+    pub fn transact(
+        &mut self,
+        id: u8,
+        request: &[u8],
+        checksum: u8,
+        response: &mut [u8],
+    ) -> Result<u8, Mcp2003aError<E>> {
+        let id = self.header_id(id);
+        self.send_frame(id, request, checksum)?;
+        let send_duration_ns = self.last_frame_duration_ns;
+
+        // Drop the echo of the request, so its header is not mistaken for the echo of the response header
+        self.clear_rx()?;
+
+        // The inter-frame space after the request already separates it from the response
+        let timeout = self.config.read_device_response_timeout;
+        let result = self
+            .read_response(id, response, timeout, LinInterFrameSpace::None, None)
+            .map(|(_, checksum)| checksum);
+
+        // Inter-frame space delay
+        delay_ns_u64(&mut self.delay, self.config.inter_frame_space.get_duration_ns());
+
+        self.last_frame_duration_ns += send_duration_ns + self.config.inter_frame_space.get_duration_ns();
+        result
+    }
+
+    /// Send a request frame like `transact`, with its checksum calculated with `tx_mode`, then read the response
+    /// and validate its checksum with `rx_mode`. For mixed networks where the request and the response use
+    /// different checksums, e.g. a classic request to a LIN 1.3 gateway answering with an enhanced response.
+    /// Returns the checksum mode that matched and the checksum of the response.
+    ///
+    /// - Note: The id must be the PID, as the enhanced checksum is calculated over it.
+    /// - Note: With `auto_pid` configured, the id can be the raw 6-bit ID and both checksums use the PID.
+    /// - Note: A `tx_mode` of `ChecksumMode::Auto` sends the enhanced checksum.
+    pub fn transact_validated(
+        &mut self,
+        id: u8,
+        request: &[u8],
+        tx_mode: ChecksumMode,
+        response: &mut [u8],
+        rx_mode: ChecksumMode,
+    ) -> Result<(ChecksumMode, u8), Mcp2003aError<E>> {
+        let id = self.header_id(id);
+        let checksum = self.transact(id, request, tx_mode.get_checksum(id, request), response)?;
+        match rx_mode.validate(id, response, checksum) {
+            Some(matched) => Ok((matched, checksum)),
+            None => Err(Mcp2003aError::LinReadInvalidChecksum(checksum)),
+        }
+    }
+
+    /// Read the error frame of a transceiver or slave exposing its error state over a diagnostic frame, then
+    /// send the command clearing it. The error frame is read into `errors` and its checksum validated with the
+    /// checksum mode, then the clear frame is sent with `clear_data` and its checksum calculated with the mode.
+    /// Returns the checksum of the error frame.
+    ///
+    /// - Note: The error and clear frames are application-specific, you must supply their IDs and the clear data.
+    /// - Note: The ids must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: The clear frame is only sent once the errors were read, so errors are never cleared unseen.
+    /// - Note: Inter-frame space is applied between and after the frames.
+    pub fn read_and_clear_errors(
+        &mut self,
+        read_id: u8,
+        errors: &mut [u8],
+        clear_id: u8,
+        clear_data: &[u8],
+        mode: ChecksumMode,
+    ) -> Result<u8, Mcp2003aError<E>> {
+        let (_, checksum) = self.read_frame_validated(read_id, errors, mode)?;
+        self.send_frame(clear_id, clear_data, mode.get_checksum(clear_id, clear_data))?;
+        Ok(checksum)
+    }
+
+    /// Poll each of the given IDs in turn, reading the response to each into the same index of `out`.
+    /// Headers are sent back to back without the inter-frame space between them, which is only applied once
+    /// after the last ID, to speed up diagnostic scans compared to calling `read_frame` in a loop.
+    ///
+    /// Sets the data length of the response to each ID at its index in `lens`, or `None` if it did not respond,
+    /// and returns the number of IDs that responded:
+    /// - A response of 1 to 8 bytes is accepted when it ends with a valid classic or enhanced checksum.
+    /// - Unused bytes of the row in `out` are set to zero.
+    ///
+    /// - Note: The ids must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: With `auto_pid` configured, the ids can be raw 6-bit IDs and the checksums are validated over the PIDs.
+    /// - Note: Returns `OutputTooSmall` if `out` or `lens` has fewer entries than `ids`.
+    /// - Note: Serial errors stop the polling and are returned.
+    pub fn poll_ids(
+        &mut self,
+        ids: &[u8],
+        out: &mut [[u8; MAX_DATA_LEN]],
+        lens: &mut [Option<u8>],
+    ) -> Result<usize, Mcp2003aError<E>> {
+        if out.len() < ids.len() || lens.len() < ids.len() {
+            return Err(Mcp2003aError::OutputTooSmall(ids.len()));
+        }
+
+        let timeout = self.config.read_device_response_timeout;
+        let mut responded = 0;
+        let mut duration_ns = 0;
+        let mut result = Ok(());
+        for (i, id) in ids.iter().enumerate() {
+            let read = self
+                .read_response(*id, &mut out[i], timeout, LinInterFrameSpace::None, None)
+                .map(|(_, checksum)| checksum);
+            duration_ns += self.last_frame_duration_ns;
+            match polled_response_len(self.header_id(*id), &mut out[i], read) {
+                Ok(len) => {
+                    lens[i] = len;
+                    responded += len.is_some() as usize;
+                }
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+
+        // Inter-frame space delay
+        delay_ns_u64(&mut self.delay, self.config.inter_frame_space.get_duration_ns());
+
+        self.last_frame_duration_ns = duration_ns + self.config.inter_frame_space.get_duration_ns();
+        result.map(|_| responded)
+    }
+
+    /// Probe each ID of the range for an active slave, sending its header and recording in `results` whether
+    /// any response bytes were received, at the index of the ID from the start of the range.
+    /// Returns the number of IDs that responded, for a quick inventory of the live frame IDs on the bus.
+    ///
+    /// - Note: The IDs of the range are the 6-bit frame IDs (0x00 to 0x3F), the PID is sent for each of them.
+    /// - Note: Returns `OutputTooSmall` if `results` has fewer entries than the range,
+    ///   and `InvalidFrame` if the range goes past 0x3F.
+    /// - Note: The inter-frame space is applied between probes, like consecutive `read_frame` calls.
+    /// - Note: Serial errors stop the scan and are returned.
+    pub fn scan_bus(
+        &mut self,
+        id_range: core::ops::RangeInclusive<u8>,
+        results: &mut [bool],
+    ) -> Result<usize, Mcp2003aError<E>> {
+        if *id_range.end() > 0x3F {
+            return Err(Mcp2003aError::InvalidFrame(FrameError::InvalidId(*id_range.end())));
+        }
+        if results.len() < id_range.clone().count() {
+            return Err(Mcp2003aError::OutputTooSmall(id_range.count()));
+        }
+
+        let mut responsive = 0;
+        let mut duration_ns = 0;
+        for (i, id) in id_range.enumerate() {
+            let mut buffer = [0u8; MAX_DATA_LEN];
+            let read = self.read_frame(get_pid(id), &mut buffer);
+            duration_ns += self.last_frame_duration_ns;
+            results[i] = match read {
+                Ok(_)
+                | Err(Mcp2003aError::LinReadOnlyPartialResponse { .. })
+                | Err(Mcp2003aError::LinReadNoChecksumReceived) => true,
+                Err(e @ Mcp2003aError::UartError(_))
+                | Err(e @ Mcp2003aError::AsyncUartError(_))
+                | Err(e @ Mcp2003aError::UartWriteNotReady)
+                | Err(e @ Mcp2003aError::GpioError) => {
+                    self.last_frame_duration_ns = duration_ns;
+                    return Err(e);
+                }
+                Err(_) => false,
+            };
+            if results[i] {
+                responsive += 1;
+            }
+        }
+
+        self.last_frame_duration_ns = duration_ns;
+        Ok(responsive)
+    }
+
+    /// Read a frame from the LIN bus with the given ID into the buffer, like `read_frame`, then validate the
+    /// received checksum with the given checksum mode. Returns the checksum mode that matched and the checksum.
+    /// With `ChecksumMode::Auto`, the enhanced then the classic checksum are tried, for mixed-version networks.
+    ///
+    /// - Note: The id must be the PID, as the enhanced checksum is calculated over it.
+    pub fn read_frame_validated(
+        &mut self,
+        id: u8,
+        buffer: &mut [u8],
+        mode: ChecksumMode,
+    ) -> Result<(ChecksumMode, u8), Mcp2003aError<E>> {
+        let checksum = self.read_frame(id, buffer)?;
+        match mode.validate(self.header_id(id), buffer, checksum) {
+            Some(matched) => Ok((matched, checksum)),
+            None => Err(Mcp2003aError::LinReadInvalidChecksum(checksum)),
+        }
+    }
+
+    /// Read the frame with the given raw 6-bit LIN ID, with the data length and checksum mode defined for it
+    /// in the frame table set with `set_frame_table`. The PID is calculated from the raw ID, and the checksum
+    /// is validated like `read_frame_validated`. Returns the frame read.
+    ///
+    /// - Note: Returns `FrameNotInTable` if the ID is not in the frame table, or no table was set.
+    pub fn read_frame_auto(&mut self, raw_id: u8) -> Result<ParsedFrame, Mcp2003aError<E>> {
+        let definition = self.frame_definition(raw_id)?;
+        let pid = get_pid(raw_id);
+        let mut data = [0u8; MAX_DATA_LEN];
+        let data = &mut data[..definition.len as usize];
+        let (checksum_mode, checksum) = self.read_frame_validated(pid, data, definition.checksum_mode)?;
+        Ok(ParsedFrame::new(pid, data, checksum, checksum_mode))
+    }
+
+    /// Read a frame from the LIN bus with the given ID into the buffer, like `read_frame`, then run the given
+    /// integrity check over the data, e.g. a CRC embedded in a proprietary payload.
+    ///
+    /// - Note: The integrity check is in addition to the LIN checksum, which you still must validate.
+    pub fn read_frame_checked<C: IntegrityCheck>(
+        &mut self,
+        id: u8,
+        buffer: &mut [u8],
+        check: &C,
+    ) -> Result<u8, Mcp2003aError<E>> {
+        let checksum = self.read_frame(id, buffer)?;
+        if !check.check(buffer) {
+            return Err(Mcp2003aError::IntegrityCheckFailed(checksum));
+        }
+        Ok(checksum)
+    }
+
+    /// Read a frame from the LIN bus with the given ID into the buffer.
+    /// Fills the buffer and returns the checksum is received after the data.
+    ///
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: Inter-frame space is applied after reading the frame.
+    /// - Note: Assumes your buffer is the size of the data you expect to receive.
+    /// - Note: You must decide how to validate the checksum based on your application and LIN version.
+    /// - Note: The UART receive buffer is drained first if `clear_rx_before_read` is configured.
+    /// - Note: The header echo is expected before the response unless `expect_header_echo` is disabled.
+    /// - Note: The buffer after the received data bytes is zeroed, so reusing a buffer never leaks a previous frame.
+    /// - Note: With `auto_pid` configured, the id can be the raw 6-bit ID and the PID is calculated from it.
+    pub fn read_frame(&mut self, id: u8, buffer: &mut [u8]) -> Result<u8, Mcp2003aError<E>> {
+        self.read_frame_timeout(id, buffer, self.config.read_device_response_timeout)
+    }
+
+    /// Read a frame from the LIN bus with the given ID like `read_frame`, into a buffer that does not need to be
+    /// initialized, to avoid zeroing large buffers. Returns the number of data bytes written from the start of
+    /// the buffer, which are then initialized, and the checksum.
+    ///
+    /// - Note: The response is expected to be the length of the buffer, up to 8 data bytes.
+    ///   The buffer past the returned length is left as it was.
+    /// - Note: Nothing is written to the buffer if an error is returned.
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: Inter-frame space is applied after reading the frame.
+    pub fn read_frame_uninit(
+        &mut self,
+        id: u8,
+        buffer: &mut [MaybeUninit<u8>],
+    ) -> Result<(usize, u8), Mcp2003aError<E>> {
+        // The response is read into a frame sized buffer, then only the bytes received are written
+        let len = buffer.len().min(MAX_DATA_LEN);
+        let mut data = [0u8; MAX_DATA_LEN];
+        let checksum = self.read_frame(id, &mut data[..len])?;
+        write_uninit(buffer, &data[..len]);
+        Ok((len, checksum))
+    }
+
+    /// Read a frame from the LIN bus with the given ID into the buffer like `read_frame`, for variable length frames
+    /// where the slave may send fewer data bytes than the buffer holds. Returns the number of data bytes received
+    /// and the checksum.
+    ///
+    /// - Note: A response of at least `min_data_len` data bytes is accepted when its last byte is a valid classic
+    ///   or enhanced checksum of the bytes before it. Shorter responses return `LinReadOnlyPartialResponse`.
+    /// - Note: The buffer after the received data bytes is zeroed.
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: Inter-frame space is applied after reading the frame.
+    pub fn read_frame_min_len(
+        &mut self,
+        id: u8,
+        buffer: &mut [u8],
+        min_data_len: usize,
+    ) -> Result<(usize, u8), Mcp2003aError<E>> {
+        let result = self.read_frame(id, buffer);
+        short_response(self.header_id(id), buffer, min_data_len, result)
+    }
+
+    /// Send a header on the LIN bus with the given ID and read a response of only a checksum byte, without data,
+    /// as some slaves send to acknowledge a command. Returns the checksum.
+    ///
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: Inter-frame space is applied after reading the frame.
+    /// - Note: Returns `LinReadDeviceTimeoutNoResponse` if the slave did not respond.
+    pub fn read_ack(&mut self, id: u8) -> Result<u8, Mcp2003aError<E>> {
+        self.read_frame(id, &mut [])
+    }
+
+    /// Read a frame from the LIN bus with the given ID into the buffer like `read_frame`, waiting the given
+    /// response timeout instead of the configured one, e.g. for a slave known to be slower than the others.
+    ///
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: Inter-frame space is applied after reading the frame.
+    /// - Note: Assumes your buffer is the size of the data you expect to receive.
+    /// - Note: You must decide how to validate the checksum based on your application and LIN version.
+    pub fn read_frame_timeout(
+        &mut self,
+        id: u8,
+        buffer: &mut [u8],
+        timeout: LinReadDeviceResponseTimeout,
+    ) -> Result<u8, Mcp2003aError<E>> {
+        self.read_response(id, buffer, timeout, self.config.inter_frame_space, None)
+            .map(|(_, checksum)| checksum)
+    }
+
+    /// Read a frame from the LIN bus with the given ID into the buffer like `read_frame`, for self-describing
+    /// payloads whose length is not known a priori, e.g. with an in-band length marker. Data bytes are read until
+    /// the given predicate returns true for the data received so far, then the next byte is read as the checksum.
+    /// Returns the number of data bytes received and the checksum.
+    ///
+    /// - Note: Standard LIN frames have a fixed length per ID and do not need this, proprietary protocols may.
+    /// - Note: At most the length of the buffer is read, a predicate that never returns true reads a full buffer.
+    /// - Note: The response timeout is waited for a response filling the buffer.
+    /// - Note: The buffer after the received data bytes is zeroed.
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: Inter-frame space is applied after reading the frame.
+    pub fn read_frame_until(
+        &mut self,
+        id: u8,
+        buffer: &mut [u8],
+        done: impl Fn(&[u8]) -> bool,
+    ) -> Result<(usize, u8), Mcp2003aError<E>> {
+        let timeout = self.config.read_device_response_timeout;
+        self.read_response(id, buffer, timeout, self.config.inter_frame_space, Some(&done))
+    }
+
+    /// Send the header and read the response into the buffer, ending the data bytes early when the given
+    /// predicate returns true. The given inter-frame space is applied before and after the frame, so chained reads
+    /// can leave it out without changing the configuration. Returns the number of data bytes received and the checksum.
+    fn read_response(
+        &mut self,
+        id: u8,
+        buffer: &mut [u8],
+        timeout: LinReadDeviceResponseTimeout,
+        inter_frame_space: LinInterFrameSpace,
+        done: Option<FrameComplete<'_>>,
+    ) -> Result<(usize, u8), Mcp2003aError<E>> {
+        let response_timeout_ns = self.response_timeout_ns(timeout, buffer.len());
+        let inter_frame_space_ns = inter_frame_space.get_duration_ns();
+        let id = self.header_id(id);
+
+        // Inter-frame space delay
+        delay_ns_u64(&mut self.delay, inter_frame_space_ns);
+
+        // Drop any stale bytes so they are not mistaken for the header echo
+        if self.config.clear_rx_before_read {
+            self.clear_rx()?;
+        }
+
+        // Send the break signal to notify the device of the start of a frame
+        let break_duration = self.frame_break_duration();
+        self.send_break(break_duration)?;
+
+        // Write the header to UART
+        let header = [SYNC_BYTE, id];
+        for byte in header.iter() {
+            self.uart.write(*byte)?;
+        }
+        self.record_tx(&header);
+        self.record_rx(&[], true);
+
+        // Delay to ensure the header has time to be received and responded to by the device
+        delay_ns_u64(&mut self.delay, response_timeout_ns);
+
+        // Read the response from the device
+        // NOTE: The mcp2003a will replay the header back to you when you read.
+        let mut reader = ResponseReader::new(id, buffer, &self.config);
+        if let Some(done) = done {
+            reader = reader.until(done);
+        }
+        let mut checksum_wait_ns = self.config.checksum_wait.get_duration_ns();
+        loop {
+            match self.uart.read() {
+                Ok(byte) => {
+                    self.record_rx(&[byte], false);
+                    if reader.push(byte) {
+                        // We've read the whole frame
+                        break;
+                    }
+                }
+                Err(embedded_hal_nb::nb::Error::WouldBlock) if checksum_wait_ns > 0 && reader.awaiting_checksum() => {
+                    // Give a late checksum byte one more chance to arrive
+                    delay_ns_u64(&mut self.delay, checksum_wait_ns);
+                    checksum_wait_ns = 0;
+                }
+                Err(embedded_hal_nb::nb::Error::WouldBlock) => {
+                    // If we get a WouldBlock error, we've read all the bytes in the buffer
+                    break;
+                }
+                // The break may be read back as a framing error before the sync byte
+                Err(embedded_hal_nb::nb::Error::Other(e))
+                    if e.kind() == embedded_hal_nb::serial::ErrorKind::FrameFormat && reader.skip_break() => {}
+                Err(e) => return Err(Mcp2003aError::UartError(e)),
+            }
+        }
+
+        // Inter-frame space delay
+        delay_ns_u64(&mut self.delay, inter_frame_space_ns);
+
+        self.last_frame_duration_ns = self.read_frame_duration_ns(response_timeout_ns, inter_frame_space_ns);
+
+        let len = reader.data_bytes_received;
+        reader.finish().map(|checksum| (len, checksum))
+    }
+
+    /// Send a header on the LIN bus with the given ID and pass each received byte to the callback, stopping when
+    /// it returns false or no more bytes were received. Returns the number of bytes passed to the callback.
+    /// This allows parsing non-standard responses, e.g. of proprietary protocols, while reusing the break and header.
+    ///
+    /// - Note: The id is sent as given, `auto_pid` is not applied.
+    /// - Note: Bypasses the sync, echo, and checksum handling of `read_frame`: the header echo, break bytes, and
+    ///   checksum are all passed to the callback as received. Up to `break_byte_tolerance` breaks read back as
+    ///   framing errors before any byte are passed as 0x00 bytes, other serial errors end the read.
+    /// - Note: Waits the configured response timeout for a response of 8 data bytes before reading.
+    /// - Note: Inter-frame space is applied before and after reading.
+    /// - Note: The break is extended after `send_wakeup` like the break of `read_frame`.
+    pub fn read_raw(&mut self, id: u8, mut f: impl FnMut(u8) -> bool) -> Result<usize, Mcp2003aError<E>> {
+        let response_timeout_ns = self.response_timeout_ns(self.config.read_device_response_timeout, MAX_DATA_LEN);
+
+        // Inter-frame space delay
+        delay_ns_u64(&mut self.delay, self.config.inter_frame_space.get_duration_ns());
+
+        if self.config.clear_rx_before_read {
+            self.clear_rx()?;
+        }
+
+        let break_duration = self.frame_break_duration();
+        self.send_break(break_duration)?;
+
+        let header = [SYNC_BYTE, id];
+        for byte in header.iter() {
+            self.uart.write(*byte)?;
+        }
+        self.record_tx(&header);
+        self.record_rx(&[], true);
+
+        delay_ns_u64(&mut self.delay, response_timeout_ns);
+
+        let mut received = 0;
+        let mut breaks = 0;
+        loop {
+            let byte = match self.uart.read() {
+                Ok(byte) => byte,
+                Err(embedded_hal_nb::nb::Error::WouldBlock) => break,
+                // The break may be read back as a framing error before the sync byte, passed on like a 0x00 break byte
+                Err(embedded_hal_nb::nb::Error::Other(e))
+                    if e.kind() == embedded_hal_nb::serial::ErrorKind::FrameFormat
+                        && received == breaks as usize
+                        && breaks < self.config.break_byte_tolerance =>
+                {
+                    breaks += 1;
+                    0x00
+                }
+                Err(e) => return Err(Mcp2003aError::UartError(e)),
+            };
+            self.record_rx(&[byte], false);
+            received += 1;
+            if !f(byte) {
+                break;
+            }
+        }
+
+        // Inter-frame space delay
+        delay_ns_u64(&mut self.delay, self.config.inter_frame_space.get_duration_ns());
+
+        self.last_frame_duration_ns =
+            self.read_frame_duration_ns(response_timeout_ns, self.config.inter_frame_space.get_duration_ns());
+
+        Ok(received)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<UART, GPIO, DELAY, E> Mcp2003a<UART, GPIO, DELAY>
+where
+    UART: AsyncUartRead<Error = E> + AsyncUartWrite<Error = E>,
+    GPIO: OutputPin,
+    DELAY: AsyncDelayNs,
+{
+    /// Flush the UART so none of the written bytes are still buffered. If the flush fails with an `Unsupported`
+    /// error and the `flush_fallback` allows it, wait the given nominal time of the written bytes instead.
+    async fn flush_uart_async(&mut self, fallback_ns: u64) -> Result<(), Mcp2003aError<E>> {
+        match self.uart.flush().await {
+            Ok(_) => Ok(()),
+            Err(e)
+                if self.config.flush_fallback == FlushFallback::WaitByteTime
+                    && async_error_kind::<UART>(&e) == embedded_io_async::ErrorKind::Unsupported =>
+            {
+                delay_ns_u64_async(&mut self.delay, fallback_ns).await;
+                Ok(())
+            }
+            Err(e) => Err(Mcp2003aError::AsyncUartError(e)),
+        }
+    }
+
+    /// Enable the transceiver by driving its enable pin high, then wait the configured `enable_settle`
+    /// if it was disabled. Does nothing without an enable pin.
+    /// - Note: This function is async to allow for the delay to be async.
+    pub async fn enable_async(&mut self) -> Result<(), Mcp2003aError<E>> {
+        if let Some(ref mut pin) = self.enable_pin {
+            set_pin_level(pin, true)?;
+            if !self.enabled {
+                self.enabled = true;
+                delay_ns_u64_async(&mut self.delay, self.config.enable_settle.get_duration_ns()).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Disable the transceiver by driving its enable pin low, e.g. to save energy between bursts of frames.
+    /// Does nothing without an enable pin.
+    /// - Note: This function is async for symmetry with `enable_async`, the pin itself is driven synchronously.
+    pub async fn disable_async(&mut self) -> Result<(), Mcp2003aError<E>> {
+        if let Some(ref mut pin) = self.enable_pin {
+            set_pin_level(pin, false)?;
+            self.enabled = false;
+        }
+        Ok(())
+    }
+
+    /// Restore a safe state after cancelling an async method, e.g. by dropping its future in a `select!`:
+    /// drive the break and wakeup pins low, leaving the bus recessive, and switch the UART back to the bus speed
+    /// if a `BaudSwitcher` is registered, in case a `UartBreak` was cut short. The TX line is released if
+    /// a `TxBreakControl` is registered, in case a `FramingError` break was cut short.
+    ///
+    /// - Note: The pins are already driven low when the future is dropped, this also reports their errors.
+    /// - Note: Bytes of a cut short frame may remain in the UART receive buffer, use `clear_rx_async` to drop them.
+    pub fn abort(&mut self) -> Result<(), Mcp2003aError<E>> {
+        set_pin_level(&mut self.break_pin, false)?;
+        if let Some(ref mut pin) = self.wakeup_pin {
+            set_pin_level(pin, false)?;
+        }
+        if let Some(switch_baud) = self.baud_switcher {
+            switch_baud(&mut self.uart, self.config.speed.get_baud_rate());
+        }
+        if let Some(hold_tx) = self.tx_break_control {
+            hold_tx(&mut self.uart, false);
+        }
+        Ok(())
+    }
+
+    /// Enable the transceiver before using the bus if `auto_enable` is configured and it is not enabled yet.
+    async fn auto_enable_async(&mut self) -> Result<(), Mcp2003aError<E>> {
+        if self.config.auto_enable && !self.enabled {
+            self.enable_async().await?;
+        }
+        Ok(())
+    }
+
+    /// Send a break signal on the LIN bus, pausing execution for at least 730 microseconds (13 bits).
+    /// The break of the given duration is generated with the configured `break_method`, after waiting the configured
+    /// `bus_idle_before_break`.
+    async fn send_break_async(&mut self, break_duration: LinBreakDuration) -> Result<(), Mcp2003aError<E>> {
+        self.ensure_initialized()?;
+        self.auto_enable_async().await?;
+
+        // Let the bus settle after the previous transmission
+        delay_ns_u64_async(&mut self.delay, self.config.bus_idle_before_break.get_duration_ns()).await;
+
+        // Calculate the duration of the break signal
+        let bit_period_ns = self.config.speed.get_bit_period_ns();
+        let break_duration_ns = LinBusConfig {
+            break_duration,
+            ..self.config
+        }
+        .break_dominant_time_ns();
+
+        match (self.config.break_method, self.tx_break_control) {
+            (BreakMethod::FramingError, Some(hold_tx)) => {
+                // Hold the TX line low past the stop bit, a framing error for the slaves
+                hold_tx(&mut self.uart, true);
+                self.delay.delay_ns(break_duration_ns).await;
+                hold_tx(&mut self.uart, false);
+            }
+            (BreakMethod::BreakPin | BreakMethod::FramingError, _) => {
+                // Start the break, ended even if the future is dropped during it
+                let break_pin = DominantPin::set_high(&mut self.break_pin)?;
+
+                // Break for the duration based on baud rate
+                self.delay.delay_ns(break_duration_ns).await;
+
+                // End the break
+                break_pin.release()?;
+            }
+            (BreakMethod::UartBreak, _) => {
+                // Lower the baud rate so the byte lasts the break duration
+                let break_baud_rate = self.break_baud_rate(break_duration_ns);
+                if let Some(switch_baud) = self.baud_switcher {
+                    switch_baud(&mut self.uart, break_baud_rate);
+                }
+
+                // The 0x00 byte holds the bus dominant for the start bit and 8 data bits
+                let written = match self.uart.write(&[0x00]).await {
+                    Ok(_) => {
+                        self.flush_uart_async(
+                            self.config.uart_framing.bits_per_byte() as u64 * 1_000_000_000 / break_baud_rate as u64,
+                        )
+                        .await
+                    }
+                    Err(e) => Err(Mcp2003aError::AsyncUartError(e)),
+                };
+
+                // Restore the LIN bus speed, even if the write failed
+                if let Some(switch_baud) = self.baud_switcher {
+                    switch_baud(&mut self.uart, self.config.speed.get_baud_rate());
+                }
+                written?;
+            }
+        }
+
+        // Break delimiter is 1 bit time
+        self.delay.delay_ns(bit_period_ns).await;
+
+        Ok(())
+    }
+
+    /// Send a break signal of the given duration in microseconds on the LIN bus with the break pin,
+    /// followed by the standard 1 bit break delimiter. For bring-up, testing, and slaves with unusual
+    /// break requirements outside the `LinBreakDuration` options.
+    /// - Note: Returns `BreakTooShort` if the duration is less than 11 bit periods at the configured bus speed,
+    ///   as a shorter dominant period is read by the slaves as a data byte instead of a break.
+    /// - Note: This function is async to allow for the delay to be async.
+    pub async fn send_break_us_async(&mut self, duration_us: u32) -> Result<(), Mcp2003aError<E>> {
+        let bit_period_ns = self.config.speed.get_bit_period_ns();
+        let min_duration_us = (11 * bit_period_ns as u64).div_ceil(1_000) as u32;
+        if duration_us < min_duration_us {
+            return Err(Mcp2003aError::BreakTooShort(min_duration_us));
+        }
+
+        // Break for the requested duration, ended even if the future is dropped during it
+        let break_pin = DominantPin::set_high(&mut self.break_pin)?;
+        self.delay.delay_us(duration_us).await;
+        break_pin.release()?;
+
+        // Break delimiter is 1 bit time
+        self.delay.delay_ns(bit_period_ns).await;
+
+        Ok(())
+    }
+
+    /// Send only the break, the break delimiter, and the sync byte 0x55, like `send_break_sync`.
+    /// - Note: This is a diagnostic primitive, not a complete frame: slaves see a header without an ID.
+    /// - Note: No inter-frame space is applied.
+    /// - Note: The configured `break_duration` is always sent, without the extension after `send_wakeup_async`.
+    /// - Note: This function is async to allow for the delay and serial write to be async.
+    pub async fn send_break_sync_async(&mut self) -> Result<(), Mcp2003aError<E>> {
+        self.send_break_async(self.config.break_duration).await?;
+        self.uart.write(&[SYNC_BYTE]).await?;
+        self.flush_uart_async(self.config.byte_time_ns()).await?;
+        self.record_tx(&[SYNC_BYTE]);
+        Ok(())
+    }
+
+    /// Send a wakeup signal on the LIN bus, pausing execution for at least 250 microseconds.
+    /// The wakeup is driven on the separate wakeup pin if one was given, else on the break pin.
+    /// - Note: there is an additional delay of the configured wakeup settle time after the wakeup signal
+    ///   to ensure the bus devices are ready to receive frames after activation.
+    /// - Note: Returns `WakeupTooLong` if the configured wakeup duration is longer than 5 milliseconds.
+    /// - Note: With the `FrameTraffic` wakeup methods, headers without an ID are sent after the pulse
+    ///   or instead of it, before the settle time.
+    /// - Note: The break of the next `post_wakeup_break_frames` frames is extended by `post_wakeup_break_extension`.
+    /// - Note: This function is async to allow for the delay to be async.
+    pub async fn send_wakeup_async(&mut self) -> Result<(), Mcp2003aError<E>> {
+        self.auto_enable_async().await?;
+
+        let method = self.config.wakeup_method;
+        if method.pulse() {
+            // Calculate the duration of the wakeup signal
+            let wakeup_duration_ns = self.config.wakeup_duration.get_duration_ns();
+
+            // Ensure the wakeup duration is less than 5 milliseconds
+            if wakeup_duration_ns > 5_000_000 {
+                return Err(Mcp2003aError::WakeupTooLong(wakeup_duration_ns));
+            }
+
+            // Start the wakeup signal, ended even if the future is dropped during it
+            let pin = match self.wakeup_pin {
+                Some(ref mut pin) => pin,
+                None => &mut self.break_pin,
+            };
+            let wakeup_pin = DominantPin::set_high(pin)?;
+
+            // Wakeup for the duration
+            self.delay.delay_ns(wakeup_duration_ns).await;
+
+            // End the wakeup signal
+            wakeup_pin.release()?;
+        }
+
+        // Generate bus traffic for the slaves waking on it
+        for _ in 0..method.traffic_headers() {
+            self.send_break_sync_async().await?;
+            delay_ns_u64_async(&mut self.delay, self.config.inter_frame_space.get_duration_ns()).await;
+        }
+
+        // Let the bus devices settle after the wakeup signal
+        delay_ns_u64_async(&mut self.delay, self.config.wakeup_settle.get_duration_ns()).await;
+
+        // Extend the break of the first frames after the wakeup, if configured
+        self.post_wakeup_breaks = self.config.post_wakeup_break_frames;
+
+        Ok(())
+    }
+
+    /// Send the go-to-sleep command like `send_sleep_command`, telling all slaves to enter sleep.
+    /// - Note: This function is async to allow for the delay and serial write to be async.
+    pub async fn send_sleep_command_async(&mut self) -> Result<(), Mcp2003aError<E>> {
+        let checksum = ChecksumMode::Classic.get_checksum(GO_TO_SLEEP_PID, &GO_TO_SLEEP_DATA);
+        self.send_frame_async(GO_TO_SLEEP_PID, &GO_TO_SLEEP_DATA, checksum)
+            .await?;
+        Ok(())
+    }
+
+    /// Send a frame on the LIN bus with the given ID, data, and checksum.
+    /// The data length must be between 1 and 8 bytes, else `InvalidFrame` is returned.
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: You must calculate the checksum based on your application and LIN version.
+    /// - Note: Inter-frame space is applied after sending the frame.
+    /// - Note: This function is async to allow for the delay and serial write to be async.
+    pub async fn send_frame_async(
+        &mut self,
+        id: u8,
+        data: &[u8],
+        checksum: u8,
+    ) -> Result<[u8; MAX_FRAME_LEN], Mcp2003aError<E>> {
+        let break_duration = self.frame_break_duration();
+        self.send_frame_with_break_async(id, data, checksum, break_duration)
+            .await
+    }
+
+    /// Send a frame on the LIN bus like `send_frame_async`, with the given break duration instead of the configured
+    /// one for this frame only, e.g. for slaves wanting a longer break for the first frame after a wakeup.
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: Inter-frame space is applied after sending the frame.
+    /// - Note: This function is async to allow for the delay and serial write to be async.
+    pub async fn send_frame_with_break_async(
+        &mut self,
+        id: u8,
+        data: &[u8],
+        checksum: u8,
+        break_duration: LinBreakDuration,
+    ) -> Result<[u8; MAX_FRAME_LEN], Mcp2003aError<E>> {
+        validate_frame(id, data, false)?;
+
+        // Send the break signal
+        self.send_break_async(break_duration).await?;
+
+        // Send the rest of the frame
+        let frame = self.send_frame_no_break_async(id, data, checksum).await?;
+
+        self.last_frame_duration_ns += LinBusConfig {
+            break_duration,
+            ..self.config
+        }
+        .nominal_break_time_ns();
+        Ok(frame)
+    }
+
+    /// Send a frame on the LIN bus with the given ID, data, and checksum, without sending a break first.
+    /// Only the sync, ID, data, and checksum bytes are written.
+    /// - Warning: A LIN frame must start with a break, so omitting it violates standard LIN framing.
+    ///   Only use this when the break was already sent, or for custom protocols that do not use one.
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: You must calculate the checksum based on your application and LIN version.
+    /// - Note: Inter-frame space is applied after sending the frame.
+    /// - Note: This function is async to allow for the delay and serial write to be async.
+    pub async fn send_frame_no_break_async(
+        &mut self,
+        id: u8,
+        data: &[u8],
+        checksum: u8,
+    ) -> Result<[u8; MAX_FRAME_LEN], Mcp2003aError<E>> {
+        validate_frame(id, data, false)?;
+        let data_len = data.len();
+        self.ensure_initialized()?;
+
+        // Calculate the frame
+        let mut frame = [0; MAX_FRAME_LEN];
+
+        // This is the constant value to lead every frame with per the LIN specification.
+        // In bits, this is "10101010" or "0x55" in hex.
+        frame[0] = SYNC_BYTE;
+
+        frame[1] = id;
+        frame[2..2 + data_len].copy_from_slice(data);
+        frame[2 + data_len] = checksum;
+
+        // Write the frame to the UART
+        let byte_time_ns = self.config.byte_time_ns();
+        let inter_byte_space_ns = self.config.inter_byte_space.get_duration_ns();
+        let unflushed = if inter_byte_space_ns > 0 {
+            for (i, byte) in frame[..data_len + 3].iter().enumerate() {
+                // Let the previous byte leave the UART before spacing the next one
+                if i > 0 {
+                    self.flush_uart_async(byte_time_ns).await?;
+                    delay_ns_u64_async(&mut self.delay, inter_byte_space_ns).await;
+                }
+                self.uart.write(core::slice::from_ref(byte)).await?;
+            }
+            1
+        } else {
+            self.uart.write(&frame[..data_len + 3]).await?;
+            data_len as u64 + 3
+        };
+
+        // Ensures the frame has left the UART before the inter-frame space starts
+        self.flush_uart_async(unflushed * byte_time_ns).await?;
+        self.record_tx(&frame[..data_len + 3]);
+
+        // Inter-frame space delay
+        delay_ns_u64_async(&mut self.delay, self.config.inter_frame_space.get_duration_ns()).await;
+
+        self.last_frame_duration_ns = self.send_frame_duration_ns(data_len);
+        Ok(frame)
+    }
+
+    /// Send a LIN 2.x frame on the LIN bus with the given raw ID and data, using the enhanced checksum.
+    /// The PID is calculated from the raw ID, then the enhanced checksum is calculated over the PID and data.
+    /// - Note: The raw id must be the 6-bit LIN ID (0x00 to 0x3F), not the PID, or `InvalidId` is returned.
+    /// - Note: Diagnostic frames (0x3C and 0x3D) use the classic checksum, use `send_frame_async` for those.
+    /// - Note: Inter-frame space is applied after sending the frame.
+    /// - Note: This function is async to allow for the delay and serial write to be async.
+    pub async fn send_frame_enhanced_async(
+        &mut self,
+        raw_id: u8,
+        data: &[u8],
+    ) -> Result<[u8; MAX_FRAME_LEN], Mcp2003aError<E>> {
+        if raw_id > 0x3F {
+            return Err(Mcp2003aError::InvalidFrame(FrameError::InvalidId(raw_id)));
+        }
+        let pid = get_pid(raw_id);
+        let checksum = ChecksumMode::Enhanced.get_checksum(pid, data);
+        self.send_frame_async(pid, data, checksum).await
+    }
+
+    /// Send the frame with the given raw 6-bit LIN ID and data, with the checksum mode defined for it in the
+    /// frame table, like `send_frame_auto`.
+    /// - Note: Returns `FrameNotInTable` if the ID is not in the frame table, or no table was set.
+    /// - Note: Returns `LengthMismatch` if the data length differs from the length defined in the frame table.
+    /// - Note: Inter-frame space is applied after sending the frame.
+    /// - Note: This function is async to allow for the delay and serial write to be async.
+    pub async fn send_frame_auto_async(
+        &mut self,
+        raw_id: u8,
+        data: &[u8],
+    ) -> Result<[u8; MAX_FRAME_LEN], Mcp2003aError<E>> {
+        let definition = self.frame_definition(raw_id)?;
+        if data.len() != definition.len as usize {
+            return Err(Mcp2003aError::InvalidFrame(FrameError::LengthMismatch {
+                expected: definition.len as usize,
+                actual: data.len(),
+            }));
+        }
+        let pid = get_pid(raw_id);
+        let checksum = definition.checksum_mode.get_checksum(pid, data);
+        self.send_frame_async(pid, data, checksum).await
+    }
+
+    /// Send a frame built with a `FrameBuilder` on the LIN bus.
+    /// The PID and checksum were already calculated and validated when the frame was built.
+    /// - Note: Inter-frame space is applied after sending the frame.
+    /// - Note: This function is async to allow for the delay and serial write to be async.
+    pub async fn send_wire_async(&mut self, frame: &LinFrame) -> Result<[u8; MAX_FRAME_LEN], Mcp2003aError<E>> {
+        self.send_frame_async(frame.pid(), frame.data(), frame.checksum()).await
+    }
+
+    /// Send the same frame the given number of times like `send_frame_repeated`.
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: Inter-frame space is applied after each repetition.
+    /// - Note: The first error stops the repetitions and is returned. Nothing is sent if `times` is 0.
+    /// - Note: This function is async to allow for the delay and serial write to be async.
+    pub async fn send_frame_repeated_async(
+        &mut self,
+        id: u8,
+        data: &[u8],
+        checksum: u8,
+        times: u8,
+    ) -> Result<(), Mcp2003aError<E>> {
+        let mut duration_ns = 0;
+        for _ in 0..times {
+            self.send_frame_async(id, data, checksum).await?;
+            duration_ns += self.last_frame_duration_ns;
+        }
+        self.last_frame_duration_ns = duration_ns;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl<UART, GPIO, DELAY, E> Mcp2003a<UART, GPIO, DELAY>
+where
+    UART: AsyncUartRead<Error = E> + AsyncUartWrite<Error = E> + AsyncUartReadReady,
+    GPIO: OutputPin,
+    DELAY: AsyncDelayNs,
+{
+    /// Drain the UART receive buffer, reading and discarding bytes while the UART reports some are ready.
+    /// Stale bytes from a previously aborted frame would otherwise be read as part of the next frame.
+    /// - Note: Requires the UART to implement `ReadReady`, so the drain does not wait for more bytes.
+    pub async fn clear_rx_async(&mut self) -> Result<(), Mcp2003aError<E>> {
+        self.rx_pending_async().await.map(|_| ())
+    }
+
+    /// Count the bytes pending in the UART receive buffer like `rx_pending`. Returns how many bytes were pending.
+    /// - Note: This is destructive, the pending bytes are drained and discarded like `clear_rx_async`.
+    /// - Note: Requires the UART to implement `ReadReady`, so the drain does not wait for more bytes.
+    /// - Note: This function is async to allow for the serial read to be async.
+    pub async fn rx_pending_async(&mut self) -> Result<usize, Mcp2003aError<E>> {
+        let mut discard = [0u8; 8];
+        let mut pending = 0;
+        loop {
+            match self.uart.read_ready() {
+                Ok(true) => (),
+                Ok(false) => return Ok(pending),
+                Err(e) => return Err(Mcp2003aError::AsyncUartError(e)),
+            }
+            pending += self.uart.read(&mut discard).await?;
+        }
+    }
+
+    /// Send the go-to-sleep command like `go_to_sleep`, then verify the bus stays quiet for the configured
+    /// `sleep_verify` time. Returns `BusActiveAfterSleep` if bytes were received during it.
+    /// - Note: Async UART errors are returned as `AsyncUartError`, as they do not tell line errors apart.
+    /// - Note: Requires the UART to implement `ReadReady`, so the drain does not wait for more bytes.
+    /// - Note: This function is async to allow for the delay and serial read/write to be async.
+    pub async fn go_to_sleep_async(&mut self) -> Result<(), Mcp2003aError<E>> {
+        self.send_sleep_command_async().await?;
+
+        let verify_ns = self.config.sleep_verify.get_duration_ns();
+        if verify_ns == 0 {
+            return Ok(());
+        }
+
+        // Drop the echo of the command, so only bytes sent after it are bus activity
+        self.clear_rx_async().await?;
+        delay_ns_u64_async(&mut self.delay, verify_ns).await;
+
+        match self.rx_pending_async().await? {
+            0 => Ok(()),
+            received => Err(Mcp2003aError::BusActiveAfterSleep(received)),
+        }
+    }
+
+    /// Send a wakeup signal only if the bus appears asleep, like `ensure_awake`. Returns whether the wakeup was sent.
+    /// - Note: Only bytes already received are seen, so a bus quiet between two frames appears asleep.
+    /// - Note: Stale bytes in the UART receive buffer are bus activity too, use `clear_rx_async` first if needed.
+    /// - Note: The byte that signaled the activity is consumed.
+    /// - Note: Requires the UART to implement `ReadReady`, so the probe does not wait for a byte.
+    /// - Note: This function is async to allow for the delay and serial read to be async.
+    pub async fn ensure_awake_async(&mut self) -> Result<bool, Mcp2003aError<E>> {
+        match self.uart.read_ready() {
+            Ok(true) => {
+                self.uart.read(&mut [0u8; 1]).await?;
+                return Ok(false);
+            }
+            Ok(false) => (),
+            Err(e) => return Err(Mcp2003aError::AsyncUartError(e)),
+        }
+        self.send_wakeup_async().await?;
+        Ok(true)
+    }
+
+    /// Send a frame on the LIN bus like `send_frame_async`, then count the bytes the transceiver echoed back.
+    /// Returns how many of the frame bytes (sync, ID, data, and checksum) were echoed, so a count less than
+    /// `data.len() + 3` means the transceiver or the bus dropped bytes.
+    /// - Note: This only counts the echoed bytes, it does not compare them to the sent frame.
+    /// - Note: Up to `break_byte_tolerance` 0x00 break bytes read back before the frame are not counted.
+    /// - Note: Stale bytes in the UART receive buffer are counted too, use `clear_rx_async` first if there may be some.
+    /// - Note: Requires the UART to implement `ReadReady`, so counting does not wait for more bytes.
+    /// - Note: This function is async to allow for the delay and serial read/write to be async.
+    pub async fn send_frame_confirmed_async(
+        &mut self,
+        id: u8,
+        data: &[u8],
+        checksum: u8,
+    ) -> Result<usize, Mcp2003aError<E>> {
+        self.send_frame_async(id, data, checksum).await?;
+
+        let expected = data.len() + 3;
+        let mut echoed = 0;
+        let mut break_bytes_skipped = 0;
+        let mut byte = [0u8; 1];
+        while echoed < expected {
+            match self.uart.read_ready() {
+                Ok(true) => (),
+                Ok(false) => break,
+                Err(e) => return Err(Mcp2003aError::AsyncUartError(e)),
+            }
+            match self.uart.read(&mut byte).await {
+                Ok(0) => break,
+                // The break may be read back as a 0x00 byte before the sync byte
+                Ok(_) if byte[0] == 0x00 && echoed == 0 && break_bytes_skipped < self.config.break_byte_tolerance => {
+                    break_bytes_skipped += 1;
+                }
+                Ok(_) => echoed += 1,
+                Err(e) => return Err(Mcp2003aError::AsyncUartError(e)),
+            }
+        }
+
+        Ok(echoed)
+    }
+
+    /// Send a frame on the LIN bus like `send_frame_with_backoff`, retrying after a backoff delay if the transmit
+    /// fails or another node transmitted at the same time. Up to 3 attempts are made, waiting
+    /// `backoff_units * attempt` bit periods before each retry.
+    /// - Note: Standard LIN is single-master, so contention does not happen on a compliant bus.
+    ///   This is only intended for nonstandard topologies where more than one node may transmit headers.
+    /// - Note: Lost arbitration is detected from the echo of the frame when `expect_header_echo` is enabled.
+    ///   Returns `ArbitrationLost` if the echo still differs after the last attempt.
+    /// - Note: Only retryable errors are retried, see `Mcp2003aError::is_retryable`.
+    /// - Note: There is no RNG in `no-std`, so the backoff is deterministic. Give each node a different
+    ///   `backoff_units` value (e.g. derived from its node address) so their retries do not collide again.
+    /// - Note: Requires the UART to implement `ReadReady`, so reading the echo does not wait for more bytes.
+    /// - Note: This function is async to allow for the delay and serial read/write to be async.
+    pub async fn send_frame_with_backoff_async(
+        &mut self,
+        id: u8,
+        data: &[u8],
+        checksum: u8,
+        backoff_units: u16,
+    ) -> Result<[u8; MAX_FRAME_LEN], Mcp2003aError<E>> {
+        let bit_period_ns = self.config.speed.get_bit_period_ns() as u64;
+        let mut attempt = 1;
+        loop {
+            let result = match self.send_frame_async(id, data, checksum).await {
+                // The echo differs from the frame when another node transmitted at the same time
+                Ok(frame) if self.config.expect_header_echo => {
+                    match self.echo_matches_async(id, data, checksum).await {
+                        Ok(true) => Ok(frame),
+                        Ok(false) => Err(Mcp2003aError::ArbitrationLost),
+                        Err(e) => Err(e),
+                    }
+                }
+                result => result,
+            };
+            match result {
+                Ok(frame) => return Ok(frame),
+                Err(e) if attempt >= 3 || !e.is_retryable() => return Err(e),
+                Err(_) => {
+                    // Back off for a node-specific number of bit periods before retrying
+                    delay_ns_u64_async(&mut self.delay, backoff_units as u64 * attempt * bit_period_ns).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Read back the echo of a frame just sent like `echo_matches`, returning whether its sync, ID, data,
+    /// and checksum bytes all match the frame.
+    async fn echo_matches_async(&mut self, id: u8, data: &[u8], checksum: u8) -> Result<bool, Mcp2003aError<E>> {
+        // The echo is read like a response to the header, with the data bytes provided by the master
+        let mut echo = [0u8; MAX_DATA_LEN];
+        let mut reader = ResponseReader::new(id, &mut echo[..data.len()], &self.config);
+        // The bytes after the header are the echo of the published data, not extra echoed bytes to skip
+        reader.skip_no_echo_bytes();
+        let mut byte = [0u8; 1];
+        loop {
+            // An async read waits for a byte, so only read the bytes already received
+            match self.uart.read_ready() {
+                Ok(true) => (),
+                Ok(false) => break,
+                Err(e) => return Err(Mcp2003aError::AsyncUartError(e)),
+            }
+            match self.uart.read(&mut byte).await {
+                Ok(0) => break,
+                Ok(_) => {
+                    if reader.push(byte[0]) {
+                        break;
+                    }
+                }
+                Err(e) => return Err(Mcp2003aError::AsyncUartError(e)),
+            }
+        }
+
+        match reader.finish::<E>() {
+            Ok(echoed_checksum) => Ok(echoed_checksum == checksum && &echo[..data.len()] == data),
+            Err(_) => Ok(false),
+        }
+    }
+
+    /// Send a request frame on the LIN bus, then read the response frame into the buffer with the same ID,
+    /// like `transact`. Returns the checksum of the response.
+    ///
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: With `auto_pid` configured, the id can be the raw 6-bit ID and the PID is sent for both headers.
+    /// - Note: You must calculate the request checksum and validate the response checksum.
+    /// - Note: The inter-frame space is applied once between the request and the response, and after the response.
+    /// - Note: The UART receive buffer is drained after the request, to drop its echo.
+    /// - Note: Requires the UART to implement `ReadReady`, so the drain does not wait for more bytes.
+    /// - Note: This function is async to allow for the delay and serial read/write to be async.
+    pub async fn transact_async(
+        &mut self,
+        id: u8,
+        request: &[u8],
+        checksum: u8,
+        response: &mut [u8],
+    ) -> Result<u8, Mcp2003aError<E>> {
+        let id = self.header_id(id);
+        self.send_frame_async(id, request, checksum).await?;
+        let send_duration_ns = self.last_frame_duration_ns;
+
+        // Drop the echo of the request, so its header is not mistaken for the echo of the response header
+        self.clear_rx_async().await?;
+
+        // The inter-frame space after the request already separates it from the response
+        let timeout = self.config.read_device_response_timeout;
+        let result = self
+            .read_response_async(id, response, timeout, LinInterFrameSpace::None, None)
+            .await
+            .map(|(_, checksum)| checksum);
+
+        // Inter-frame space delay
+        delay_ns_u64_async(&mut self.delay, self.config.inter_frame_space.get_duration_ns()).await;
+
+        self.last_frame_duration_ns += send_duration_ns + self.config.inter_frame_space.get_duration_ns();
+        result
+    }
+
+    /// Reset the LIN bus to a known idle state, to recover from a confused bus state after errors:
+    /// 1. Drain the UART receive buffer of any stale bytes.
+    /// 2. Hold the bus recessive (break pin low) for the configured `reset_idle` time,
+    ///    so the bus devices drop any partially received frame.
+    /// 3. If `wakeup` is true, send a wakeup signal, including its settle time.
+    /// - Note: Requires the UART to implement `ReadReady`, so the drain does not wait for more bytes.
+    /// - Note: This function is async to allow for the delay and serial read to be async.
+    pub async fn reset_bus_async(&mut self, wakeup: bool) -> Result<(), Mcp2003aError<E>> {
+        self.clear_rx_async().await?;
+
+        // Hold the bus recessive
+        set_pin_level(&mut self.break_pin, false)?;
+        delay_ns_u64_async(&mut self.delay, self.config.reset_idle.get_duration_ns()).await;
+
+        if wakeup {
+            self.send_wakeup_async().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Poll each of the given IDs in turn, reading the response to each into the same index of `out`.
+    /// Headers are sent back to back without the inter-frame space between them, which is only applied once
+    /// after the last ID, to speed up diagnostic scans compared to calling `read_frame_async` in a loop.
+    ///
+    /// Sets the data length of the response to each ID at its index in `lens`, or `None` if it did not respond,
+    /// and returns the number of IDs that responded:
+    /// - A response of 1 to 8 bytes is accepted when it ends with a valid classic or enhanced checksum.
+    /// - Unused bytes of the row in `out` are set to zero.
+    ///
+    /// - Note: The ids must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: With `auto_pid` configured, the ids can be raw 6-bit IDs and the checksums are validated over the PIDs.
+    /// - Note: Returns `OutputTooSmall` if `out` or `lens` has fewer entries than `ids`.
+    /// - Note: Serial errors stop the polling and are returned.
+    /// - Note: Requires the UART to implement `ReadReady`, so the read stops once no more bytes were received.
+    /// - Note: This function is async to allow for the delay and serial read/write to be async.
+    pub async fn poll_ids_async(
+        &mut self,
+        ids: &[u8],
+        out: &mut [[u8; MAX_DATA_LEN]],
+        lens: &mut [Option<u8>],
+    ) -> Result<usize, Mcp2003aError<E>> {
+        if out.len() < ids.len() || lens.len() < ids.len() {
+            return Err(Mcp2003aError::OutputTooSmall(ids.len()));
+        }
+
+        let timeout = self.config.read_device_response_timeout;
+        let mut responded = 0;
+        let mut duration_ns = 0;
+        let mut result = Ok(());
+        for (i, id) in ids.iter().enumerate() {
+            let read = self
+                .read_response_async(*id, &mut out[i], timeout, LinInterFrameSpace::None, None)
+                .await
+                .map(|(_, checksum)| checksum);
+            duration_ns += self.last_frame_duration_ns;
+            match polled_response_len(self.header_id(*id), &mut out[i], read) {
+                Ok(len) => {
+                    lens[i] = len;
+                    responded += len.is_some() as usize;
+                }
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+
+        // Inter-frame space delay
+        delay_ns_u64_async(&mut self.delay, self.config.inter_frame_space.get_duration_ns()).await;
+
+        self.last_frame_duration_ns = duration_ns + self.config.inter_frame_space.get_duration_ns();
+        result.map(|_| responded)
+    }
+
+    /// Read a frame from the LIN bus with the given ID into the buffer, like `read_frame_async`, then validate the
+    /// received checksum with the given checksum mode. Returns the checksum mode that matched and the checksum.
+    /// With `ChecksumMode::Auto`, the enhanced then the classic checksum are tried, for mixed-version networks.
+    /// - Note: The id must be the PID, as the enhanced checksum is calculated over it.
+    /// - Note: This function is async to allow for the delay and serial read to be async.
+    pub async fn read_frame_validated_async(
+        &mut self,
+        id: u8,
+        buffer: &mut [u8],
+        mode: ChecksumMode,
+    ) -> Result<(ChecksumMode, u8), Mcp2003aError<E>> {
+        let checksum = self.read_frame_async(id, buffer).await?;
+        match mode.validate(self.header_id(id), buffer, checksum) {
+            Some(matched) => Ok((matched, checksum)),
+            None => Err(Mcp2003aError::LinReadInvalidChecksum(checksum)),
+        }
+    }
+
+    /// Read the frame with the given raw 6-bit LIN ID, with the data length and checksum mode defined for it
+    /// in the frame table, like `read_frame_auto`. Returns the frame read.
+    /// - Note: Returns `FrameNotInTable` if the ID is not in the frame table, or no table was set.
+    /// - Note: This function is async to allow for the delay and serial read to be async.
+    pub async fn read_frame_auto_async(&mut self, raw_id: u8) -> Result<ParsedFrame, Mcp2003aError<E>> {
+        let definition = self.frame_definition(raw_id)?;
+        let pid = get_pid(raw_id);
+        let mut data = [0u8; MAX_DATA_LEN];
+        let data = &mut data[..definition.len as usize];
+        let (checksum_mode, checksum) = self
+            .read_frame_validated_async(pid, data, definition.checksum_mode)
+            .await?;
+        Ok(ParsedFrame::new(pid, data, checksum, checksum_mode))
+    }
+
+    /// Read the error frame of a transceiver or slave, then send the command clearing it, like
+    /// `read_and_clear_errors`. Returns the checksum of the error frame.
+    /// - Note: The error and clear frames are application-specific, you must supply their IDs and the clear data.
+    /// - Note: The clear frame is only sent once the errors were read, so errors are never cleared unseen.
+    /// - Note: This function is async to allow for the delay and serial read/write to be async.
+    pub async fn read_and_clear_errors_async(
+        &mut self,
+        read_id: u8,
+        errors: &mut [u8],
+        clear_id: u8,
+        clear_data: &[u8],
+        mode: ChecksumMode,
+    ) -> Result<u8, Mcp2003aError<E>> {
+        let (_, checksum) = self.read_frame_validated_async(read_id, errors, mode).await?;
+        self.send_frame_async(clear_id, clear_data, mode.get_checksum(clear_id, clear_data))
+            .await?;
+        Ok(checksum)
+    }
+
+    /// Read a frame from the LIN bus with the given ID into the buffer, like `read_frame_async`, then run the given
+    /// integrity check over the data, e.g. a CRC embedded in a proprietary payload.
+    /// - Note: The integrity check is in addition to the LIN checksum, which you still must validate.
+    /// - Note: This function is async to allow for the delay and serial read to be async.
+    pub async fn read_frame_checked_async<C: IntegrityCheck>(
+        &mut self,
+        id: u8,
+        buffer: &mut [u8],
+        check: &C,
+    ) -> Result<u8, Mcp2003aError<E>> {
+        let checksum = self.read_frame_async(id, buffer).await?;
+        if !check.check(buffer) {
+            return Err(Mcp2003aError::IntegrityCheckFailed(checksum));
+        }
+        Ok(checksum)
+    }
+
+    /// Read a frame from the LIN bus with the given ID into the buffer.
+    /// Fills the buffer and returns the checksum is received after the data.
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: Inter-frame space is applied after reading the frame.
+    /// - Note: Assumes your buffer is the size of the data you expect to receive.
+    /// - Note: You must decide how to validate the checksum based on your application and LIN version.
+    /// - Note: The buffer after the received data bytes is zeroed, so reusing a buffer never leaks a previous frame.
+    /// - Note: With `auto_pid` configured, the id can be the raw 6-bit ID and the PID is calculated from it.
+    /// - Note: Requires the UART to implement `ReadReady`, so the read stops once no more bytes were received.
+    /// - Note: This function is async to allow for the delay and serial read to be async.
+    pub async fn read_frame_async(&mut self, id: u8, buffer: &mut [u8]) -> Result<u8, Mcp2003aError<E>> {
+        self.read_frame_timeout_async(id, buffer, self.config.read_device_response_timeout)
+            .await
+    }
+
+    /// Read a frame from the LIN bus with the given ID like `read_frame_uninit`, into a buffer that does not need
+    /// to be initialized. Returns the number of data bytes written from the start of the buffer and the checksum.
+    /// - Note: The response is expected to be the length of the buffer, up to 8 data bytes.
+    /// - Note: Nothing is written to the buffer if an error is returned.
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: Inter-frame space is applied after reading the frame.
+    /// - Note: This function is async to allow for the delay and serial read to be async.
+    pub async fn read_frame_uninit_async(
+        &mut self,
+        id: u8,
+        buffer: &mut [MaybeUninit<u8>],
+    ) -> Result<(usize, u8), Mcp2003aError<E>> {
+        let len = buffer.len().min(MAX_DATA_LEN);
+        let mut data = [0u8; MAX_DATA_LEN];
+        let checksum = self.read_frame_async(id, &mut data[..len]).await?;
+        write_uninit(buffer, &data[..len]);
+        Ok((len, checksum))
+    }
+
+    /// Read a frame from the LIN bus with the given ID into the buffer like `read_frame_min_len`, accepting
+    /// a response of at least `min_data_len` data bytes followed by a valid checksum.
+    /// Returns the number of data bytes received and the checksum.
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: Inter-frame space is applied after reading the frame.
+    /// - Note: This function is async to allow for the delay and serial read to be async.
+    pub async fn read_frame_min_len_async(
+        &mut self,
+        id: u8,
+        buffer: &mut [u8],
+        min_data_len: usize,
+    ) -> Result<(usize, u8), Mcp2003aError<E>> {
+        let result = self.read_frame_async(id, buffer).await;
+        short_response(self.header_id(id), buffer, min_data_len, result)
+    }
+
+    /// Read a frame from the LIN bus with the given ID into the buffer like `read_frame_async`, waiting the given
+    /// response timeout instead of the configured one, e.g. for a slave known to be slower than the others.
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: Inter-frame space is applied after reading the frame.
+    /// - Note: Assumes your buffer is the size of the data you expect to receive.
+    /// - Note: You must decide how to validate the checksum based on your application and LIN version.
+    /// - Note: Requires the UART to implement `ReadReady`, so the read stops once no more bytes were received.
+    /// - Note: This function is async to allow for the delay and serial read to be async.
+    pub async fn read_frame_timeout_async(
+        &mut self,
+        id: u8,
+        buffer: &mut [u8],
+        timeout: LinReadDeviceResponseTimeout,
+    ) -> Result<u8, Mcp2003aError<E>> {
+        self.read_response_async(id, buffer, timeout, self.config.inter_frame_space, None)
+            .await
+            .map(|(_, checksum)| checksum)
+    }
+
+    /// Read a frame from the LIN bus with the given ID into the buffer like `read_frame_until`, reading data bytes
+    /// until the given predicate returns true for the data received so far, then the checksum.
+    /// Returns the number of data bytes received and the checksum.
+    /// - Note: At most the length of the buffer is read, a predicate that never returns true reads a full buffer.
+    /// - Note: The id must be ready to send (i.e., send in the PID if needed for your LIN version).
+    /// - Note: Inter-frame space is applied after reading the frame.
+    /// - Note: Requires the UART to implement `ReadReady`, so the read stops once no more bytes were received.
+    /// - Note: This function is async to allow for the delay and serial read to be async.
+    pub async fn read_frame_until_async(
+        &mut self,
+        id: u8,
+        buffer: &mut [u8],
+        done: impl Fn(&[u8]) -> bool,
+    ) -> Result<(usize, u8), Mcp2003aError<E>> {
+        let timeout = self.config.read_device_response_timeout;
+        self.read_response_async(id, buffer, timeout, self.config.inter_frame_space, Some(&done))
+            .await
+    }
+
+    /// Send the header and read the response into the buffer like `read_response`.
+    /// - Note: This function is async to allow for the delay and serial read to be async.
+    async fn read_response_async(
+        &mut self,
+        id: u8,
+        buffer: &mut [u8],
+        timeout: LinReadDeviceResponseTimeout,
+        inter_frame_space: LinInterFrameSpace,
+        done: Option<FrameComplete<'_>>,
+    ) -> Result<(usize, u8), Mcp2003aError<E>> {
+        let response_timeout_ns = self.response_timeout_ns(timeout, buffer.len());
+        let inter_frame_space_ns = inter_frame_space.get_duration_ns();
+        let id = self.header_id(id);
+
+        // Inter-frame space delay
+        delay_ns_u64_async(&mut self.delay, inter_frame_space_ns).await;
+
+        // Send the break signal to notify the device of the start of a frame
+        let break_duration = self.frame_break_duration();
+        self.send_break_async(break_duration).await?;
+
+        // Write the header to UART
+        let header = [SYNC_BYTE, id];
+        self.uart.write(&header).await?;
+        self.record_tx(&header);
+        self.record_rx(&[], true);
+
+        // Delay to ensure the header has time to be received and responded to by the device
+        delay_ns_u64_async(&mut self.delay, response_timeout_ns).await;
+
+        // Read the response from the device
+        // NOTE: The mcp2003a will replay the header back to you when you read.
+        let mut reader = ResponseReader::new(id, buffer, &self.config);
+        if let Some(done) = done {
+            reader = reader.until(done);
+        }
+        let mut byte = [0u8; 1];
+        let mut checksum_wait_ns = self.config.checksum_wait.get_duration_ns();
+        loop {
+            // An async read waits for a byte, so only read the bytes already received
+            match self.uart.read_ready() {
+                Ok(true) => (),
+                Ok(false) if checksum_wait_ns > 0 && reader.awaiting_checksum() => {
+                    // Give a late checksum byte one more chance to arrive
+                    delay_ns_u64_async(&mut self.delay, checksum_wait_ns).await;
+                    checksum_wait_ns = 0;
+                    continue;
+                }
+                // The UART has no more bytes
+                Ok(false) => break,
+                Err(e) => return Err(Mcp2003aError::AsyncUartError(e)),
+            }
+            match self.uart.read(&mut byte).await {
+                // End of the stream
+                Ok(0) => break,
+                Ok(_) => {
+                    self.record_rx(&byte, false);
+                    if reader.push(byte[0]) {
+                        // We've read the whole frame
+                        break;
+                    }
+                }
+                Err(e) => return Err(Mcp2003aError::AsyncUartError(e)),
+            }
+        }
+
+        // Inter-frame space delay
+        delay_ns_u64_async(&mut self.delay, inter_frame_space_ns).await;
+
+        self.last_frame_duration_ns = self.read_frame_duration_ns(response_timeout_ns, inter_frame_space_ns);
+
+        let len = reader.data_bytes_received;
+        reader.finish().map(|checksum| (len, checksum))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    extern crate std;
+    use std::vec::Vec;
+
+    use super::*;
+    use core::convert::Infallible;
+
+    /// Mock UART error.
+    #[derive(Debug, PartialEq)]
+    pub(crate) enum MockUartError {
+        Other,
+        FrameFormat,
+        Unsupported,
+    }
+
+    impl embedded_hal_nb::serial::Error for MockUartError {
+        fn kind(&self) -> embedded_hal_nb::serial::ErrorKind {
+            match self {
+                MockUartError::Other | MockUartError::Unsupported => embedded_hal_nb::serial::ErrorKind::Other,
+                MockUartError::FrameFormat => embedded_hal_nb::serial::ErrorKind::FrameFormat,
+            }
+        }
+    }
+
+    /// Mock UART that records written bytes and replays queued bytes on read.
+    #[derive(Default)]
+    pub(crate) struct MockUart {
+        pub(crate) tx: Vec<u8>,
+        pub(crate) rx: Vec<u8>,
+        /// Bytes written by the async UART, moved to `tx` when flushed.
+        #[cfg(feature = "async")]
+        tx_buffered: Vec<u8>,
+        /// Number of upcoming writes that fail with a `MockUartError`.
+        write_errors: usize,
+        /// Number of framing errors read before the queued bytes.
+        rx_framing_errors: usize,
+        /// Number of framing errors read after the next pause, as a line error between frames.
+        rx_framing_errors_after_pause: usize,
+        /// Whether flushing fails with `MockUartError::Unsupported`, the written bytes are still sent.
+        flush_unsupported: bool,
+        /// Number of bytes read before each pause where a read returns `WouldBlock` once, as between frames.
+        pub(crate) rx_would_block_after: Vec<usize>,
+    }
+
+    impl embedded_hal_nb::serial::ErrorType for MockUart {
+        type Error = MockUartError;
+    }
+
+    impl UartRead for MockUart {
+        fn read(&mut self) -> embedded_hal_nb::nb::Result<u8, MockUartError> {
+            if self.rx_framing_errors > 0 {
+                self.rx_framing_errors -= 1;
+                Err(embedded_hal_nb::nb::Error::Other(MockUartError::FrameFormat))
+            } else if self.rx_would_block_after.first() == Some(&0) {
+                self.rx_would_block_after.remove(0);
+                self.rx_framing_errors += core::mem::take(&mut self.rx_framing_errors_after_pause);
+                Err(embedded_hal_nb::nb::Error::WouldBlock)
+            } else if self.rx.is_empty() {
+                Err(embedded_hal_nb::nb::Error::WouldBlock)
+            } else {
+                if let Some(count) = self.rx_would_block_after.first_mut() {
+                    *count -= 1;
+                }
+                Ok(self.rx.remove(0))
+            }
+        }
+    }
+
+    impl UartWrite for MockUart {
+        fn write(&mut self, word: u8) -> embedded_hal_nb::nb::Result<(), MockUartError> {
+            if self.write_errors > 0 {
+                self.write_errors -= 1;
+                return Err(embedded_hal_nb::nb::Error::Other(MockUartError::Other));
+            }
+            self.tx.push(word);
+            Ok(())
+        }
+
+        fn flush(&mut self) -> embedded_hal_nb::nb::Result<(), MockUartError> {
+            if self.flush_unsupported {
+                return Err(embedded_hal_nb::nb::Error::Other(MockUartError::Unsupported));
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "async")]
+    impl embedded_io_async::Error for MockUartError {
+        fn kind(&self) -> embedded_io_async::ErrorKind {
+            match self {
+                MockUartError::Unsupported => embedded_io_async::ErrorKind::Unsupported,
+                _ => embedded_io_async::ErrorKind::Other,
+            }
+        }
+    }
+
+    #[cfg(feature = "async")]
+    impl embedded_io_async::ErrorType for MockUart {
+        type Error = MockUartError;
+    }
+
+    #[cfg(feature = "async")]
+    impl AsyncUartRead for MockUart {
+        async fn read(&mut self, buf: &mut [u8]) -> Result<usize, MockUartError> {
+            // Like a real UART, a read waits for a byte instead of returning 0 bytes
+            if self.rx.is_empty() || self.rx_would_block_after.first() == Some(&0) {
+                return core::future::pending().await;
+            }
+            let mut len = buf.len().min(self.rx.len());
+            if let Some(count) = self.rx_would_block_after.first_mut() {
+                len = len.min(*count);
+                *count -= len;
+            }
+            for (i, byte) in self.rx.drain(..len).enumerate() {
+                buf[i] = byte;
+            }
+            Ok(len)
+        }
+    }
+
+    #[cfg(feature = "async")]
+    impl AsyncUartReadReady for MockUart {
+        fn read_ready(&mut self) -> Result<bool, MockUartError> {
+            if self.rx_would_block_after.first() == Some(&0) {
+                self.rx_would_block_after.remove(0);
+                return Ok(false);
+            }
+            Ok(!self.rx.is_empty())
+        }
+    }
+
+    #[cfg(feature = "async")]
+    impl AsyncUartWrite for MockUart {
+        async fn write(&mut self, buf: &[u8]) -> Result<usize, MockUartError> {
+            if self.write_errors > 0 {
+                self.write_errors -= 1;
+                return Err(MockUartError::Other);
+            }
+            self.tx_buffered.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        async fn flush(&mut self) -> Result<(), MockUartError> {
+            self.tx.append(&mut self.tx_buffered);
+            if self.flush_unsupported {
+                return Err(MockUartError::Unsupported);
+            }
+            Ok(())
+        }
+    }
+
+    /// Mock GPIO pin that records every level it is driven to.
+    #[derive(Default)]
+    pub(crate) struct MockPin {
+        pub(crate) levels: Vec<bool>,
+    }
+
+    impl embedded_hal::digital::ErrorType for MockPin {
+        type Error = Infallible;
+    }
+
+    impl OutputPin for MockPin {
+        fn set_low(&mut self) -> Result<(), Infallible> {
+            self.levels.push(false);
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Infallible> {
+            self.levels.push(true);
+            Ok(())
+        }
+    }
+
+    /// Mock delay that records every requested delay in nanoseconds.
+    #[derive(Default)]
+    pub(crate) struct MockDelay {
+        pub(crate) delays_ns: Vec<u64>,
+        /// An async delay of this many nanoseconds never completes, to cancel the future during it.
+        #[cfg(feature = "async")]
+        pending_ns: Option<u64>,
+    }
+
+    impl DelayNs for MockDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.delays_ns.push(ns as u64);
+        }
+
+        fn delay_us(&mut self, us: u32) {
+            self.delays_ns.push(us as u64 * 1_000);
+        }
+    }
+
+    #[cfg(feature = "async")]
+    impl AsyncDelayNs for MockDelay {
+        async fn delay_ns(&mut self, ns: u32) {
+            self.delays_ns.push(ns as u64);
+            if self.pending_ns == Some(ns as u64) {
+                core::future::pending::<()>().await;
+            }
+        }
+
+        async fn delay_us(&mut self, us: u32) {
+            self.delays_ns.push(us as u64 * 1_000);
+            if self.pending_ns == Some(us as u64 * 1_000) {
+                core::future::pending::<()>().await;
+            }
+        }
+    }
+
+    #[cfg(feature = "async")]
+    /// Run a future to completion. The mocks only return `Poll::Pending` for a read with no bytes to read,
+    /// which would wait forever on a real UART, so that panics instead of hanging the test.
+    pub(crate) fn block_on<F: core::future::Future>(future: F) -> F::Output {
+        let mut future = core::pin::pin!(future);
+        let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+        match future.as_mut().poll(&mut cx) {
+            core::task::Poll::Ready(output) => output,
+            core::task::Poll::Pending => panic!("The future is waiting for a byte that never comes"),
+        }
+    }
+
+    pub(crate) fn mock_mcp2003a() -> Mcp2003a<MockUart, MockPin, MockDelay> {
+        typestate::Uninitialized::new(MockUart::default(), MockPin::default(), MockDelay::default())
+            .init(LinBusConfig::default())
+    }
+
+    #[test]
+    fn test_send_frame_enhanced() {
+        let mut mcp2003a = mock_mcp2003a();
+        let data = [0x00, 0xF0, 0x0A, 0x00, 0x00, 0x00, 0x00, 0x08];
+        let frame = mcp2003a.send_frame_enhanced(0x00, &data).unwrap();
+
+        let checksum = ChecksumMode::Enhanced.get_checksum(0x80, &data);
+        assert_eq!(checksum, 0x7C);
+        assert_eq!(
+            &mcp2003a.uart.tx[..],
+            &[0x55, 0x80, 0x00, 0xF0, 0x0A, 0x00, 0x00, 0x00, 0x00, 0x08, 0x7C]
+        );
+        assert_eq!(frame[10], checksum);
+
+        // A PID is not a raw ID, and nothing is sent
+        mcp2003a.uart.tx.clear();
+        assert!(matches!(
+            mcp2003a.send_frame_enhanced(0x80, &data),
+            Err(Mcp2003aError::InvalidFrame(FrameError::InvalidId(0x80)))
+        ));
+        #[cfg(feature = "async")]
+        {
+            assert!(matches!(
+                block_on(mcp2003a.send_frame_enhanced_async(0x80, &data)),
+                Err(Mcp2003aError::InvalidFrame(FrameError::InvalidId(0x80)))
+            ));
+        }
+        assert!(mcp2003a.uart.tx.is_empty());
+    }
+
+    #[test]
+    fn test_send_frame_with_backoff() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.write_errors = 1;
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x3D];
+        mcp2003a.send_frame_with_backoff(0xC1, &[0x01], 0x3D, 4).unwrap();
+
+        // Retry succeeded after backing off for 4 bit periods, in a single delay
+        let bit_period_ns = mcp2003a.config.speed.get_bit_period_ns() as u64;
+        assert!(mcp2003a.delay.delays_ns.contains(&(4 * bit_period_ns)));
+        assert_eq!(&mcp2003a.uart.tx[..3], &[0x55, 0xC1, 0x01]);
+
+        // Lost arbitration is detected from the echo, and the frame sent again
+        mcp2003a.uart.tx.clear();
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x02, 0x3C, 0x55, 0xC1, 0x01, 0x3D];
+        mcp2003a.send_frame_with_backoff(0xC1, &[0x01], 0x3D, 4).unwrap();
+        assert_eq!(mcp2003a.uart.tx, [0x55, 0xC1, 0x01, 0x3D, 0x55, 0xC1, 0x01, 0x3D]);
+
+        // Errors that are not retryable are returned without retrying
+        mcp2003a.uart.tx.clear();
+        mcp2003a.delay.delays_ns.clear();
+        assert!(matches!(
+            mcp2003a.send_frame_with_backoff(0xC1, &[], 0x00, 4),
+            Err(Mcp2003aError::InvalidFrame(FrameError::NoData))
+        ));
+        assert!(mcp2003a.delay.delays_ns.is_empty());
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x02, 0x3C, 0x55, 0xC1, 0x01, 0x3D];
+            block_on(mcp2003a.send_frame_with_backoff_async(0xC1, &[0x01], 0x3D, 4)).unwrap();
+            assert!(mcp2003a.delay.delays_ns.contains(&(4 * bit_period_ns)));
+            assert_eq!(mcp2003a.uart.tx, [0x55, 0xC1, 0x01, 0x3D, 0x55, 0xC1, 0x01, 0x3D]);
+        }
+    }
+
+    #[test]
+    fn test_validate_frame() {
+        assert!(validate_frame::<MockUartError>(0xC1, &[0x01], true).is_ok());
+        assert!(validate_frame::<MockUartError>(0x01, &[0x00; 8], false).is_ok());
+        assert!(matches!(
+            validate_frame::<MockUartError>(0xC1, &[], false),
+            Err(Mcp2003aError::InvalidFrame(FrameError::NoData))
+        ));
+        assert!(matches!(
+            validate_frame::<MockUartError>(0xC1, &[0x00; 9], false),
+            Err(Mcp2003aError::InvalidFrame(FrameError::DataTooLong))
+        ));
+        // Parity bits of ID 0x01 are 0b11, not 0b00
+        assert!(matches!(
+            validate_frame::<MockUartError>(0x01, &[0x01], true),
+            Err(Mcp2003aError::InvalidFrame(FrameError::InvalidParity(0x01)))
+        ));
+
+        // Nothing is sent for an invalid frame
+        let mut mcp2003a = mock_mcp2003a();
+        assert!(matches!(
+            mcp2003a.send_frame(0xC1, &[], 0x00),
+            Err(Mcp2003aError::InvalidFrame(FrameError::NoData))
+        ));
+        assert!(matches!(
+            mcp2003a.send_frame_no_break(0xC1, &[0x00; 9], 0x00),
+            Err(Mcp2003aError::InvalidFrame(FrameError::DataTooLong))
+        ));
+        #[cfg(feature = "async")]
+        {
+            assert!(matches!(
+                block_on(mcp2003a.send_frame_async(0xC1, &[], 0x00)),
+                Err(Mcp2003aError::InvalidFrame(FrameError::NoData))
+            ));
+        }
+        assert!(mcp2003a.uart.tx.is_empty());
+        assert!(mcp2003a.break_pin.levels.is_empty());
+    }
+
+    #[test]
+    fn test_send_frame_repeated() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.send_frame_repeated(0xC1, &[0x01], 0x3D, 3).unwrap();
+        assert_eq!(mcp2003a.uart.tx, [0x55, 0xC1, 0x01, 0x3D].repeat(3));
+        assert_eq!(mcp2003a.break_pin.levels, [true, false].repeat(3));
+        // Inter-frame space after each repetition
+        assert_eq!(
+            mcp2003a.delay.delays_ns.iter().filter(|ns| **ns == 1_000_000).count(),
+            3
+        );
+        let mut single = mock_mcp2003a();
+        single.send_frame(0xC1, &[0x01], 0x3D).unwrap();
+        assert_eq!(mcp2003a.last_frame_duration_ns(), 3 * single.last_frame_duration_ns());
+
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.send_frame_repeated(0xC1, &[0x01], 0x3D, 0).unwrap();
+        assert!(mcp2003a.uart.tx.is_empty());
+
+        // The first error stops the repetitions
+        mcp2003a.uart.write_errors = 1;
+        assert!(mcp2003a.send_frame_repeated(0xC1, &[0x01], 0x3D, 3).is_err());
+        assert!(mcp2003a.uart.tx.is_empty());
+
+        #[cfg(feature = "async")]
+        {
+            let mut mcp2003a = mock_mcp2003a();
+            block_on(mcp2003a.send_frame_repeated_async(0xC1, &[0x01], 0x3D, 2)).unwrap();
+            assert_eq!(mcp2003a.uart.tx, [0x55, 0xC1, 0x01, 0x3D].repeat(2));
+        }
+    }
+
+    #[test]
+    fn test_flush_fallback() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.flush_unsupported = true;
+        assert!(matches!(
+            mcp2003a.send_frame(0xC1, &[0x01], 0x3D),
+            Err(Mcp2003aError::UartWriteNotReady)
+        ));
+        #[cfg(feature = "async")]
+        {
+            assert!(matches!(
+                block_on(mcp2003a.send_frame_async(0xC1, &[0x01], 0x3D)),
+                Err(Mcp2003aError::AsyncUartError(MockUartError::Unsupported))
+            ));
+        }
+
+        // The nominal time of the 4 frame bytes is waited instead of the flush
+        mcp2003a.init(LinBusConfig {
+            flush_fallback: FlushFallback::WaitByteTime,
+            ..LinBusConfig::default()
+        });
+        let frame_bytes_ns = 4 * 10 * mcp2003a.config.speed.get_bit_period_ns() as u64;
+        mcp2003a.uart.tx.clear();
+        mcp2003a.delay.delays_ns.clear();
+        mcp2003a.send_frame(0xC1, &[0x01], 0x3D).unwrap();
+        assert_eq!(mcp2003a.uart.tx, [0x55, 0xC1, 0x01, 0x3D]);
+        assert!(mcp2003a.delay.delays_ns.contains(&frame_bytes_ns));
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.tx.clear();
+            mcp2003a.delay.delays_ns.clear();
+            block_on(mcp2003a.send_frame_async(0xC1, &[0x01], 0x3D)).unwrap();
+            assert_eq!(mcp2003a.uart.tx, [0x55, 0xC1, 0x01, 0x3D]);
+            assert!(mcp2003a.delay.delays_ns.contains(&frame_bytes_ns));
+        }
+    }
+
+    #[test]
+    fn test_send_frames() {
+        let mut mcp2003a = mock_mcp2003a();
+        let frames: [(u8, &[u8], u8); 3] = [
+            (0xC1, &[0x01], 0x3D),
+            (0x42, &[0x02, 0x03], 0x04),
+            (0x03, &[0x05], 0x06),
+        ];
+        let mut sent = [false; 3];
+        assert_eq!(mcp2003a.send_frames(&frames, &mut sent).unwrap(), 3);
+        assert_eq!(sent, [true; 3]);
+        assert_eq!(
+            mcp2003a.uart.tx,
+            [0x55, 0xC1, 0x01, 0x3D, 0x55, 0x42, 0x02, 0x03, 0x04, 0x55, 0x03, 0x05, 0x06]
+        );
+        assert_eq!(mcp2003a.break_pin.levels, [true, false, true, false, true, false]);
+        let mut single = mock_mcp2003a();
+        single.send_frame(0xC1, &[0x01], 0x3D).unwrap();
+        let one_byte_ns = single.last_frame_duration_ns();
+        single.send_frame(0x42, &[0x02, 0x03], 0x04).unwrap();
+        assert_eq!(
+            mcp2003a.last_frame_duration_ns(),
+            2 * one_byte_ns + single.last_frame_duration_ns()
+        );
+
+        // A failed frame does not stop the batch
+        mcp2003a.uart.tx.clear();
+        mcp2003a.uart.write_errors = 1;
+        assert_eq!(mcp2003a.send_frames(&frames, &mut sent).unwrap(), 2);
+        assert_eq!(sent, [false, true, true]);
+        assert_eq!(
+            &mcp2003a.uart.tx[..],
+            &[0x55, 0x42, 0x02, 0x03, 0x04, 0x55, 0x03, 0x05, 0x06]
+        );
+    }
+
+    #[test]
+    fn test_send_frame_with_backoff_gives_up() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.write_errors = 3;
+        let result = mcp2003a.send_frame_with_backoff(0xC1, &[0x01], 0x3D, 4);
+        assert!(matches!(result, Err(Mcp2003aError::UartError(_))));
+
+        // The echo differs on every attempt
+        mcp2003a.uart.rx = [0x55, 0xC1, 0x02, 0x3C].repeat(3);
+        let result = mcp2003a.send_frame_with_backoff(0xC1, &[0x01], 0x3D, 4);
+        assert!(matches!(result, Err(Mcp2003aError::ArbitrationLost)));
+        assert!(mcp2003a.uart.rx.is_empty());
+    }
+
+    #[test]
+    fn test_read_frame_partial_response() {
+        let mut mcp2003a = mock_mcp2003a();
+        let checksum = ChecksumMode::Enhanced.get_checksum(0xC1, &[0x01, 0x02]);
+        mcp2003a.uart.rx.extend_from_slice(&[0x55, 0xC1, 0x01, 0x02, checksum]);
+
+        let mut buffer = [0u8; 8];
+        match mcp2003a.read_frame(0xC1, &mut buffer) {
+            Err(Mcp2003aError::LinReadOnlyPartialResponse { received, saw_checksum }) => {
+                assert_eq!(received, 3);
+                assert!(saw_checksum);
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        assert_eq!(&buffer[..3], &[0x01, 0x02, checksum]);
+    }
+
+    #[test]
+    fn test_read_frame_checksum_wait() {
+        let mut mcp2003a = mock_mcp2003a();
+        let checksum = ChecksumMode::Enhanced.get_checksum(0xC1, &[0x01, 0x02]);
+        // The checksum arrives after a pause
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, checksum];
+        mcp2003a.uart.rx_would_block_after = std::vec![4];
+        let mut buffer = [0u8; 2];
+        assert!(matches!(
+            mcp2003a.read_frame(0xC1, &mut buffer),
+            Err(Mcp2003aError::LinReadNoChecksumReceived)
+        ));
+
+        mcp2003a.init(LinBusConfig {
+            checksum_wait: LinDelay::DelayMicroseconds(500),
+            ..LinBusConfig::default()
+        });
+        mcp2003a.clear_rx().unwrap();
+        mcp2003a.delay.delays_ns.clear();
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, checksum];
+        mcp2003a.uart.rx_would_block_after = std::vec![4];
+        assert_eq!(mcp2003a.read_frame(0xC1, &mut buffer).unwrap(), checksum);
+        assert_eq!(buffer, [0x01, 0x02]);
+        assert!(mcp2003a.delay.delays_ns.contains(&500_000));
+
+        // A checksum that never arrives is only waited for once
+        mcp2003a.delay.delays_ns.clear();
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02];
+        assert!(matches!(
+            mcp2003a.read_frame(0xC1, &mut buffer),
+            Err(Mcp2003aError::LinReadNoChecksumReceived)
+        ));
+        assert_eq!(mcp2003a.delay.delays_ns.iter().filter(|ns| **ns == 500_000).count(), 1);
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, checksum];
+            mcp2003a.uart.rx_would_block_after = std::vec![4];
+            assert_eq!(
+                block_on(mcp2003a.read_frame_async(0xC1, &mut buffer)).unwrap(),
+                checksum
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn test_read_frame_async_ends_without_bytes() {
+        // The mock read waits forever once the bytes run out, like a UART, so these would hang on a read
+        let mut mcp2003a = mock_mcp2003a();
+        let mut buffer = [0u8; 2];
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1];
+        assert!(matches!(
+            block_on(mcp2003a.read_frame_async(0xC1, &mut buffer)),
+            Err(Mcp2003aError::LinReadDeviceTimeoutNoResponse)
+        ));
+
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01];
+        assert!(matches!(
+            block_on(mcp2003a.read_frame_async(0xC1, &mut buffer)),
+            Err(Mcp2003aError::LinReadOnlyPartialResponse { received: 1, .. })
+        ));
+
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02];
+        assert!(matches!(
+            block_on(mcp2003a.read_frame_async(0xC1, &mut buffer)),
+            Err(Mcp2003aError::LinReadNoChecksumReceived)
+        ));
+
+        assert!(matches!(
+            block_on(mcp2003a.read_frame_async(0xC1, &mut buffer)),
+            Err(Mcp2003aError::SyncByteNotReceivedBack)
+        ));
+    }
+
+    #[test]
+    fn test_read_frame_partial_response_without_checksum() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx.extend_from_slice(&[0x55, 0xC1, 0x01, 0x02]);
+
+        let mut buffer = [0u8; 8];
+        let result = mcp2003a.read_frame(0xC1, &mut buffer);
+        assert!(matches!(
+            result,
+            Err(Mcp2003aError::LinReadOnlyPartialResponse {
+                received: 2,
+                saw_checksum: false
+            })
+        ));
+    }
+
+    #[test]
+    fn test_scan_bus() {
+        let mut mcp2003a = mock_mcp2003a();
+        let checksum = ChecksumMode::Enhanced.get_checksum(get_pid(0x01), &[0x01, 0x02]);
+        mcp2003a.uart.rx = std::vec![
+            0x55,
+            get_pid(0x00), // No response
+            0x55,
+            get_pid(0x01),
+            0x01,
+            0x02,
+            checksum,
+        ];
+        mcp2003a.uart.rx_would_block_after = std::vec![2];
+
+        let mut results = [true; 3];
+        assert_eq!(mcp2003a.scan_bus(0x00..=0x02, &mut results).unwrap(), 1);
+        assert_eq!(results, [false, true, false]);
+        assert_eq!(
+            &mcp2003a.uart.tx,
+            &[0x55, get_pid(0x00), 0x55, get_pid(0x01), 0x55, get_pid(0x02)]
+        );
+        // The inter-frame space before and after each probe
+        assert_eq!(
+            mcp2003a.delay.delays_ns.iter().filter(|ns| **ns == 1_000_000).count(),
+            6
+        );
+    }
+
+    #[test]
+    fn test_run_schedule() {
+        use schedule::*;
+
+        let mut mcp2003a = mock_mcp2003a();
+        let checksum = ChecksumMode::Enhanced.get_checksum(0xC1, &[0x01, 0x02]);
+        mcp2003a.uart.rx.extend_from_slice(&[0x55, 0xC1, 0x01, 0x02, checksum]);
+
+        let slots = [
+            ScheduleSlot {
+                frame: ScheduleFrame::Publish {
+                    id: 0x80,
+                    data: &[0x01],
+                    checksum: 0x7D,
+                },
+                spacing: LinInterFrameSpace::DelayMicroseconds(500),
+            },
+            ScheduleSlot {
+                frame: ScheduleFrame::Subscribe { id: 0xC1, len: 2 },
+                spacing: LinInterFrameSpace::DelayMicroseconds(700),
+            },
+        ];
+        let mut results = Vec::new();
+        mcp2003a.run_schedule(&Schedule::new(&slots), |_, result| {
+            let (data, checksum) = result.unwrap();
+            results.push((data.to_vec(), checksum));
+        });
+
+        assert_eq!(results[0], (std::vec![0x01], 0x7D));
+        assert_eq!(results[1], (std::vec![0x01, 0x02], checksum));
+        assert_eq!(mcp2003a.delay.delays_ns.iter().filter(|ns| **ns == 500_000).count(), 1);
+        assert_eq!(mcp2003a.delay.delays_ns.last(), Some(&700_000));
+    }
+
+    #[test]
+    fn test_run_schedule_invalid_len() {
+        use schedule::*;
+
+        let mut mcp2003a = mock_mcp2003a();
+        let slots = [
+            ScheduleSlot {
+                frame: ScheduleFrame::Subscribe { id: 0xC1, len: 0 },
+                spacing: LinInterFrameSpace::None,
+            },
+            ScheduleSlot {
+                frame: ScheduleFrame::Subscribe { id: 0xC1, len: 9 },
+                spacing: LinInterFrameSpace::None,
+            },
+        ];
+        let mut errors = Vec::new();
+        mcp2003a.run_schedule(&Schedule::new(&slots), |_, result| match result {
+            Err(Mcp2003aError::InvalidFrame(e)) => errors.push(e),
+            _ => panic!("Expected InvalidFrame"),
+        });
+        assert_eq!(errors, [FrameError::NoData, FrameError::DataTooLong]);
+        assert!(mcp2003a.uart.tx.is_empty());
+
+        #[cfg(feature = "async")]
+        {
+            let mut errors = 0;
+            block_on(mcp2003a.run_schedule_async(&Schedule::new(&slots), |_, result| {
+                assert!(matches!(result, Err(Mcp2003aError::InvalidFrame(_))));
+                errors += 1;
+            }));
+            assert_eq!(errors, 2);
+        }
+    }
+
+    #[test]
+    fn test_run_schedule_jitter() {
+        use schedule::*;
+
+        let mut mcp2003a = mock_mcp2003a();
+        let slots = [ScheduleSlot {
+            frame: ScheduleFrame::Publish {
+                id: 0x80,
+                data: &[0x01],
+                checksum: 0x7D,
+            },
+            spacing: LinInterFrameSpace::None,
+        }];
+        assert_eq!(mcp2003a.jitter_ns(), 0);
+
+        // A published frame takes its nominal time, the inter-frame space is part of the slot
+        mcp2003a.run_schedule(&Schedule::new(&slots), |_, _| {});
+        assert_eq!(mcp2003a.jitter_ns(), 0);
+
+        // Inter-byte spaces run the frame over its nominal time, within its budget
+        mcp2003a.init(LinBusConfig {
+            inter_byte_space: LinDelay::DelayMicroseconds(100),
+            ..LinBusConfig::default()
+        });
+        mcp2003a.run_schedule(&Schedule::new(&slots), |_, _| {});
+        assert_eq!(mcp2003a.jitter_ns(), 300_000);
+        assert_eq!(mcp2003a.slot_overruns(), 0);
+
+        // A response timeout far longer than the response overruns the budget of the slot
+        mcp2003a.init(LinBusConfig {
+            read_device_response_timeout: LinReadDeviceResponseTimeout::DelayMilliseconds(5),
+            ..LinBusConfig::default()
+        });
+        mcp2003a.reset_jitter();
+        let checksum = ChecksumMode::Enhanced.get_checksum(0xC1, &[0x01]);
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, checksum];
+        let slots = [ScheduleSlot {
+            frame: ScheduleFrame::Subscribe { id: 0xC1, len: 1 },
+            spacing: LinInterFrameSpace::None,
+        }];
+        mcp2003a.run_schedule(&Schedule::new(&slots), |_, result| assert!(result.is_ok()));
+        // 5 ms timeout less the nominal response of 2 bytes at 19200 baud
+        assert_eq!(mcp2003a.jitter_ns(), 5_000_000 - 2 * 520_830);
+        assert_eq!(mcp2003a.slot_overruns(), 1);
+
+        // Failed slots are not accumulated
+        mcp2003a.run_schedule(&Schedule::new(&slots), |_, result| assert!(result.is_err()));
+        assert_eq!(mcp2003a.slot_overruns(), 1);
+
+        mcp2003a.reset_jitter();
+        assert_eq!(mcp2003a.jitter_ns(), 0);
+        assert_eq!(mcp2003a.slot_overruns(), 0);
+    }
+
+    #[test]
+    fn test_schedule_cycle_time() {
+        use schedule::*;
+
+        let slots = [
+            ScheduleSlot {
+                frame: ScheduleFrame::Publish {
+                    id: 0x80,
+                    data: &[0x01],
+                    checksum: 0x7D,
+                },
+                spacing: LinInterFrameSpace::DelayMicroseconds(500),
+            },
+            ScheduleSlot {
+                frame: ScheduleFrame::Subscribe { id: 0xC1, len: 2 },
+                spacing: LinInterFrameSpace::DelayMicroseconds(700),
+            },
+        ];
+        let schedule = Schedule::new(&slots);
+        let config = LinBusConfig::default();
+
+        // Bus idle, frame of 1 data byte at 19200 baud, inter-frame space, and spacing
+        let publish_ns = 100_000 + 2_812_482 + 1_000_000 + 500_000;
+        // Bus idle, header, 2 ms response timeout, both inter-frame spaces, and spacing
+        let subscribe_ns = 100_000 + 1_770_822 + 2_000_000 + 2_000_000 + 700_000;
+        assert_eq!(schedule.cycle_time_ns(&config), publish_ns + subscribe_ns);
+        // Does not fit a 10 ms cycle
+        assert!(schedule.cycle_time_ns(&config) > 10_000_000);
+
+        // A response timeout shorter than the response counts the nominal response time
+        let config = LinBusConfig {
+            read_device_response_timeout: LinReadDeviceResponseTimeout::None,
+            ..config
+        };
+        let subscribe_ns = 100_000 + 1_770_822 + 3 * 520_830 + 2_000_000 + 700_000;
+        assert_eq!(schedule.cycle_time_ns(&config), publish_ns + subscribe_ns);
+        assert_eq!(Schedule::new(&[]).cycle_time_ns(&config), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn test_run_schedule_async() {
+        use schedule::*;
+
+        let mut mcp2003a = mock_mcp2003a();
+        let slots = [ScheduleSlot {
+            frame: ScheduleFrame::Publish {
+                id: 0x80,
+                data: &[0x01],
+                checksum: 0x7D,
+            },
+            spacing: LinInterFrameSpace::DelayMicroseconds(500),
+        }];
+        let mut published = 0;
+        block_on(mcp2003a.run_schedule_async(&Schedule::new(&slots), |_, result| {
+            assert!(result.is_ok());
+            published += 1;
+        }));
+
+        assert_eq!(published, 1);
+        assert_eq!(&mcp2003a.uart.tx[..3], &[0x55, 0x80, 0x01]);
+        assert_eq!(mcp2003a.delay.delays_ns.last(), Some(&500_000));
+    }
+
+    #[test]
+    fn test_read_frame_possible_baud_mismatch() {
+        let mut mcp2003a = mock_mcp2003a();
+        // Sync byte shifted by a mismatched baud rate
+        mcp2003a.uart.rx.extend_from_slice(&[0xAA, 0xFE, 0x01]);
+
+        let mut buffer = [0u8; 2];
+        let result = mcp2003a.read_frame(0xC1, &mut buffer);
+        assert!(matches!(result, Err(Mcp2003aError::PossibleBaudMismatch(0xAA))));
+    }
+
+    #[test]
+    fn test_read_frame_sync_not_received() {
+        let mut mcp2003a = mock_mcp2003a();
+        let mut buffer = [0u8; 2];
+        let result = mcp2003a.read_frame(0xC1, &mut buffer);
+        assert!(matches!(result, Err(Mcp2003aError::SyncByteNotReceivedBack)));
+    }
+
+    #[test]
+    fn test_clear_rx() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx.extend_from_slice(&[0x55, 0x00, 0x01]);
+        mcp2003a.clear_rx().unwrap();
+        assert!(mcp2003a.uart.rx.is_empty());
+    }
+
+    #[test]
+    fn test_go_to_sleep() {
+        let mut mcp2003a = mock_mcp2003a();
+        // Without verification, only the command is sent
+        mcp2003a.go_to_sleep().unwrap();
+        let checksum = ChecksumMode::Classic.get_checksum(0x3C, &GO_TO_SLEEP_DATA);
+        assert_eq!(mcp2003a.uart.tx[..3], [0x55, 0x3C, 0x00]);
+        assert_eq!(mcp2003a.uart.tx[10], checksum);
+
+        mcp2003a.init(LinBusConfig {
+            sleep_verify: LinDelay::DelayMilliseconds(50),
+            ..LinBusConfig::default()
+        });
+        // The echo of the command is drained, then the bus stays quiet
+        mcp2003a.uart.rx = std::vec![0x55, 0x3C, 0x00];
+        mcp2003a.delay.delays_ns.clear();
+        mcp2003a.go_to_sleep().unwrap();
+        assert_eq!(mcp2003a.delay.delays_ns.last(), Some(&50_000_000));
+
+        // A node keeps sending after the echo
+        mcp2003a.uart.rx = std::vec![0x55, 0x3C, 0x00, 0x55, 0x80];
+        mcp2003a.uart.rx_would_block_after = std::vec![3];
+        assert!(matches!(
+            mcp2003a.go_to_sleep(),
+            Err(Mcp2003aError::BusActiveAfterSleep(2))
+        ));
+
+        // The break of the command read back as a framing error is drained with the echo
+        mcp2003a.uart.rx = std::vec![0x55, 0x3C, 0x00];
+        mcp2003a.uart.rx_framing_errors = 1;
+        mcp2003a.go_to_sleep().unwrap();
+
+        // A line error after the echo is bus activity, like a byte
+        mcp2003a.uart.rx = std::vec![0x55, 0x3C, 0x00];
+        mcp2003a.uart.rx_would_block_after = std::vec![3, 0];
+        mcp2003a.uart.rx_framing_errors_after_pause = 1;
+        assert!(matches!(
+            mcp2003a.go_to_sleep(),
+            Err(Mcp2003aError::BusActiveAfterSleep(1))
+        ));
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.rx = std::vec![0x55, 0x3C, 0x00, 0x55, 0x80];
+            mcp2003a.uart.rx_would_block_after = std::vec![3];
+            assert!(matches!(
+                block_on(mcp2003a.go_to_sleep_async()),
+                Err(Mcp2003aError::BusActiveAfterSleep(2))
+            ));
+            mcp2003a.uart.rx = std::vec![0x55, 0x3C, 0x00];
+            block_on(mcp2003a.go_to_sleep_async()).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_rx_pending() {
+        let mut mcp2003a = mock_mcp2003a();
+        assert_eq!(mcp2003a.rx_pending().unwrap(), 0);
+        mcp2003a.uart.rx.extend_from_slice(&[0x55, 0x00, 0x01]);
+        assert_eq!(mcp2003a.rx_pending().unwrap(), 3);
+        assert!(mcp2003a.uart.rx.is_empty());
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.rx.extend_from_slice(&[0x55; 10]);
+            assert_eq!(block_on(mcp2003a.rx_pending_async()).unwrap(), 10);
+            assert!(mcp2003a.uart.rx.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_read_frame_clear_rx_before_read() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.init(LinBusConfig {
+            clear_rx_before_read: true,
+            ..LinBusConfig::default()
+        });
+        mcp2003a.uart.rx.extend_from_slice(&[0x55, 0xC1, 0x01, 0x02]);
+
+        let mut buffer = [0u8; 2];
+        let result = mcp2003a.read_frame(0xC1, &mut buffer);
+        assert!(matches!(result, Err(Mcp2003aError::SyncByteNotReceivedBack)));
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn test_send_frame_async_flushes() {
+        let mut mcp2003a = mock_mcp2003a();
+        block_on(mcp2003a.send_frame_async(0x80, &[0x01], 0x7D)).unwrap();
+
+        // All bytes were flushed out of the UART before the inter-frame space
+        assert!(mcp2003a.uart.tx_buffered.is_empty());
+        assert_eq!(&mcp2003a.uart.tx[..3], &[0x55, 0x80, 0x01]);
+        assert_eq!(
+            mcp2003a.delay.delays_ns.last(),
+            Some(&mcp2003a.config.inter_frame_space.get_duration_ns())
+        );
+    }
+
+    #[test]
+    fn test_set_break_method() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.send_frame(0x80, &[0x01], 0x7D).unwrap();
+        assert_eq!(mcp2003a.break_pin.levels, [true, false]);
+        assert_eq!(mcp2003a.uart.tx[0], 0x55);
+
+        mcp2003a.set_break_method(BreakMethod::UartBreak);
+        mcp2003a.uart.tx.clear();
+        mcp2003a.send_frame(0x80, &[0x01], 0x7D).unwrap();
+        assert_eq!(mcp2003a.break_pin.levels, [true, false]);
+        assert_eq!(&mcp2003a.uart.tx[..3], &[0x00, 0x55, 0x80]);
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.tx.clear();
+            block_on(mcp2003a.send_frame_async(0x80, &[0x01], 0x7D)).unwrap();
+            assert_eq!(mcp2003a.break_pin.levels, [true, false]);
+            assert_eq!(&mcp2003a.uart.tx[..3], &[0x00, 0x55, 0x80]);
+        }
+    }
+
+    #[test]
+    fn test_send_break_includes_delimiter() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.send_frame(0x80, &[0x01], 0x7D).unwrap();
+        assert_eq!(&mcp2003a.delay.delays_ns[..3], &[100_000, 677_079, 52_083]);
+
+        mcp2003a.init(LinBusConfig {
+            break_includes_delimiter: true,
+            ..LinBusConfig::default()
+        });
+        mcp2003a.delay.delays_ns.clear();
+        mcp2003a.send_frame(0x80, &[0x01], 0x7D).unwrap();
+        assert_eq!(&mcp2003a.delay.delays_ns[..3], &[100_000, 677_079 - 52_083, 52_083]);
+    }
+
+    #[test]
+    fn test_bus_idle_before_break() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.init(LinBusConfig {
+            bus_idle_before_break: LinDelay::DelayMicroseconds(200),
+            ..LinBusConfig::default()
+        });
+        let mut buffer = [0u8; 1];
+        let _ = mcp2003a.read_frame(0xC1, &mut buffer);
+        // Inter-frame space of the previous frame, then the idle time right before the break
+        assert_eq!(&mcp2003a.delay.delays_ns[..3], &[1_000_000, 200_000, 677_079]);
+
+        mcp2003a.init(LinBusConfig {
+            bus_idle_before_break: LinDelay::None,
+            ..LinBusConfig::default()
+        });
+        mcp2003a.delay.delays_ns.clear();
+        mcp2003a.send_frame(0x80, &[0x01], 0x7D).unwrap();
+        // No delay at all without an idle time
+        assert_eq!(&mcp2003a.delay.delays_ns[..2], &[677_079, 52_083]);
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.config.bus_idle_before_break = LinDelay::DelayMilliseconds(1);
+            mcp2003a.delay.delays_ns.clear();
+            block_on(mcp2003a.send_frame_async(0x80, &[0x01], 0x7D)).unwrap();
+            assert_eq!(&mcp2003a.delay.delays_ns[..2], &[1_000_000, 677_079]);
+        }
+    }
+
+    #[test]
+    fn test_send_frame_with_break() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a
+            .send_frame_with_break(0x80, &[0x01], 0x7D, LinBreakDuration::Minimum13BitsPlus(3))
+            .unwrap();
+        // 16 bit periods at 19200 baud, then the delimiter
+        assert_eq!(&mcp2003a.delay.delays_ns[1..3], &[833_328, 52_083]);
+        let config = LinBusConfig::default();
+        assert_eq!(
+            mcp2003a.last_frame_duration_ns(),
+            config.nominal_frame_time_ns(1) + 3 * 52_083 + 1_000_000
+        );
+
+        // The configured break is used again for the next frame
+        mcp2003a.delay.delays_ns.clear();
+        mcp2003a.send_frame(0x80, &[0x01], 0x7D).unwrap();
+        assert_eq!(&mcp2003a.delay.delays_ns[1..3], &[677_079, 52_083]);
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.delay.delays_ns.clear();
+            block_on(mcp2003a.send_frame_with_break_async(0x80, &[0x01], 0x7D, LinBreakDuration::Minimum13BitsPlus(3)))
+                .unwrap();
+            assert_eq!(&mcp2003a.delay.delays_ns[1..3], &[833_328, 52_083]);
+        }
+    }
+
+    #[test]
+    fn test_send_frame_writes_only_frame_bytes() {
+        let mut mcp2003a = mock_mcp2003a();
+        let frame = mcp2003a.send_frame(0x80, &[0x01], 0x7D).unwrap();
+        assert_eq!(&mcp2003a.uart.tx[..], &[0x55, 0x80, 0x01, 0x7D]);
+        // The returned frame keeps its full length, padded with zeros
+        assert_eq!(frame, [0x55, 0x80, 0x01, 0x7D, 0, 0, 0, 0, 0, 0, 0]);
+
+        // A full frame writes all 11 bytes
+        mcp2003a.uart.tx.clear();
+        let data = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+        mcp2003a.send_frame(0x80, &data, 0x7D).unwrap();
+        assert_eq!(mcp2003a.uart.tx.len(), MAX_FRAME_LEN);
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.tx.clear();
+            block_on(mcp2003a.send_frame_async(0x80, &[0x01], 0x7D)).unwrap();
+            assert_eq!(&mcp2003a.uart.tx[..], &[0x55, 0x80, 0x01, 0x7D]);
+        }
+    }
+
+    #[test]
+    fn test_last_frame_duration() {
+        let mut mcp2003a = mock_mcp2003a();
+        assert_eq!(mcp2003a.last_frame_duration_ns(), 0);
+
+        mcp2003a.send_frame(0x80, &[0x01], 0x7D).unwrap();
+        let config = LinBusConfig::default();
+        assert_eq!(
+            mcp2003a.last_frame_duration_ns(),
+            config.nominal_frame_time_ns(1) + 1_000_000
+        );
+
+        let mut buffer = [0u8; 1];
+        let _ = mcp2003a.read_frame(0xC1, &mut buffer);
+        assert_eq!(
+            mcp2003a.last_frame_duration_ns(),
+            config.nominal_header_time_ns() + 2_000_000 + 2 * 1_000_000
+        );
+    }
+
+    #[test]
+    fn test_publish_frame() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx = std::vec![0x00, 0x55, 0x80, 0x01, 0x7D];
+        assert!(mcp2003a.publish_frame(0x80, &[0x01], 0x7D).unwrap());
+        assert_eq!(&mcp2003a.uart.tx, &[0x55, 0x80, 0x01, 0x7D]);
+
+        // Corrupted data
+        mcp2003a.uart.rx = std::vec![0x55, 0x80, 0x03, 0x7D];
+        assert!(!mcp2003a.publish_frame(0x80, &[0x01], 0x7D).unwrap());
+
+        // Missing checksum
+        mcp2003a.uart.rx = std::vec![0x55, 0x80, 0x01];
+        assert!(!mcp2003a.publish_frame(0x80, &[0x01], 0x7D).unwrap());
+    }
+
+    #[test]
+    fn test_long_delay_is_split() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.init(LinBusConfig {
+            inter_frame_space: LinInterFrameSpace::DelayMilliseconds(5_000),
+            ..LinBusConfig::default()
+        });
+        mcp2003a.send_frame(0x80, &[0x01], 0x7D).unwrap();
+        assert_eq!(
+            &mcp2003a.delay.delays_ns[mcp2003a.delay.delays_ns.len() - 2..],
+            &[u32::MAX as u64, 5_000_000_000 - u32::MAX as u64]
+        );
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.delay.delays_ns.clear();
+            block_on(mcp2003a.send_frame_async(0x80, &[0x01], 0x7D)).unwrap();
+            assert_eq!(
+                &mcp2003a.delay.delays_ns[mcp2003a.delay.delays_ns.len() - 2..],
+                &[u32::MAX as u64, 5_000_000_000 - u32::MAX as u64]
+            );
+        }
+    }
+
+    #[test]
+    fn test_sleep_until_wakeup() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx = std::vec![0x00];
+        mcp2003a.uart.rx_would_block_after = std::vec![0, 0, 0];
+        mcp2003a.sleep_until_wakeup().unwrap();
+        assert_eq!(mcp2003a.break_pin.levels, [false]);
+        assert_eq!(mcp2003a.delay.delays_ns, [1_000_000; 3]);
+        assert!(mcp2003a.uart.rx.is_empty());
+
+        let mut sleeps = 0;
+        mcp2003a.uart.rx = std::vec![0x00];
+        mcp2003a.uart.rx_would_block_after = std::vec![0, 0];
+        mcp2003a.sleep_until_wakeup_with(|| sleeps += 1).unwrap();
+        assert_eq!(sleeps, 2);
+
+        // A framing error is bus activity too
+        mcp2003a.uart.rx_framing_errors = 1;
+        mcp2003a.sleep_until_wakeup_with(|| sleeps += 1).unwrap();
+        assert_eq!(sleeps, 2);
+    }
+
+    #[test]
+    fn test_ensure_awake() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx = std::vec![0x55];
+        assert!(!mcp2003a.ensure_awake().unwrap());
+        assert!(mcp2003a.break_pin.levels.is_empty());
+
+        assert!(mcp2003a.ensure_awake().unwrap());
+        assert_eq!(mcp2003a.break_pin.levels, [true, false]);
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.rx = std::vec![0x55];
+            assert!(!block_on(mcp2003a.ensure_awake_async()).unwrap());
+            assert!(block_on(mcp2003a.ensure_awake_async()).unwrap());
+            assert_eq!(mcp2003a.break_pin.levels, [true, false, true, false]);
+        }
+    }
+
+    #[test]
+    fn test_send_frame_no_break() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.send_frame_no_break(0x80, &[0x01], 0x7D).unwrap();
+        assert!(mcp2003a.break_pin.levels.is_empty());
+        assert_eq!(&mcp2003a.uart.tx[..], &[0x55, 0x80, 0x01, 0x7D]);
+
+        let config = LinBusConfig::default();
+        assert_eq!(
+            mcp2003a.last_frame_duration_ns(),
+            config.nominal_frame_time_ns(1) - config.nominal_break_time_ns() + 1_000_000
+        );
+    }
+
+    #[test]
+    fn test_send_wakeup_settle() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.send_wakeup().unwrap();
+        assert_eq!(mcp2003a.break_pin.levels, [true, false]);
+        assert_eq!(mcp2003a.delay.delays_ns, [250_000, 100_000_000]);
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.delay.delays_ns.clear();
+            block_on(mcp2003a.send_wakeup_async()).unwrap();
+            assert_eq!(mcp2003a.delay.delays_ns, [250_000, 100_000_000]);
+        }
+    }
+
+    #[test]
+    fn test_post_wakeup_break() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.init(LinBusConfig {
+            post_wakeup_break_frames: 2,
+            post_wakeup_break_extension: 3,
+            ..LinBusConfig::default()
+        });
+        // No extension before the wakeup
+        mcp2003a.send_frame(0x80, &[0x01], 0x7D).unwrap();
+        assert_eq!(mcp2003a.delay.delays_ns[1], 677_079);
+
+        // The first 2 frames after the wakeup, sent or read, have a break of 13 + 3 bits
+        mcp2003a.send_wakeup().unwrap();
+        mcp2003a.delay.delays_ns.clear();
+        mcp2003a.send_frame(0x80, &[0x01], 0x7D).unwrap();
+        assert_eq!(mcp2003a.delay.delays_ns[1], 833_328);
+        mcp2003a.delay.delays_ns.clear();
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x3D];
+        mcp2003a.read_frame(0xC1, &mut [0u8; 1]).unwrap();
+        assert_eq!(mcp2003a.delay.delays_ns[2], 833_328);
+
+        // Then the configured break again
+        mcp2003a.delay.delays_ns.clear();
+        mcp2003a.send_frame(0x80, &[0x01], 0x7D).unwrap();
+        assert_eq!(mcp2003a.delay.delays_ns[1], 677_079);
+
+        // The calibration break is not extended and does not count as a frame, a raw read does
+        mcp2003a.send_wakeup().unwrap();
+        mcp2003a.delay.delays_ns.clear();
+        mcp2003a.send_break_sync().unwrap();
+        assert_eq!(mcp2003a.delay.delays_ns[1], 677_079);
+        mcp2003a.delay.delays_ns.clear();
+        mcp2003a.read_raw(0xC1, |_| true).unwrap();
+        assert_eq!(mcp2003a.delay.delays_ns[2], 833_328);
+        mcp2003a.delay.delays_ns.clear();
+        mcp2003a.read_raw(0xC1, |_| true).unwrap();
+        assert_eq!(mcp2003a.delay.delays_ns[2], 833_328);
+        mcp2003a.delay.delays_ns.clear();
+        mcp2003a.read_raw(0xC1, |_| true).unwrap();
+        assert_eq!(mcp2003a.delay.delays_ns[2], 677_079);
+
+        #[cfg(feature = "async")]
+        {
+            block_on(mcp2003a.send_wakeup_async()).unwrap();
+            mcp2003a.delay.delays_ns.clear();
+            block_on(mcp2003a.send_frame_async(0x80, &[0x01], 0x7D)).unwrap();
+            assert_eq!(mcp2003a.delay.delays_ns[1], 833_328);
+        }
+    }
+
+    #[test]
+    fn test_send_wakeup_frame_traffic() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.init(LinBusConfig {
+            wakeup_method: WakeupMethod::FrameTraffic(2),
+            ..LinBusConfig::default()
+        });
+        mcp2003a.send_wakeup().unwrap();
+        // Two breaks without the pulse, each followed by the sync byte and the inter-frame space
+        assert_eq!(mcp2003a.uart.tx, [0x55, 0x55]);
+        assert_eq!(mcp2003a.break_pin.levels, [true, false, true, false]);
+        assert_eq!(
+            mcp2003a.delay.delays_ns,
+            [
+                100_000,
+                677_079,
+                52_083,
+                1_000_000,
+                100_000,
+                677_079,
+                52_083,
+                1_000_000,
+                100_000_000
+            ]
+        );
+
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.init(LinBusConfig {
+            wakeup_method: WakeupMethod::PulseAndFrameTraffic(1),
+            ..LinBusConfig::default()
+        });
+        mcp2003a.send_wakeup().unwrap();
+        assert_eq!(mcp2003a.uart.tx, [0x55]);
+        assert_eq!(mcp2003a.break_pin.levels, [true, false, true, false]);
+        assert_eq!(mcp2003a.delay.delays_ns[0], 250_000);
+
+        #[cfg(feature = "async")]
+        {
+            let mut mcp2003a = mock_mcp2003a();
+            mcp2003a.init(LinBusConfig {
+                wakeup_method: WakeupMethod::FrameTraffic(1),
+                ..LinBusConfig::default()
+            });
+            block_on(mcp2003a.send_wakeup_async()).unwrap();
+            assert_eq!(mcp2003a.uart.tx, [0x55]);
+            assert_eq!(mcp2003a.break_pin.levels, [true, false]);
+        }
+    }
+
+    #[test]
+    fn test_max_transaction_time() {
+        let mcp2003a = mock_mcp2003a();
+        // 19200 baud: 6.458 ms frame, 2 ms response timeout, 2 * 1 ms inter-frame space
+        assert_eq!(mcp2003a.max_transaction_time_ns(), 6_458_292 + 2_000_000 + 2_000_000);
+    }
+
+    #[test]
+    fn test_read_frame_checked() {
+        let crc8 = Crc8::sae_j1850();
+        let data = [0x01, 0x02, crc8.calculate(&[0x01, 0x02])];
+        let checksum = ChecksumMode::Enhanced.get_checksum(0xC1, &data);
+
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx.extend_from_slice(&[0x55, 0xC1]);
+        mcp2003a.uart.rx.extend_from_slice(&data);
+        mcp2003a.uart.rx.push(checksum);
+        let mut buffer = [0u8; 3];
+        assert_eq!(mcp2003a.read_frame_checked(0xC1, &mut buffer, &crc8).unwrap(), checksum);
+
+        // Corrupted CRC
+        mcp2003a
+            .uart
+            .rx
+            .extend_from_slice(&[0x55, 0xC1, 0x01, 0x02, data[2] ^ 0xFF, checksum]);
+        let result = mcp2003a.read_frame_checked(0xC1, &mut buffer, &crc8);
+        assert!(matches!(result, Err(Mcp2003aError::IntegrityCheckFailed(c)) if c == checksum));
+    }
+
+    #[test]
+    fn test_read_frame_skips_break_byte() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx.extend_from_slice(&[0x00, 0x55, 0xC1, 0x01, 0x3D]);
+        let mut buffer = [0u8; 1];
+        assert_eq!(mcp2003a.read_frame(0xC1, &mut buffer).unwrap(), 0x3D);
+
+        mcp2003a.uart.rx_framing_errors = 1;
+        mcp2003a.uart.rx.extend_from_slice(&[0x55, 0xC1, 0x01, 0x3D]);
+        assert_eq!(mcp2003a.read_frame(0xC1, &mut buffer).unwrap(), 0x3D);
+    }
+
+    #[test]
+    fn test_read_frame_break_byte_tolerance() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx_framing_errors = 2;
+        mcp2003a.uart.rx.extend_from_slice(&[0x55, 0xC1, 0x01, 0x3D]);
+        let mut buffer = [0u8; 1];
+        let result = mcp2003a.read_frame(0xC1, &mut buffer);
+        assert!(matches!(
+            result,
+            Err(Mcp2003aError::UartError(embedded_hal_nb::nb::Error::Other(
+                MockUartError::FrameFormat
+            )))
+        ));
+
+        mcp2003a.init(LinBusConfig {
+            break_byte_tolerance: 2,
+            ..LinBusConfig::default()
+        });
+        mcp2003a.uart.rx.clear();
+        mcp2003a.uart.rx_framing_errors = 2;
+        mcp2003a.uart.rx.extend_from_slice(&[0x55, 0xC1, 0x01, 0x3D]);
+        assert_eq!(mcp2003a.read_frame(0xC1, &mut buffer).unwrap(), 0x3D);
+    }
+
+    #[test]
+    fn test_reset_bus() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx.extend_from_slice(&[0x55, 0x00]);
+        mcp2003a.reset_bus(false).unwrap();
+        assert!(mcp2003a.uart.rx.is_empty());
+        assert_eq!(mcp2003a.break_pin.levels, [false]);
+        assert_eq!(mcp2003a.delay.delays_ns, [10_000_000]);
+
+        mcp2003a.delay.delays_ns.clear();
+        mcp2003a.reset_bus(true).unwrap();
+        assert_eq!(mcp2003a.delay.delays_ns, [10_000_000, 250_000, 100_000_000]);
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn test_reset_bus_async() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx.extend_from_slice(&[0x55; 20]);
+        block_on(mcp2003a.reset_bus_async(true)).unwrap();
+        assert!(mcp2003a.uart.rx.is_empty());
+        assert_eq!(mcp2003a.break_pin.levels, [false, true, false]);
+        assert_eq!(mcp2003a.delay.delays_ns, [10_000_000, 250_000, 100_000_000]);
+    }
+
+    #[test]
+    fn test_send_break_sync() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.send_break_sync().unwrap();
+        assert_eq!(mcp2003a.break_pin.levels, [true, false]);
+        assert_eq!(mcp2003a.uart.tx, [0x55]);
+        assert_eq!(mcp2003a.delay.delays_ns, [100_000, 677_079, 52_083]);
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.tx.clear();
+            block_on(mcp2003a.send_break_sync_async()).unwrap();
+            assert_eq!(mcp2003a.uart.tx, [0x55]);
+        }
+    }
+
+    #[test]
+    fn test_send_break_us() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.send_break_us(1_000).unwrap();
+        assert_eq!(mcp2003a.break_pin.levels, [true, false]);
+        assert_eq!(mcp2003a.delay.delays_ns, [1_000_000, 52_083]);
+
+        // 11 bit periods at 19200 baud is 573 microseconds
+        let result = mcp2003a.send_break_us(500);
+        assert!(matches!(result, Err(Mcp2003aError::BreakTooShort(573))));
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.delay.delays_ns.clear();
+            block_on(mcp2003a.send_break_us_async(573)).unwrap();
+            assert_eq!(mcp2003a.delay.delays_ns, [573_000, 52_083]);
+        }
+
+        // 11 bit periods at very low bus speeds do not fit in u32 nanoseconds
+        mcp2003a.init(LinBusConfig {
+            speed: LinBusSpeed::BaudBps(1),
+            ..LinBusConfig::default()
+        });
+        let result = mcp2003a.send_break_us(1_000);
+        assert!(matches!(result, Err(Mcp2003aError::BreakTooShort(11_000_000))));
+        #[cfg(feature = "async")]
+        {
+            let result = block_on(mcp2003a.send_break_us_async(1_000));
+            assert!(matches!(result, Err(Mcp2003aError::BreakTooShort(11_000_000))));
+        }
+    }
+
+    #[test]
+    fn test_send_frame_confirmed() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx = std::vec![0x00, 0x55, 0xC1, 0x01, 0x02, 0x3B];
+        assert_eq!(mcp2003a.send_frame_confirmed(0xC1, &[0x01, 0x02], 0x3B).unwrap(), 5);
+
+        // A dropped byte shows in the count
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x3B];
+        assert_eq!(mcp2003a.send_frame_confirmed(0xC1, &[0x01, 0x02], 0x3B).unwrap(), 4);
+
+        // The break read back as a framing error is not counted
+        mcp2003a.uart.rx_framing_errors = 1;
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, 0x3B, 0xAA];
+        assert_eq!(mcp2003a.send_frame_confirmed(0xC1, &[0x01, 0x02], 0x3B).unwrap(), 5);
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn test_send_frame_confirmed_async() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx = std::vec![0x00, 0x55, 0xC1, 0x01];
+        let echoed = block_on(mcp2003a.send_frame_confirmed_async(0xC1, &[0x01, 0x02], 0x3B)).unwrap();
+        assert_eq!(echoed, 3);
+    }
+
+    #[test]
+    fn test_poll_ids() {
+        let mut mcp2003a = mock_mcp2003a();
+        let checksum = ChecksumMode::Enhanced.get_checksum(0x80, &[0x01, 0x02]);
+        mcp2003a.uart.rx = std::vec![0x55, 0x80, 0x01, 0x02, checksum];
+        let mut out = [[0xFF; 8]; 1];
+        let mut lens = [None; 2];
+        assert_eq!(mcp2003a.poll_ids(&[0x80], &mut out, &mut lens).unwrap(), 1);
+        assert_eq!(lens, [Some(2), None]);
+        assert_eq!(out[0], [0x01, 0x02, 0, 0, 0, 0, 0, 0]);
+
+        // A row and a length are needed for each ID
+        assert!(matches!(
+            mcp2003a.poll_ids(&[0x80, 0xC1], &mut out, &mut lens),
+            Err(Mcp2003aError::OutputTooSmall(2))
+        ));
+
+        // No response, and the inter-frame space is only applied once, after the last ID
+        mcp2003a.delay.delays_ns.clear();
+        let mut out = [[0xFF; 8]; 2];
+        assert_eq!(mcp2003a.poll_ids(&[0x80, 0xC1], &mut out, &mut lens).unwrap(), 0);
+        assert_eq!(lens, [None, None]);
+        let inter_frame_space = LinInterFrameSpace::DelayMilliseconds(1).get_duration_ns();
+        assert_eq!(
+            mcp2003a
+                .delay
+                .delays_ns
+                .iter()
+                .filter(|ns| **ns == inter_frame_space)
+                .count(),
+            1
+        );
+
+        // With auto_pid, an enhanced checksum over the PID is accepted for a raw ID
+        mcp2003a.init(LinBusConfig {
+            auto_pid: true,
+            ..LinBusConfig::default()
+        });
+        let checksum = ChecksumMode::Enhanced.get_checksum(0xC1, &[0x01, 0x02]);
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, checksum];
+        assert_eq!(mcp2003a.poll_ids(&[0x01], &mut out, &mut lens).unwrap(), 1);
+        assert_eq!(lens[0], Some(2));
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, checksum];
+            assert_eq!(
+                block_on(mcp2003a.poll_ids_async(&[0x01], &mut out, &mut lens)).unwrap(),
+                1
+            );
+            assert_eq!(lens[0], Some(2));
+        }
+    }
+
+    #[cfg(feature = "unchecked-init")]
+    #[test]
+    fn test_unchecked_init() {
+        let mut mcp2003a = Mcp2003a::new(MockUart::default(), MockPin::default(), MockDelay::default());
+        assert_eq!(mcp2003a.config.speed, LinBusSpeed::Baud19200);
+
+        // Frames are refused until the timings are configured
+        assert!(matches!(
+            mcp2003a.send_frame(0xC1, &[0x01], 0x3D),
+            Err(Mcp2003aError::NotInitialized)
+        ));
+        assert!(matches!(
+            mcp2003a.read_frame(0xC1, &mut [0u8; 1]),
+            Err(Mcp2003aError::NotInitialized)
+        ));
+        #[cfg(feature = "async")]
+        {
+            assert!(matches!(
+                block_on(mcp2003a.send_frame_async(0xC1, &[0x01], 0x3D)),
+                Err(Mcp2003aError::NotInitialized)
+            ));
+        }
+        assert!(mcp2003a.uart.tx.is_empty());
+        assert!(mcp2003a.break_pin.levels.is_empty());
+
+        mcp2003a.init(LinBusConfig {
+            speed: LinBusSpeed::Baud9600,
+            ..LinBusConfig::default()
+        });
+        assert_eq!(mcp2003a.config.speed, LinBusSpeed::Baud9600);
+        mcp2003a.send_frame(0xC1, &[0x01], 0x3D).unwrap();
+    }
+
+    #[test]
+    fn test_typestate_init() {
+        let mcp2003a = typestate::Uninitialized::new(MockUart::default(), MockPin::default(), MockDelay::default())
+            .init(LinBusConfig {
+                speed: LinBusSpeed::Baud9600,
+                ..LinBusConfig::default()
+            });
+        assert_eq!(mcp2003a.config.speed, LinBusSpeed::Baud9600);
+        assert_eq!(mcp2003a.last_frame_duration_ns(), 0);
+    }
+
+    #[test]
+    fn test_read_frame_validated() {
+        let mut mcp2003a = mock_mcp2003a();
+        let mut buffer = [0u8; 2];
+        let classic = ChecksumMode::Classic.get_checksum(0xC1, &[0x01, 0x02]);
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, classic];
+        let result = mcp2003a.read_frame_validated(0xC1, &mut buffer, ChecksumMode::Auto);
+        assert!(matches!(result, Ok((ChecksumMode::Classic, c)) if c == classic));
+
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, classic];
+        let result = mcp2003a.read_frame_validated(0xC1, &mut buffer, ChecksumMode::Enhanced);
+        assert!(matches!(result, Err(Mcp2003aError::LinReadInvalidChecksum(c)) if c == classic));
+    }
+
+    #[test]
+    fn test_custom_checksum() {
+        fn additive(_pid: u8, data: &[u8]) -> u8 {
+            data.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte))
+        }
+        static FRAMES: FrameTable = FrameTable::new().with_frame(0x01, 2, ChecksumMode::Custom(additive));
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.set_frame_table(&FRAMES);
+
+        mcp2003a.send_frame_auto(0x01, &[0xF0, 0x20]).unwrap();
+        assert_eq!(mcp2003a.uart.tx, [0x55, 0xC1, 0xF0, 0x20, 0x10]);
+
+        // The data length must match the frame table, and nothing is sent otherwise
+        mcp2003a.uart.tx.clear();
+        assert!(matches!(
+            mcp2003a.send_frame_auto(0x01, &[0xF0, 0x20, 0x00]),
+            Err(Mcp2003aError::InvalidFrame(FrameError::LengthMismatch {
+                expected: 2,
+                actual: 3
+            }))
+        ));
+        assert!(mcp2003a.uart.tx.is_empty());
+
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, 0x03];
+        let mut buffer = [0u8; 2];
+        let (mode, checksum) = mcp2003a
+            .read_frame_validated(0xC1, &mut buffer, ChecksumMode::Custom(additive))
+            .unwrap();
+        assert!(matches!(mode, ChecksumMode::Custom(_)));
+        assert_eq!(checksum, 0x03);
+
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, 0xFC];
+        assert!(matches!(
+            mcp2003a.read_frame_validated(0xC1, &mut buffer, ChecksumMode::Custom(additive)),
+            Err(Mcp2003aError::LinReadInvalidChecksum(0xFC))
+        ));
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.tx.clear();
+            block_on(mcp2003a.send_frame_auto_async(0x01, &[0x01, 0x02])).unwrap();
+            assert_eq!(mcp2003a.uart.tx, [0x55, 0xC1, 0x01, 0x02, 0x03]);
+
+            mcp2003a.uart.tx.clear();
+            assert!(matches!(
+                block_on(mcp2003a.send_frame_auto_async(0x01, &[0x01])),
+                Err(Mcp2003aError::InvalidFrame(FrameError::LengthMismatch {
+                    expected: 2,
+                    actual: 1
+                }))
+            ));
+            assert!(mcp2003a.uart.tx.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_read_frame_auto() {
+        static FRAMES: FrameTable = FrameTable::new()
+            .with_frame(0x01, 2, ChecksumMode::Enhanced)
+            .with_frame(0x3D, 1, ChecksumMode::Classic);
+        let mut mcp2003a = mock_mcp2003a();
+        assert!(matches!(
+            mcp2003a.read_frame_auto(0x01),
+            Err(Mcp2003aError::FrameNotInTable(0x01))
+        ));
+        mcp2003a.set_frame_table(&FRAMES);
+
+        let enhanced = ChecksumMode::Enhanced.get_checksum(0xC1, &[0x01, 0x02]);
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, enhanced];
+        let frame = mcp2003a.read_frame_auto(0x01).unwrap();
+        assert_eq!(frame.pid(), 0xC1);
+        assert_eq!(frame.data(), [0x01, 0x02]);
+        assert_eq!(frame.checksum(), enhanced);
+        assert_eq!(frame.checksum_mode(), ChecksumMode::Enhanced);
+        assert_eq!(mcp2003a.uart.tx, [0x55, 0xC1]);
+
+        #[cfg(feature = "async")]
+        {
+            let classic = ChecksumMode::Classic.get_checksum(0x7D, &[0x03]);
+            mcp2003a.uart.rx = std::vec![0x55, 0x7D, 0x03, classic];
+            let frame = block_on(mcp2003a.read_frame_auto_async(0x3D)).unwrap();
+            assert_eq!(frame.data(), [0x03]);
+            assert_eq!(frame.checksum_mode(), ChecksumMode::Classic);
+        }
+
+        assert!(matches!(
+            mcp2003a.read_frame_auto(0x02),
+            Err(Mcp2003aError::FrameNotInTable(0x02))
+        ));
+    }
+
+    #[test]
+    fn test_enable_pin() {
+        let mut mcp2003a = typestate::Uninitialized::new_with_enable(
+            MockUart::default(),
+            MockPin::default(),
+            MockPin::default(),
+            MockDelay::default(),
+        )
+        .init(LinBusConfig::default());
+        let enable_settle = LinBusConfig::default().enable_settle.get_duration_ns();
+
+        // Not enabled automatically unless configured
+        mcp2003a.send_frame(0x01, &[0x02], 0x03).unwrap();
+        assert!(mcp2003a.enable_pin.as_ref().unwrap().levels.is_empty());
+
+        // The settle time is only waited when enabling a disabled transceiver
+        mcp2003a.enable().unwrap();
+        mcp2003a.enable().unwrap();
+        mcp2003a.disable().unwrap();
+        assert_eq!(mcp2003a.enable_pin.as_ref().unwrap().levels, [true, true, false]);
+        assert_eq!(
+            mcp2003a
+                .delay
+                .delays_ns
+                .iter()
+                .filter(|ns| **ns == enable_settle)
+                .count(),
+            1
+        );
+
+        // Enabled once before the first break with auto_enable
+        mcp2003a.init(LinBusConfig {
+            auto_enable: true,
+            ..LinBusConfig::default()
+        });
+        mcp2003a.enable_pin.as_mut().unwrap().levels.clear();
+        mcp2003a.send_frame(0x01, &[0x02], 0x03).unwrap();
+        mcp2003a.send_frame(0x01, &[0x02], 0x03).unwrap();
+        assert_eq!(mcp2003a.enable_pin.as_ref().unwrap().levels, [true]);
+
+        #[cfg(feature = "async")]
+        {
+            block_on(mcp2003a.disable_async()).unwrap();
+            block_on(mcp2003a.send_wakeup_async()).unwrap();
+            assert_eq!(mcp2003a.enable_pin.as_ref().unwrap().levels, [true, false, true]);
+        }
+    }
+
+    #[test]
+    fn test_send_wakeup_separate_pin() {
+        let mut mcp2003a = typestate::Uninitialized::new_with_wakeup_pin(
+            MockUart::default(),
+            MockPin::default(),
+            Some(MockPin::default()),
+            MockDelay::default(),
+        )
+        .init(LinBusConfig::default());
+        mcp2003a.send_wakeup().unwrap();
+        assert!(mcp2003a.break_pin.levels.is_empty());
+        assert_eq!(mcp2003a.wakeup_pin.as_ref().unwrap().levels, [true, false]);
+
+        #[cfg(feature = "async")]
+        {
+            block_on(mcp2003a.send_wakeup_async()).unwrap();
+            assert_eq!(mcp2003a.wakeup_pin.as_ref().unwrap().levels, [true, false, true, false]);
+        }
+
+        // The break is still on the break pin
+        mcp2003a.send_frame(0x01, &[0x02], 0x03).unwrap();
+        assert_eq!(mcp2003a.break_pin.levels, [true, false]);
+    }
+
+    #[test]
+    fn test_error_from_uart_errors() {
+        let error: Mcp2003aError<MockUartError> = embedded_hal_nb::nb::Error::Other(MockUartError::Other).into();
+        assert!(matches!(
+            error,
+            Mcp2003aError::UartError(embedded_hal_nb::nb::Error::Other(MockUartError::Other))
+        ));
+        let error: Mcp2003aError<MockUartError> = MockUartError::FrameFormat.into();
+        assert!(matches!(
+            error,
+            Mcp2003aError::AsyncUartError(MockUartError::FrameFormat)
+        ));
+    }
+
+    #[test]
+    fn test_read_frame_per_byte_timeout() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.init(LinBusConfig {
+            read_device_response_timeout: LinReadDeviceResponseTimeout::PerByteBitPeriods(14),
+            inter_frame_space: LinInterFrameSpace::None,
+            ..LinBusConfig::default()
+        });
+        let mut buffer = [0u8; 2];
+        let _ = mcp2003a.read_frame(0xC1, &mut buffer);
+        assert!(mcp2003a.delay.delays_ns.contains(&(3 * 14 * 52_083)));
+    }
+
+    #[test]
+    fn test_gpio_error() {
+        /// Mock GPIO pin that always fails.
+        struct FailingPin;
+
+        impl embedded_hal::digital::ErrorType for FailingPin {
+            type Error = embedded_hal::digital::ErrorKind;
+        }
+
+        impl OutputPin for FailingPin {
+            fn set_low(&mut self) -> Result<(), Self::Error> {
+                Err(embedded_hal::digital::ErrorKind::Other)
+            }
+
+            fn set_high(&mut self) -> Result<(), Self::Error> {
+                Err(embedded_hal::digital::ErrorKind::Other)
+            }
+        }
+
+        let mut mcp2003a = typestate::Uninitialized::new(MockUart::default(), FailingPin, MockDelay::default())
+            .init(LinBusConfig::default());
+        let result = mcp2003a.send_frame(0x01, &[0x02], 0x03);
+        assert!(matches!(result, Err(Mcp2003aError::GpioError)));
+        assert!(mcp2003a.uart.tx.is_empty());
+
+        // The break, wakeup, and enable pins all return their errors
+        assert!(matches!(mcp2003a.send_break_us(1_000), Err(Mcp2003aError::GpioError)));
+        assert!(matches!(mcp2003a.send_wakeup(), Err(Mcp2003aError::GpioError)));
+        let mut mcp2003a = typestate::Uninitialized::new_with_enable(
+            MockUart::default(),
+            FailingPin,
+            FailingPin,
+            MockDelay::default(),
+        )
+        .init(LinBusConfig::default());
+        assert!(matches!(mcp2003a.enable(), Err(Mcp2003aError::GpioError)));
+
+        #[cfg(feature = "async")]
+        {
+            assert!(matches!(
+                block_on(mcp2003a.send_frame_async(0x01, &[0x02], 0x03)),
+                Err(Mcp2003aError::GpioError)
+            ));
+            assert!(matches!(
+                block_on(mcp2003a.send_wakeup_async()),
+                Err(Mcp2003aError::GpioError)
+            ));
+            assert!(matches!(mcp2003a.abort(), Err(Mcp2003aError::GpioError)));
+        }
+    }
+
+    #[test]
+    fn test_read_frame_resync_after_false_sync() {
+        let mut mcp2003a = mock_mcp2003a();
+        let noisy = [0x55, 0x42, 0x55, 0x55, 0xC1, 0x01, 0x02, 0x3B];
+        mcp2003a.uart.rx = noisy.to_vec();
+        let mut buffer = [0u8; 2];
+        assert_eq!(mcp2003a.read_frame(0xC1, &mut buffer).unwrap(), 0x3B);
+        assert_eq!(buffer, [0x01, 0x02]);
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.rx = noisy.to_vec();
+            let mut buffer = [0u8; 2];
+            assert_eq!(block_on(mcp2003a.read_frame_async(0xC1, &mut buffer)).unwrap(), 0x3B);
+            assert_eq!(buffer, [0x01, 0x02]);
+
+            // A false sync then a sync without its ID: the read ends once the bytes run out, as the mock read waits forever instead of
+            // returning 0 bytes
+            mcp2003a.uart.rx = std::vec![0x55, 0x42, 0x55];
+            assert!(matches!(
+                block_on(mcp2003a.read_frame_async(0xC1, &mut buffer)),
+                Err(Mcp2003aError::IdByteNotReceivedBack)
+            ));
+
+            // A resync followed by a short response
+            mcp2003a.uart.rx = std::vec![0x55, 0x42, 0x55, 0xC1, 0x01];
+            assert!(matches!(
+                block_on(mcp2003a.read_frame_async(0xC1, &mut buffer)),
+                Err(Mcp2003aError::LinReadOnlyPartialResponse { received: 1, .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_read_frame_header_skip_bytes() {
+        // The transceiver echoes 2 more bytes after the header
+        let echoed = [0x55, 0xC1, 0xAA, 0xBB, 0x01, 0x02, 0x3B];
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.init(LinBusConfig {
+            header_skip_bytes: 4,
+            ..LinBusConfig::default()
+        });
+        mcp2003a.uart.rx = echoed.to_vec();
+        let mut buffer = [0u8; 2];
+        assert_eq!(mcp2003a.read_frame(0xC1, &mut buffer).unwrap(), 0x3B);
+        assert_eq!(buffer, [0x01, 0x02]);
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.rx = echoed.to_vec();
+            assert_eq!(block_on(mcp2003a.read_frame_async(0xC1, &mut buffer)).unwrap(), 0x3B);
+            assert_eq!(buffer, [0x01, 0x02]);
+        }
+
+        // Without skipping them, the extra echoed bytes are read as the response
+        mcp2003a.init(LinBusConfig::default());
+        mcp2003a.uart.rx = echoed.to_vec();
+        assert_eq!(mcp2003a.read_frame(0xC1, &mut buffer).unwrap(), 0x01);
+        assert_eq!(buffer, [0xAA, 0xBB]);
+    }
+
+    #[test]
+    fn test_read_frame_max_sync_bytes() {
+        // The PID of ID 0x15 is 0x55, so a repeated sync byte is read as the ID byte
+        let pid = get_pid(0x15);
+        let doubled = [0x55, 0x55, pid, 0x01, 0x02, 0x03];
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx = doubled.to_vec();
+        let mut buffer = [0u8; 2];
+        assert_eq!(mcp2003a.read_frame(pid, &mut buffer).unwrap(), 0x02);
+        assert_eq!(buffer, [0x55, 0x01]);
+
+        mcp2003a.init(LinBusConfig {
+            max_sync_bytes: 2,
+            ..LinBusConfig::default()
+        });
+        mcp2003a.uart.rx = doubled.to_vec();
+        assert_eq!(mcp2003a.read_frame(pid, &mut buffer).unwrap(), 0x03);
+        assert_eq!(buffer, [0x01, 0x02]);
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.rx = doubled.to_vec();
+            assert_eq!(block_on(mcp2003a.read_frame_async(pid, &mut buffer)).unwrap(), 0x03);
+            assert_eq!(buffer, [0x01, 0x02]);
+        }
+
+        // Other PIDs are still found after a single sync byte
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, 0x3B];
+        assert_eq!(mcp2003a.read_frame(0xC1, &mut buffer).unwrap(), 0x3B);
+        assert_eq!(buffer, [0x01, 0x02]);
+    }
+
+    #[test]
+    fn test_read_frame_fuzzy_sync() {
+        let mut mcp2003a = mock_mcp2003a();
+        let mut buffer = [0u8; 2];
+        // Exact match by default
+        mcp2003a.uart.rx = std::vec![0x54, 0xC1, 0x01, 0x02, 0x3B];
+        assert!(matches!(
+            mcp2003a.read_frame(0xC1, &mut buffer),
+            Err(Mcp2003aError::PossibleBaudMismatch(0x54))
+        ));
+
+        mcp2003a.init(LinBusConfig {
+            fuzzy_sync: true,
+            ..LinBusConfig::default()
+        });
+        // Sync bytes one bit off 0x55 are accepted
+        for sync in [0x54, 0x57, 0x5D, 0xD5] {
+            mcp2003a.uart.rx = std::vec![sync, 0xC1, 0x01, 0x02, 0x3B];
+            assert_eq!(mcp2003a.read_frame(0xC1, &mut buffer).unwrap(), 0x3B);
+            assert_eq!(buffer, [0x01, 0x02]);
+        }
+
+        // Two bits off is not a sync byte
+        mcp2003a.uart.rx = std::vec![0x56, 0xC1, 0x01, 0x02, 0x3B];
+        assert!(matches!(
+            mcp2003a.read_frame(0xC1, &mut buffer),
+            Err(Mcp2003aError::PossibleBaudMismatch(0x56))
+        ));
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.rx = std::vec![0x57, 0xC1, 0x01, 0x02, 0x3B];
+            assert_eq!(block_on(mcp2003a.read_frame_async(0xC1, &mut buffer)).unwrap(), 0x3B);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn test_read_frame_async_errors() {
+        let mut mcp2003a = mock_mcp2003a();
+        let mut buffer = [0u8; 2];
+        assert!(matches!(
+            block_on(mcp2003a.read_frame_async(0xC1, &mut buffer)),
+            Err(Mcp2003aError::SyncByteNotReceivedBack)
+        ));
+
+        mcp2003a.uart.rx = std::vec![0x00, 0x55, 0xC1, 0x01];
+        assert!(matches!(
+            block_on(mcp2003a.read_frame_async(0xC1, &mut buffer)),
+            Err(Mcp2003aError::LinReadOnlyPartialResponse {
+                received: 1,
+                saw_checksum: false
+            })
+        ));
+    }
+
+    #[test]
+    fn test_read_frame_zeroes_unused_buffer() {
+        let mut mcp2003a = mock_mcp2003a();
+        let mut buffer = [0u8; 4];
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, 0x03, 0x04, 0x05];
+        mcp2003a.read_frame(0xC1, &mut buffer).unwrap();
+        assert_eq!(buffer, [0x01, 0x02, 0x03, 0x04]);
+
+        // A shorter response in the same buffer
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x0A, 0x0B];
+        assert!(matches!(
+            mcp2003a.read_frame(0xC1, &mut buffer),
+            Err(Mcp2003aError::LinReadOnlyPartialResponse { received: 2, .. })
+        ));
+        assert_eq!(buffer, [0x0A, 0x0B, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_read_frame_auto_pid() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.init(LinBusConfig {
+            auto_pid: true,
+            ..LinBusConfig::default()
+        });
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x02, 0x03];
+        let mut buffer = [0u8; 1];
+        assert_eq!(mcp2003a.read_frame(0x01, &mut buffer).unwrap(), 0x03);
+        assert_eq!(buffer, [0x02]);
+        assert_eq!(&mcp2003a.uart.tx, &[0x55, 0xC1]);
+
+        // A PID is sent unchanged
+        mcp2003a.uart.tx.clear();
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x02, 0x03];
+        assert_eq!(mcp2003a.read_frame(0xC1, &mut buffer).unwrap(), 0x03);
+        assert_eq!(&mcp2003a.uart.tx, &[0x55, 0xC1]);
+
+        // The checksum is validated over the PID
+        let checksum = ChecksumMode::Enhanced.get_checksum(0xC1, &[0x02]);
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x02, checksum];
+        let (mode, _) = mcp2003a
+            .read_frame_validated(0x01, &mut buffer, ChecksumMode::Enhanced)
+            .unwrap();
+        assert_eq!(mode, ChecksumMode::Enhanced);
+    }
+
+    #[test]
+    fn test_read_frame_without_header_echo() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.init(LinBusConfig {
+            expect_header_echo: false,
+            ..LinBusConfig::default()
+        });
+        mcp2003a.uart.rx = std::vec![0x55, 0x02, 0x03];
+        let mut buffer = [0u8; 2];
+        assert_eq!(mcp2003a.read_frame(0xC1, &mut buffer).unwrap(), 0x03);
+        assert_eq!(buffer, [0x55, 0x02]);
+    }
+
+    #[test]
+    fn test_transact() {
+        let mut mcp2003a = mock_mcp2003a();
+        // The echo of the request is not read as the response
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, 0x03];
+        let mut response = [0u8; 2];
+        let result = mcp2003a.transact(0xC1, &[0x01, 0x02], 0x03, &mut response);
+        assert!(matches!(result, Err(Mcp2003aError::SyncByteNotReceivedBack)));
+        assert_eq!(mcp2003a.uart.tx, [0x55, 0xC1, 0x01, 0x02, 0x03, 0x55, 0xC1]);
+
+        // The inter-frame space is applied after the request and after the response only
+        let inter_frame_space = LinInterFrameSpace::DelayMilliseconds(1).get_duration_ns();
+        assert_eq!(
+            mcp2003a
+                .delay
+                .delays_ns
+                .iter()
+                .filter(|ns| **ns == inter_frame_space)
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_transact_auto_pid() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.init(LinBusConfig {
+            auto_pid: true,
+            ..LinBusConfig::default()
+        });
+        // Echo of the request, then the response to its header
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, 0x03, 0x55, 0xC1, 0x04, 0x05, 0x06];
+        mcp2003a.uart.rx_would_block_after = std::vec![5];
+        let mut response = [0u8; 2];
+        assert_eq!(
+            mcp2003a.transact(0x01, &[0x01, 0x02], 0x03, &mut response).unwrap(),
+            0x06
+        );
+        // Both headers are sent with the PID
+        assert_eq!(mcp2003a.uart.tx, [0x55, 0xC1, 0x01, 0x02, 0x03, 0x55, 0xC1]);
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.tx.clear();
+            mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, 0x03, 0x55, 0xC1, 0x04, 0x05, 0x06];
+            mcp2003a.uart.rx_would_block_after = std::vec![5];
+            let result = block_on(mcp2003a.transact_async(0x01, &[0x01, 0x02], 0x03, &mut response));
+            assert_eq!(result.unwrap(), 0x06);
+            assert_eq!(mcp2003a.uart.tx, [0x55, 0xC1, 0x01, 0x02, 0x03]);
+            // The response header is written without a flush
+            assert_eq!(mcp2003a.uart.tx_buffered, [0x55, 0xC1]);
+        }
+    }
+
+    #[test]
+    fn test_transact_validated_mixed_modes() {
+        let request = [0x01, 0x02];
+        let classic = ChecksumMode::Classic.get_checksum(0xC1, &request);
+        let enhanced = ChecksumMode::Enhanced.get_checksum(0xC1, &[0x03, 0x04]);
+        let mut mcp2003a = mock_mcp2003a();
+        // Echo of the request, then the response to its header
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, classic, 0x55, 0xC1, 0x03, 0x04, enhanced];
+        mcp2003a.uart.rx_would_block_after = std::vec![5];
+        let mut response = [0u8; 2];
+        let result = mcp2003a.transact_validated(
+            0xC1,
+            &request,
+            ChecksumMode::Classic,
+            &mut response,
+            ChecksumMode::Enhanced,
+        );
+        assert_eq!(result.unwrap(), (ChecksumMode::Enhanced, enhanced));
+        assert_eq!(response, [0x03, 0x04]);
+        assert_eq!(mcp2003a.uart.tx, [0x55, 0xC1, 0x01, 0x02, classic, 0x55, 0xC1]);
+
+        // The response is not accepted with the checksum of the request
+        mcp2003a.uart.tx.clear();
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, classic, 0x55, 0xC1, 0x03, 0x04, enhanced];
+        mcp2003a.uart.rx_would_block_after = std::vec![5];
+        let result = mcp2003a.transact_validated(
+            0xC1,
+            &request,
+            ChecksumMode::Classic,
+            &mut response,
+            ChecksumMode::Classic,
+        );
+        assert!(matches!(result, Err(Mcp2003aError::LinReadInvalidChecksum(c)) if c == enhanced));
+
+        // With auto_pid, the request checksum is calculated over the PID like the response checksum
+        let request_enhanced = ChecksumMode::Enhanced.get_checksum(0xC1, &request);
+        mcp2003a.init(LinBusConfig {
+            auto_pid: true,
+            ..LinBusConfig::default()
+        });
+        mcp2003a.uart.tx.clear();
+        mcp2003a.uart.rx = std::vec![
+            0x55,
+            0xC1,
+            0x01,
+            0x02,
+            request_enhanced,
+            0x55,
+            0xC1,
+            0x03,
+            0x04,
+            enhanced
+        ];
+        mcp2003a.uart.rx_would_block_after = std::vec![5];
+        let result = mcp2003a.transact_validated(
+            0x01,
+            &request,
+            ChecksumMode::Enhanced,
+            &mut response,
+            ChecksumMode::Enhanced,
+        );
+        assert_eq!(result.unwrap(), (ChecksumMode::Enhanced, enhanced));
+        assert_eq!(mcp2003a.uart.tx, [0x55, 0xC1, 0x01, 0x02, request_enhanced, 0x55, 0xC1]);
+    }
+
+    #[test]
+    fn test_read_and_clear_errors() {
+        let mut mcp2003a = mock_mcp2003a();
+        let checksum = ChecksumMode::Enhanced.get_checksum(0xC1, &[0x04, 0x00]);
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x04, 0x00, checksum];
+        let mut errors = [0u8; 2];
+        let result = mcp2003a.read_and_clear_errors(0xC1, &mut errors, 0x42, &[0xFF], ChecksumMode::Enhanced);
+        assert_eq!(result.unwrap(), checksum);
+        assert_eq!(errors, [0x04, 0x00]);
+        let clear_checksum = ChecksumMode::Enhanced.get_checksum(0x42, &[0xFF]);
+        assert_eq!(mcp2003a.uart.tx, [0x55, 0xC1, 0x55, 0x42, 0xFF, clear_checksum]);
+
+        // Nothing is cleared when the errors could not be read
+        mcp2003a.uart.tx.clear();
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x04, 0x00, 0x00];
+        let result = mcp2003a.read_and_clear_errors(0xC1, &mut errors, 0x42, &[0xFF], ChecksumMode::Enhanced);
+        assert!(matches!(result, Err(Mcp2003aError::LinReadInvalidChecksum(0x00))));
+        assert_eq!(mcp2003a.uart.tx, [0x55, 0xC1]);
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.tx.clear();
+            mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x04, 0x00, checksum];
+            let result = block_on(mcp2003a.read_and_clear_errors_async(
+                0xC1,
+                &mut errors,
+                0x42,
+                &[0xFF],
+                ChecksumMode::Enhanced,
+            ));
+            assert_eq!(result.unwrap(), checksum);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn test_transact_async() {
+        let mut mcp2003a = mock_mcp2003a();
+        // Echo of the request, then the response to its header
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, 0x03, 0x55, 0xC1, 0x04, 0x05, 0x06];
+        mcp2003a.uart.rx_would_block_after = std::vec![5];
+        let mut response = [0u8; 2];
+        let result = block_on(mcp2003a.transact_async(0xC1, &[0x01, 0x02], 0x03, &mut response));
+        assert_eq!(result.unwrap(), 0x06);
+        assert_eq!(response, [0x04, 0x05]);
+
+        // The inter-frame space is applied after the request and after the response only
+        let inter_frame_space = LinInterFrameSpace::DelayMilliseconds(1).get_duration_ns();
+        assert_eq!(
+            mcp2003a
+                .delay
+                .delays_ns
+                .iter()
+                .filter(|ns| **ns == inter_frame_space)
+                .count(),
+            2
+        );
+
+        // The echo of the request is not read as the response
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, 0x03];
+        let result = block_on(mcp2003a.transact_async(0xC1, &[0x01, 0x02], 0x03, &mut response));
+        assert!(matches!(result, Err(Mcp2003aError::SyncByteNotReceivedBack)));
+    }
+
+    #[test]
+    fn test_measure_break() {
+        /// Mock bus sense pin that reads dominant for the given number of samples.
+        struct MockSensePin {
+            dominant_samples: usize,
+        }
+
+        impl embedded_hal::digital::ErrorType for MockSensePin {
+            type Error = Infallible;
+        }
+
+        impl InputPin for MockSensePin {
+            fn is_high(&mut self) -> Result<bool, Infallible> {
+                self.is_low().map(|low| !low)
+            }
+
+            fn is_low(&mut self) -> Result<bool, Infallible> {
+                let dominant = self.dominant_samples > 0;
+                self.dominant_samples = self.dominant_samples.saturating_sub(1);
+                Ok(dominant)
+            }
+        }
+
+        let mut mcp2003a = mock_mcp2003a();
+        // 13 bit periods at 19200 baud is 678 microseconds, the bus is released a bit late
+        let mut sense = MockSensePin { dominant_samples: 690 };
+        assert_eq!(mcp2003a.measure_break(&mut sense).unwrap(), 690);
+        // The break pin is released once
+        assert_eq!(mcp2003a.break_pin.levels, [true, false]);
+        assert_eq!(mcp2003a.delay.delays_ns.last(), Some(&52_083));
+
+        // A bus stuck dominant is measured for up to twice the break duration
+        let mut sense = MockSensePin {
+            dominant_samples: 10_000,
+        };
+        assert_eq!(mcp2003a.measure_break(&mut sense).unwrap(), 2 * 678);
+
+        // The break delimiter is not held dominant when the break duration includes it
+        mcp2003a.init(LinBusConfig {
+            break_includes_delimiter: true,
+            ..LinBusConfig::default()
+        });
+        let mut sense = MockSensePin {
+            dominant_samples: 10_000,
+        };
+        assert_eq!(mcp2003a.measure_break(&mut sense).unwrap(), 2 * 625);
+
+        // Nothing is driven before the timings are configured
+        #[cfg(feature = "unchecked-init")]
+        {
+            let mut mcp2003a = Mcp2003a::new(MockUart::default(), MockPin::default(), MockDelay::default());
+            assert!(matches!(
+                mcp2003a.measure_break(&mut sense),
+                Err(Mcp2003aError::NotInitialized)
+            ));
+            assert!(mcp2003a.break_pin.levels.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_send_frame_scratch() {
+        let mut mcp2003a = mock_mcp2003a();
+        let data = [0xA5; 12];
+        let mut scratch = [0u8; 16];
+        assert_eq!(
+            mcp2003a.send_frame_scratch(0xC1, &data, 0x3C, &mut scratch).unwrap(),
+            15
+        );
+        assert_eq!(&mcp2003a.uart.tx[..2], &[0x55, 0xC1]);
+        assert_eq!(&mcp2003a.uart.tx[2..14], &data);
+        assert_eq!(mcp2003a.uart.tx[14], 0x3C);
+        assert_eq!(mcp2003a.uart.tx.len(), 15);
+        assert_eq!(&scratch[..15], mcp2003a.uart.tx.as_slice());
+
+        let result = mcp2003a.send_frame_scratch(0xC1, &[0xA5; 14], 0x3C, &mut scratch);
+        assert!(matches!(result, Err(Mcp2003aError::ScratchBufferTooSmall(17))));
+        let result = mcp2003a.send_frame_scratch(0xC1, &[], 0x3C, &mut scratch);
+        assert!(matches!(result, Err(Mcp2003aError::InvalidFrame(FrameError::NoData))));
+
+        // The break is extended after a wakeup like `send_frame`
+        mcp2003a.init(LinBusConfig {
+            post_wakeup_break_frames: 1,
+            post_wakeup_break_extension: 3,
+            ..LinBusConfig::default()
+        });
+        mcp2003a.send_wakeup().unwrap();
+        mcp2003a.delay.delays_ns.clear();
+        mcp2003a.send_frame_scratch(0xC1, &data, 0x3C, &mut scratch).unwrap();
+        assert_eq!(mcp2003a.delay.delays_ns[1], 833_328);
+        let config = LinBusConfig {
+            break_duration: LinBreakDuration::Minimum13BitsPlus(3),
+            ..LinBusConfig::default()
+        };
+        assert_eq!(
+            mcp2003a.last_frame_duration_ns(),
+            config.nominal_frame_time_ns(12) + 1_000_000
+        );
+    }
+
+    #[test]
+    fn test_send_wakeup_too_long() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.init(LinBusConfig {
+            wakeup_duration: LinWakeupDuration::Minimum250MicrosecondsPlus(4_800_000),
+            ..LinBusConfig::default()
+        });
+        assert!(matches!(
+            mcp2003a.send_wakeup(),
+            Err(Mcp2003aError::WakeupTooLong(5_050_000))
+        ));
+        #[cfg(feature = "async")]
+        {
+            assert!(matches!(
+                block_on(mcp2003a.send_wakeup_async()),
+                Err(Mcp2003aError::WakeupTooLong(5_050_000))
+            ));
+        }
+        assert!(mcp2003a.break_pin.levels.is_empty());
+    }
+
+    #[test]
+    fn test_uart_break_baud_switcher() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.set_break_method(BreakMethod::UartBreak);
+        // The mock records the baud rate as a byte written, 0xB0 then the rate in kilobaud
+        mcp2003a.set_baud_switcher(|uart, baud| uart.tx.extend_from_slice(&[0xB0, (baud / 1_000) as u8]));
+        mcp2003a.send_frame(0x01, &[0x02], 0x03).unwrap();
+        // 9 bits at 13292 baud last the 13 bit break at 19200 baud
+        assert_eq!(mcp2003a.uart.tx, [0xB0, 13, 0x00, 0xB0, 19, 0x55, 0x01, 0x02, 0x03]);
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.tx.clear();
+            block_on(mcp2003a.send_frame_async(0x01, &[0x02], 0x03)).unwrap();
+            assert_eq!(mcp2003a.uart.tx, [0xB0, 13, 0x00, 0xB0, 19, 0x55, 0x01, 0x02, 0x03]);
+        }
+    }
+
+    #[test]
+    fn test_framing_error_break() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.set_break_method(BreakMethod::FramingError);
+        // Without a break control, the break pin is driven
+        mcp2003a.send_frame(0x01, &[0x02], 0x03).unwrap();
+        assert_eq!(mcp2003a.break_pin.levels, [true, false]);
+        assert_eq!(mcp2003a.uart.tx, [0x55, 0x01, 0x02, 0x03]);
+
+        // The mock records holding and releasing TX as 0xBB and 0xB1 written
+        mcp2003a.set_tx_break_control(|uart, hold| uart.tx.push(if hold { 0xBB } else { 0xB1 }));
+        mcp2003a.uart.tx.clear();
+        mcp2003a.delay.delays_ns.clear();
+        mcp2003a.send_frame(0x01, &[0x02], 0x03).unwrap();
+        assert_eq!(mcp2003a.break_pin.levels, [true, false]);
+        assert_eq!(mcp2003a.uart.tx, [0xBB, 0xB1, 0x55, 0x01, 0x02, 0x03]);
+        assert_eq!(mcp2003a.delay.delays_ns[..3], [100_000, 677_079, 52_083]);
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.tx.clear();
+            block_on(mcp2003a.send_frame_async(0x01, &[0x02], 0x03)).unwrap();
+            assert_eq!(mcp2003a.uart.tx, [0xBB, 0xB1, 0x55, 0x01, 0x02, 0x03]);
+
+            mcp2003a.uart.tx.clear();
+            mcp2003a.abort().unwrap();
+            assert_eq!(mcp2003a.uart.tx, [0xB1]);
+        }
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_cancelled_future_releases_pins() {
+        use core::future::Future;
+
+        let mut mcp2003a = mock_mcp2003a();
+        let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+
+        // Dropped during the break
+        mcp2003a.delay.pending_ns = Some(677_079);
+        {
+            let mut future = core::pin::pin!(mcp2003a.send_frame_async(0x80, &[0x01], 0x7D));
+            assert!(future.as_mut().poll(&mut cx).is_pending());
+        }
+        assert_eq!(mcp2003a.break_pin.levels, [true, false]);
+
+        // Dropped during the wakeup signal
+        mcp2003a.delay.pending_ns = Some(250_000);
+        {
+            let mut future = core::pin::pin!(mcp2003a.send_wakeup_async());
+            assert!(future.as_mut().poll(&mut cx).is_pending());
+        }
+        assert_eq!(mcp2003a.break_pin.levels, [true, false, true, false]);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_abort() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.set_baud_switcher(|uart, baud| uart.tx.extend_from_slice(&[0xB0, (baud / 1_000) as u8]));
+        mcp2003a.abort().unwrap();
+        assert_eq!(mcp2003a.break_pin.levels, [false]);
+        assert_eq!(mcp2003a.uart.tx, [0xB0, 19]);
+    }
+
+    #[test]
+    fn test_read_frame_end_byte_count() {
+        // A header echo of another ID, then a frame streamed right after it without a pause
+        let streamed = [0x55, 0x42, 0x01, 0x02, 0x03, 0x55, 0xC1, 0x04, 0x05, 0x06];
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx = streamed.to_vec();
+        let mut buffer = [0u8; 2];
+        // Until the UART runs out of bytes, the second frame is found
+        assert_eq!(mcp2003a.read_frame(0xC1, &mut buffer).unwrap(), 0x06);
+
+        mcp2003a.init(LinBusConfig {
+            frame_end_detection: FrameEndDetection::ByteCount,
+            ..LinBusConfig::default()
+        });
+        mcp2003a.uart.rx = streamed.to_vec();
+        assert!(mcp2003a.read_frame(0xC1, &mut buffer).is_err());
+        // Only the 5 bytes of the expected frame were read
+        assert_eq!(mcp2003a.uart.rx, [0x55, 0xC1, 0x04, 0x05, 0x06]);
+        assert_eq!(mcp2003a.read_frame(0xC1, &mut buffer).unwrap(), 0x06);
+        assert_eq!(buffer, [0x04, 0x05]);
+    }
+
+    #[test]
+    fn test_error_is_retryable() {
+        let retryable: [Mcp2003aError<MockUartError>; 12] = [
+            Mcp2003aError::UartError(embedded_hal_nb::nb::Error::Other(MockUartError::FrameFormat)),
+            Mcp2003aError::AsyncUartError(MockUartError::Other),
+            Mcp2003aError::UartWriteNotReady,
+            Mcp2003aError::SyncByteNotReceivedBack,
+            Mcp2003aError::IdByteNotReceivedBack,
+            Mcp2003aError::LinReadDeviceTimeoutNoResponse,
+            Mcp2003aError::LinReadOnlyPartialResponse {
+                received: 1,
+                saw_checksum: false,
+            },
+            Mcp2003aError::LinReadNoChecksumReceived,
+            Mcp2003aError::LinReadInvalidChecksum(0x00),
+            Mcp2003aError::IntegrityCheckFailed(0x00),
+            Mcp2003aError::BusActiveAfterSleep(2),
+            Mcp2003aError::ArbitrationLost,
+        ];
+        for error in retryable.iter() {
+            assert!(error.is_retryable(), "{:?}", error);
+        }
+
+        let fatal: [Mcp2003aError<MockUartError>; 10] = [
+            Mcp2003aError::PossibleBaudMismatch(0xAA),
+            Mcp2003aError::BreakTooShort(573),
+            Mcp2003aError::WakeupTooLong(5_050_000),
+            Mcp2003aError::ScratchBufferTooSmall(12),
+            Mcp2003aError::GpioError,
+            Mcp2003aError::FrameNotInTable(0x01),
+            Mcp2003aError::BaudRateMismatch {
+                configured: 19200,
+                actual: 9600,
+            },
+            Mcp2003aError::NotInitialized,
+            Mcp2003aError::InvalidFrame(FrameError::NoData),
+            Mcp2003aError::OutputTooSmall(2),
+        ];
+        for error in fatal.iter() {
+            assert!(!error.is_retryable(), "{:?}", error);
+        }
+    }
+
+    #[test]
+    fn test_assert_baud_matches() {
+        let mut mcp2003a = mock_mcp2003a();
+        assert!(mcp2003a.assert_baud_matches::<MockUartError>(19200).is_ok());
+        assert!(matches!(
+            mcp2003a.assert_baud_matches::<MockUartError>(9600),
+            Err(Mcp2003aError::BaudRateMismatch {
+                configured: 19200,
+                actual: 9600
+            })
+        ));
+
+        mcp2003a.init(LinBusConfig {
+            speed: LinBusSpeed::BaudBps(10417),
+            ..LinBusConfig::default()
+        });
+        assert!(mcp2003a.assert_baud_matches::<MockUartError>(10417).is_ok());
+        assert!(mcp2003a.assert_baud_matches::<MockUartError>(10400).is_err());
+    }
+
+    #[test]
+    fn test_read_frame_timeout() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02];
+        let mut buffer = [0u8; 1];
+        let timeout = LinReadDeviceResponseTimeout::DelayMilliseconds(7);
+        assert_eq!(mcp2003a.read_frame_timeout(0xC1, &mut buffer, timeout).unwrap(), 0x02);
+        assert!(mcp2003a.delay.delays_ns.contains(&7_000_000));
+        assert!(!mcp2003a.delay.delays_ns.contains(&2_000_000));
+    }
+
+    #[test]
+    fn test_read_frame_min_len() {
+        let mut mcp2003a = mock_mcp2003a();
+        let mut buffer = [0u8; 4];
+        // Full length response
+        let checksum = ChecksumMode::Enhanced.get_checksum(0xC1, &[0x01, 0x02, 0x03, 0x04]);
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, 0x03, 0x04, checksum];
+        assert_eq!(
+            mcp2003a.read_frame_min_len(0xC1, &mut buffer, 2).unwrap(),
+            (4, checksum)
+        );
+
+        // Shorter valid responses with classic and enhanced checksums
+        let checksum = ChecksumMode::Enhanced.get_checksum(0xC1, &[0x05, 0x06]);
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x05, 0x06, checksum];
+        assert_eq!(
+            mcp2003a.read_frame_min_len(0xC1, &mut buffer, 2).unwrap(),
+            (2, checksum)
+        );
+        assert_eq!(buffer, [0x05, 0x06, 0x00, 0x00]);
+        let checksum = ChecksumMode::Classic.get_checksum(0xC1, &[0x07, 0x08, 0x09]);
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x07, 0x08, 0x09, checksum];
+        assert_eq!(
+            mcp2003a.read_frame_min_len(0xC1, &mut buffer, 2).unwrap(),
+            (3, checksum)
+        );
+        assert_eq!(buffer, [0x07, 0x08, 0x09, 0x00]);
+
+        // Shorter than the minimum
+        let checksum = ChecksumMode::Enhanced.get_checksum(0xC1, &[0x0A]);
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x0A, checksum];
+        assert!(matches!(
+            mcp2003a.read_frame_min_len(0xC1, &mut buffer, 2),
+            Err(Mcp2003aError::LinReadOnlyPartialResponse {
+                received: 2,
+                saw_checksum: true
+            })
+        ));
+
+        // Short without a valid checksum
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x05, 0x06, 0x00];
+        assert!(matches!(
+            mcp2003a.read_frame_min_len(0xC1, &mut buffer, 2),
+            Err(Mcp2003aError::LinReadOnlyPartialResponse {
+                received: 3,
+                saw_checksum: false
+            })
+        ));
+
+        #[cfg(feature = "async")]
+        {
+            let checksum = ChecksumMode::Enhanced.get_checksum(0xC1, &[0x05, 0x06]);
+            mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x05, 0x06, checksum];
+            assert_eq!(
+                block_on(mcp2003a.read_frame_min_len_async(0xC1, &mut buffer, 2)).unwrap(),
+                (2, checksum)
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_frame_until() {
+        let mut mcp2003a = mock_mcp2003a();
+        let mut buffer = [0xFFu8; 8];
+        // The first data byte is the number of data bytes that follow it
+        let done = |data: &[u8]| data.len() > data[0] as usize;
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x02, 0x0A, 0x0B, 0x3C];
+        assert_eq!(mcp2003a.read_frame_until(0xC1, &mut buffer, done).unwrap(), (3, 0x3C));
+        assert_eq!(buffer, [0x02, 0x0A, 0x0B, 0x00, 0x00, 0x00, 0x00, 0x00]);
+
+        // Capped at the buffer length
+        let mut buffer = [0u8; 2];
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x07, 0x0A, 0x3C];
+        assert_eq!(mcp2003a.read_frame_until(0xC1, &mut buffer, done).unwrap(), (2, 0x3C));
+
+        // Missing checksum after the predicate ended the data
+        let mut buffer = [0u8; 8];
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x0A];
+        assert!(matches!(
+            mcp2003a.read_frame_until(0xC1, &mut buffer, done),
+            Err(Mcp2003aError::LinReadNoChecksumReceived)
+        ));
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x0A, 0x3C];
+            assert_eq!(
+                block_on(mcp2003a.read_frame_until_async(0xC1, &mut buffer, done)).unwrap(),
+                (2, 0x3C)
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_frame_uninit() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, 0x03];
+        let mut buffer = [MaybeUninit::<u8>::uninit(); 2];
+        let (len, checksum) = mcp2003a.read_frame_uninit(0xC1, &mut buffer).unwrap();
+        assert_eq!((len, checksum), (2, 0x03));
+        // SAFETY: the first `len` bytes were written by `read_frame_uninit`
+        let data: Vec<u8> = buffer[..len].iter().map(|byte| unsafe { byte.assume_init() }).collect();
+        assert_eq!(data, [0x01, 0x02]);
+
+        // A larger buffer reads at most 8 data bytes, leaving the rest untouched
+        let data = [0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17];
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1];
+        mcp2003a.uart.rx.extend_from_slice(&data);
+        mcp2003a.uart.rx.push(0x42);
+        let mut buffer = [MaybeUninit::new(0xEE); 16];
+        let (len, checksum) = mcp2003a.read_frame_uninit(0xC1, &mut buffer).unwrap();
+        assert_eq!((len, checksum), (8, 0x42));
+        for (i, byte) in buffer.iter().enumerate() {
+            let expected = if i < 8 { data[i] } else { 0xEE };
+            // SAFETY: the whole buffer was initialized with `MaybeUninit::new`
+            assert_eq!(unsafe { byte.assume_init() }, expected);
+        }
+
+        // Nothing is written on errors
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01];
+        let mut buffer = [MaybeUninit::new(0xEE); 2];
+        assert!(matches!(
+            mcp2003a.read_frame_uninit(0xC1, &mut buffer),
+            Err(Mcp2003aError::LinReadOnlyPartialResponse { .. })
+        ));
+        // SAFETY: the whole buffer was initialized with `MaybeUninit::new`
+        assert_eq!(unsafe { buffer[0].assume_init() }, 0xEE);
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x02, 0x03];
+            let (len, checksum) = block_on(mcp2003a.read_frame_uninit_async(0xC1, &mut buffer)).unwrap();
+            assert_eq!((len, checksum), (2, 0x03));
+            // SAFETY: the whole buffer was initialized with `MaybeUninit::new`
+            assert_eq!(
+                unsafe { [buffer[0].assume_init(), buffer[1].assume_init()] },
+                [0x01, 0x02]
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_ack() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x3E];
+        assert_eq!(mcp2003a.read_ack(0xC1).unwrap(), 0x3E);
+
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1];
+        assert!(matches!(
+            mcp2003a.read_ack(0xC1),
+            Err(Mcp2003aError::LinReadDeviceTimeoutNoResponse)
+        ));
+    }
+
+    #[test]
+    fn test_read_raw() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0xAA, 0x02, 0xBB, 0xCC, 0xDD];
+
+        // Length-prefixed response: stop after the announced number of bytes
+        let mut received = std::vec::Vec::new();
+        let mut remaining = None;
+        let count = mcp2003a
+            .read_raw(0xC1, |byte| {
+                received.push(byte);
+                match remaining {
+                    None if received.len() == 4 => remaining = Some(byte),
+                    Some(n) => remaining = Some(n - 1),
+                    None => {}
+                }
+                remaining != Some(0)
+            })
+            .unwrap();
+        assert_eq!(count, 6);
+        assert_eq!(received, [0x55, 0xC1, 0xAA, 0x02, 0xBB, 0xCC]);
+        assert_eq!(mcp2003a.uart.tx, [0x55, 0xC1]);
+
+        // Stops when nothing more is received
+        mcp2003a.clear_rx().unwrap();
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1];
+        assert_eq!(mcp2003a.read_raw(0xC1, |_| true).unwrap(), 2);
+
+        // A break read back as a framing error is passed as a 0x00 byte
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1];
+        mcp2003a.uart.rx_framing_errors = 1;
+        let mut received = std::vec::Vec::new();
+        let count = mcp2003a
+            .read_raw(0xC1, |byte| {
+                received.push(byte);
+                true
+            })
+            .unwrap();
+        assert_eq!(count, 3);
+        assert_eq!(received, [0x00, 0x55, 0xC1]);
+
+        // Past the break byte tolerance, the framing error ends the read
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1];
+        mcp2003a.uart.rx_framing_errors = 2;
+        assert!(matches!(
+            mcp2003a.read_raw(0xC1, |_| true),
+            Err(Mcp2003aError::UartError(embedded_hal_nb::nb::Error::Other(
+                MockUartError::FrameFormat
+            )))
+        ));
+    }
+
+    #[test]
+    fn test_send_frame_inter_byte_space() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.init(LinBusConfig {
+            inter_byte_space: LinDelay::DelayMicroseconds(50),
+            ..LinBusConfig::default()
+        });
+        mcp2003a.send_frame(0x01, &[0x02, 0x03], 0x04).unwrap();
+        assert_eq!(mcp2003a.uart.tx, [0x55, 0x01, 0x02, 0x03, 0x04]);
+        // Between each of the 5 bytes
+        assert_eq!(mcp2003a.delay.delays_ns.iter().filter(|ns| **ns == 50_000).count(), 4);
+
+        #[cfg(feature = "async")]
+        {
+            mcp2003a.delay.delays_ns.clear();
+            mcp2003a.uart.tx.clear();
+            block_on(mcp2003a.send_frame_async(0x01, &[0x02, 0x03], 0x04)).unwrap();
+            assert_eq!(mcp2003a.uart.tx, [0x55, 0x01, 0x02, 0x03, 0x04]);
+            assert_eq!(mcp2003a.delay.delays_ns.iter().filter(|ns| **ns == 50_000).count(), 4);
+        }
+    }
+
+    #[cfg(feature = "last-frame")]
+    #[test]
+    fn test_last_tx_rx() {
+        let mut mcp2003a = mock_mcp2003a();
+        mcp2003a.send_frame(0x01, &[0x02, 0x03], 0x04).unwrap();
+        assert_eq!(mcp2003a.last_tx(), &[0x55, 0x01, 0x02, 0x03, 0x04]);
+        assert!(mcp2003a.last_rx().is_empty());
+
+        mcp2003a.uart.rx = std::vec![0x00, 0x55, 0xC1, 0x05, 0x06];
+        let mut buffer = [0u8; 1];
+        mcp2003a.read_frame(0xC1, &mut buffer).unwrap();
+        assert_eq!(mcp2003a.last_tx(), &[0x55, 0xC1]);
+        assert_eq!(mcp2003a.last_rx(), &[0x00, 0x55, 0xC1, 0x05, 0x06]);
     }
 }
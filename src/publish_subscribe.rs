@@ -0,0 +1,295 @@
+//! The command and feedback cycle of actuators, where the master publishes a command with a frame ID
+//! and the slave later answers a header of the same ID with its updated value.
+//!
+//! `PublishSubscribe` keeps the last published payload, so the confirmation read afterwards can be compared to it:
+//!
+//! ```rust,ignore
+//! let mut valve = PublishSubscribe::new(0xC1, 2, ChecksumMode::Enhanced)?;
+//! valve.publish(&mut mcp2003a, &[0x01, 0x40])?; // Open to 0x40
+//! // ... give the actuator time to move, e.g. other slots of the schedule ...
+//! let confirmation = valve.confirm(&mut mcp2003a)?;
+//! if !confirmation.matches_published() {
+//!     log::warn!("Valve at {:?}, commanded {:?}", confirmation.data(), valve.published());
+//! }
+//! ```
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+use embedded_hal_nb::serial::{Read as UartRead, Write as UartWrite};
+
+#[cfg(feature = "async")]
+use embedded_hal_async::delay::DelayNs as AsyncDelayNs;
+#[cfg(feature = "async")]
+use embedded_io_async::Read as AsyncUartRead;
+#[cfg(feature = "async")]
+use embedded_io_async::ReadReady as AsyncUartReadReady;
+#[cfg(feature = "async")]
+use embedded_io_async::Write as AsyncUartWrite;
+
+use crate::frame::{ChecksumMode, FrameError, MAX_DATA_LEN};
+use crate::{Mcp2003a, Mcp2003aError};
+
+/// A frame ID the master publishes a command with, then reads the confirmation of the slave from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PublishSubscribe {
+    id: u8,
+    len: usize,
+    checksum_mode: ChecksumMode,
+    published: Option<[u8; MAX_DATA_LEN]>,
+}
+
+/// The value a slave confirmed after a publish, with the payload published before it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Confirmation {
+    data: [u8; MAX_DATA_LEN],
+    published: Option<[u8; MAX_DATA_LEN]>,
+    len: usize,
+    checksum: u8,
+}
+
+impl Confirmation {
+    /// Get the data confirmed by the slave.
+    pub fn data(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+
+    /// Get the checksum received after the data.
+    pub fn checksum(&self) -> u8 {
+        self.checksum
+    }
+
+    /// Whether the slave confirmed exactly the last published payload. False if nothing was published yet.
+    pub fn matches_published(&self) -> bool {
+        self.published
+            .is_some_and(|published| published[..self.len] == self.data[..self.len])
+    }
+
+    /// Get the bits differing between the confirmed data and the last published payload, for each data byte
+    /// (bytes past the data length are zero), or `None` if nothing was published yet.
+    pub fn changed_bits(&self) -> Option<[u8; MAX_DATA_LEN]> {
+        self.published.map(|published| {
+            let mut changed = [0u8; MAX_DATA_LEN];
+            for (i, byte) in changed.iter_mut().enumerate().take(self.len) {
+                *byte = published[i] ^ self.data[i];
+            }
+            changed
+        })
+    }
+}
+
+impl PublishSubscribe {
+    /// Create the helper for the given ID, data length (1 to 8 bytes), and checksum mode of both frames.
+    /// The id must be ready to send (i.e. the PID if needed for your LIN version).
+    ///
+    /// - Note: Returns `NoData` or `DataTooLong` if the data length is not between 1 and 8 bytes.
+    pub fn new(id: u8, len: usize, checksum_mode: ChecksumMode) -> Result<Self, FrameError> {
+        match len {
+            0 => Err(FrameError::NoData),
+            len if len > MAX_DATA_LEN => Err(FrameError::DataTooLong),
+            len => Ok(PublishSubscribe {
+                id,
+                len,
+                checksum_mode,
+                published: None,
+            }),
+        }
+    }
+
+    /// Check the payload to publish has the data length of the helper.
+    fn check_len<E>(&self, data: &[u8]) -> Result<(), Mcp2003aError<E>> {
+        if data.len() != self.len {
+            return Err(Mcp2003aError::InvalidFrame(FrameError::LengthMismatch {
+                expected: self.len,
+                actual: data.len(),
+            }));
+        }
+        Ok(())
+    }
+
+    /// Get the last published payload, or `None` if nothing was published yet.
+    pub fn published(&self) -> Option<&[u8]> {
+        self.published.as_ref().map(|published| &published[..self.len])
+    }
+
+    /// Keep the published payload, once its frame was sent.
+    fn record_published(&mut self, data: &[u8]) {
+        let mut published = [0u8; MAX_DATA_LEN];
+        published[..self.len].copy_from_slice(data);
+        self.published = Some(published);
+    }
+
+    /// Build the confirmation of the data read with the given checksum.
+    fn confirmation(&self, data: [u8; MAX_DATA_LEN], checksum: u8) -> Confirmation {
+        Confirmation {
+            data,
+            published: self.published,
+            len: self.len,
+            checksum,
+        }
+    }
+
+    /// Publish the given payload, with the checksum calculated with the checksum mode, and keep it
+    /// to compare the confirmation to.
+    ///
+    /// - Note: Returns `LengthMismatch` if the data length differs from the length of the helper.
+    /// - Note: The payload is only kept once the frame was sent.
+    pub fn publish<UART, GPIO, DELAY, E>(
+        &mut self,
+        mcp2003a: &mut Mcp2003a<UART, GPIO, DELAY>,
+        data: &[u8],
+    ) -> Result<(), Mcp2003aError<E>>
+    where
+        UART: UartRead<Error = E> + UartWrite<Error = E>,
+        E: embedded_hal_nb::serial::Error,
+        GPIO: OutputPin,
+        DELAY: DelayNs,
+    {
+        self.check_len(data)?;
+        mcp2003a.send_frame(self.id, data, self.checksum_mode.get_checksum(self.id, data))?;
+        self.record_published(data);
+        Ok(())
+    }
+
+    /// Read the updated value of the slave for the same ID, validating its checksum with the checksum mode.
+    /// Returns the confirmation, to compare with the last published payload.
+    pub fn confirm<UART, GPIO, DELAY, E>(
+        &self,
+        mcp2003a: &mut Mcp2003a<UART, GPIO, DELAY>,
+    ) -> Result<Confirmation, Mcp2003aError<E>>
+    where
+        UART: UartRead<Error = E> + UartWrite<Error = E>,
+        E: embedded_hal_nb::serial::Error,
+        GPIO: OutputPin,
+        DELAY: DelayNs,
+    {
+        let mut data = [0u8; MAX_DATA_LEN];
+        let (_, checksum) = mcp2003a.read_frame_validated(self.id, &mut data[..self.len], self.checksum_mode)?;
+        Ok(self.confirmation(data, checksum))
+    }
+
+    /// Publish the given payload like `publish`.
+    /// - Note: Returns `LengthMismatch` if the data length differs from the length of the helper.
+    /// - Note: This function is async to allow for the delay and serial write to be async.
+    #[cfg(feature = "async")]
+    pub async fn publish_async<UART, GPIO, DELAY, E>(
+        &mut self,
+        mcp2003a: &mut Mcp2003a<UART, GPIO, DELAY>,
+        data: &[u8],
+    ) -> Result<(), Mcp2003aError<E>>
+    where
+        UART: AsyncUartRead<Error = E> + AsyncUartWrite<Error = E>,
+        GPIO: OutputPin,
+        DELAY: AsyncDelayNs,
+    {
+        self.check_len(data)?;
+        mcp2003a
+            .send_frame_async(self.id, data, self.checksum_mode.get_checksum(self.id, data))
+            .await?;
+        self.record_published(data);
+        Ok(())
+    }
+
+    /// Read the updated value of the slave like `confirm`.
+    /// - Note: Requires the UART to implement `ReadReady`, so the read stops once no more bytes were received.
+    /// - Note: This function is async to allow for the delay and serial read to be async.
+    #[cfg(feature = "async")]
+    pub async fn confirm_async<UART, GPIO, DELAY, E>(
+        &self,
+        mcp2003a: &mut Mcp2003a<UART, GPIO, DELAY>,
+    ) -> Result<Confirmation, Mcp2003aError<E>>
+    where
+        UART: AsyncUartRead<Error = E> + AsyncUartWrite<Error = E> + AsyncUartReadReady,
+        GPIO: OutputPin,
+        DELAY: AsyncDelayNs,
+    {
+        let mut data = [0u8; MAX_DATA_LEN];
+        let (_, checksum) = mcp2003a
+            .read_frame_validated_async(self.id, &mut data[..self.len], self.checksum_mode)
+            .await?;
+        Ok(self.confirmation(data, checksum))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    #[cfg(feature = "async")]
+    use crate::tests::block_on;
+    use crate::tests::mock_mcp2003a;
+
+    #[test]
+    fn test_publish_subscribe() {
+        let mut mcp2003a = mock_mcp2003a();
+        let mut valve = PublishSubscribe::new(0xC1, 2, ChecksumMode::Enhanced).unwrap();
+        assert_eq!(valve.published(), None);
+
+        // Confirming before publishing has nothing to compare to
+        let checksum = ChecksumMode::Enhanced.get_checksum(0xC1, &[0x01, 0x40]);
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x40, checksum];
+        let confirmation = valve.confirm(&mut mcp2003a).unwrap();
+        assert!(!confirmation.matches_published());
+        assert_eq!(confirmation.changed_bits(), None);
+
+        valve.publish(&mut mcp2003a, &[0x01, 0x40]).unwrap();
+        assert_eq!(valve.published(), Some(&[0x01, 0x40][..]));
+        assert_eq!(&mcp2003a.uart.tx[2..], &[0x55, 0xC1, 0x01, 0x40, checksum]);
+
+        // The actuator reached the commanded value
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x40, checksum];
+        let confirmation = valve.confirm(&mut mcp2003a).unwrap();
+        assert!(confirmation.matches_published());
+        assert_eq!(confirmation.changed_bits(), Some([0u8; MAX_DATA_LEN]));
+
+        // The actuator is still moving
+        let checksum = ChecksumMode::Enhanced.get_checksum(0xC1, &[0x01, 0x30]);
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x30, checksum];
+        let confirmation = valve.confirm(&mut mcp2003a).unwrap();
+        assert_eq!(confirmation.data(), [0x01, 0x30]);
+        assert_eq!(confirmation.checksum(), checksum);
+        assert!(!confirmation.matches_published());
+        assert_eq!(confirmation.changed_bits().unwrap()[..2], [0x00, 0x70]);
+
+        mcp2003a.uart.rx = std::vec![0x55, 0xC1, 0x01, 0x30, 0x00];
+        assert!(matches!(
+            valve.confirm(&mut mcp2003a),
+            Err(Mcp2003aError::LinReadInvalidChecksum(0x00))
+        ));
+
+        // A payload of another length is not published, and the last one is kept
+        mcp2003a.uart.tx.clear();
+        assert!(matches!(
+            valve.publish(&mut mcp2003a, &[0x01]),
+            Err(Mcp2003aError::InvalidFrame(FrameError::LengthMismatch {
+                expected: 2,
+                actual: 1
+            }))
+        ));
+        #[cfg(feature = "async")]
+        {
+            assert!(matches!(
+                block_on(valve.publish_async(&mut mcp2003a, &[0x01, 0x02, 0x03])),
+                Err(Mcp2003aError::InvalidFrame(FrameError::LengthMismatch {
+                    expected: 2,
+                    actual: 3
+                }))
+            ));
+        }
+        assert!(mcp2003a.uart.tx.is_empty());
+        assert_eq!(valve.published(), Some(&[0x01, 0x40][..]));
+    }
+
+    #[test]
+    fn test_publish_subscribe_new() {
+        assert_eq!(
+            PublishSubscribe::new(0xC1, 0, ChecksumMode::Enhanced),
+            Err(FrameError::NoData)
+        );
+        assert_eq!(
+            PublishSubscribe::new(0xC1, 9, ChecksumMode::Enhanced),
+            Err(FrameError::DataTooLong)
+        );
+        assert!(PublishSubscribe::new(0xC1, 8, ChecksumMode::Enhanced).is_ok());
+    }
+}
@@ -0,0 +1,255 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+use embedded_hal_nb::serial::{Read as UartRead, Write as UartWrite};
+
+#[cfg(feature = "async")]
+use embedded_hal_async::delay::DelayNs as AsyncDelayNs;
+#[cfg(feature = "async")]
+use embedded_io_async::Read as AsyncUartRead;
+#[cfg(feature = "async")]
+use embedded_io_async::ReadReady as AsyncUartReadReady;
+#[cfg(feature = "async")]
+use embedded_io_async::Write as AsyncUartWrite;
+
+use crate::config::{LinBusConfig, LinInterFrameSpace};
+#[cfg(feature = "async")]
+use crate::delay_ns_u64_async;
+use crate::frame::{FrameError, MAX_DATA_LEN};
+use crate::{delay_ns_u64, Mcp2003a, Mcp2003aError};
+
+/// A frame to run in a slot of a LIN schedule table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScheduleFrame<'a> {
+    /// The master publishes the data, like `send_frame`.
+    /// The id must be ready to send (i.e. the PID if needed for your LIN version).
+    Publish { id: u8, data: &'a [u8], checksum: u8 },
+    /// The master sends the header and a slave responds with `len` (1 to 8) data bytes, like `read_frame`.
+    /// The id must be ready to send (i.e. the PID if needed for your LIN version).
+    Subscribe { id: u8, len: usize },
+}
+
+/// A slot in a LIN schedule table: the frame to run, then how long to wait before the next slot.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScheduleSlot<'a> {
+    /// The frame to run in this slot.
+    pub frame: ScheduleFrame<'a>,
+    /// How long to wait after the frame before starting the next slot.
+    /// This is in addition to the inter-frame space applied by the frame itself.
+    pub spacing: LinInterFrameSpace,
+}
+
+/// A LIN schedule table, shared by the blocking and async schedule executors.
+///
+/// The slots run in the order of the table, which sets the priority of their frames: a frame with a tight
+/// deadline should come first in the cycle, before frames that may overrun their budget (see `slot_overruns`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Schedule<'a> {
+    /// The slots of the schedule, run in order.
+    pub slots: &'a [ScheduleSlot<'a>],
+}
+
+impl<'a> Schedule<'a> {
+    /// Create a new schedule from the given slots.
+    pub fn new(slots: &'a [ScheduleSlot<'a>]) -> Self {
+        Schedule { slots }
+    }
+
+    /// Get the worst-case time in nanoseconds of one cycle of the schedule with the given configuration,
+    /// to verify it fits the required cycle period (e.g. 10 ms). Each slot counts the bus idle before its break,
+    /// then its spacing and the inter-frame space applied by its frame, and:
+    /// - For a `Publish` frame, the nominal frame time and the inter-byte spaces.
+    /// - For a `Subscribe` frame, the nominal header time, then the response timeout or the nominal response time
+    ///   if longer, and the inter-frame space applied before the header.
+    ///
+    /// - Note: This is calculated from the configured timings, so the time the code takes between delays
+    ///   adds to it on the target.
+    pub fn cycle_time_ns(&self, config: &LinBusConfig) -> u64 {
+        let bit_period_ns = config.speed.get_bit_period_ns();
+        let inter_frame_space_ns = config.inter_frame_space.get_duration_ns();
+        self.slots
+            .iter()
+            .map(|slot| {
+                let frame_ns = match slot.frame {
+                    ScheduleFrame::Publish { data, .. } => {
+                        config.nominal_frame_time_ns(data.len())
+                            + (data.len() as u64 + 2) * config.inter_byte_space.get_duration_ns()
+                            + inter_frame_space_ns
+                    }
+                    ScheduleFrame::Subscribe { len, .. } => {
+                        let response_timeout_ns = config.read_device_response_timeout.get_duration_ns_framed(
+                            bit_period_ns,
+                            len,
+                            config.uart_framing,
+                        );
+                        let response_ns = config.nominal_frame_time_ns(len) - config.nominal_header_time_ns();
+                        config.nominal_header_time_ns()
+                            + response_timeout_ns.max(response_ns)
+                            + 2 * inter_frame_space_ns
+                    }
+                };
+                config.bus_idle_before_break.get_duration_ns() + frame_ns + slot.spacing.get_duration_ns()
+            })
+            .sum()
+    }
+}
+
+/// Check the data length of a `Subscribe` frame is 1 to 8 bytes, returning `InvalidFrame` otherwise.
+fn subscribe_len<E>(len: usize) -> Result<usize, Mcp2003aError<E>> {
+    match len {
+        0 => Err(Mcp2003aError::InvalidFrame(FrameError::NoData)),
+        len if len > MAX_DATA_LEN => Err(Mcp2003aError::InvalidFrame(FrameError::DataTooLong)),
+        len => Ok(len),
+    }
+}
+
+impl<UART, GPIO, DELAY> Mcp2003a<UART, GPIO, DELAY> {
+    /// Get the jitter in nanoseconds accumulated by the schedule executor: the sum over every slot run
+    /// of the time its frame took on the bus beyond the nominal time of the frame, e.g. inter-byte spaces
+    /// or a response timeout longer than the nominal response. The inter-frame spaces and the spacing of
+    /// the slot are not jitter, as they are part of the slot.
+    ///
+    /// - Note: This is calculated from the configured timings like `last_frame_duration_ns`, not measured,
+    ///   as there is no hardware timer available to the driver.
+    /// - Note: Slots whose frame failed are not accumulated, as their duration is unknown.
+    pub fn jitter_ns(&self) -> u64 {
+        self.schedule_jitter_ns
+    }
+
+    /// Get the number of slots run by the schedule executor whose frame overran its budget, `TFrame_Maximum`
+    /// of the LIN specification (LIN 2.2A section 2.3.2): 1.4 times the nominal time of the frame.
+    /// An overrun slot delays the slots after it, which may then miss their frames.
+    pub fn slot_overruns(&self) -> u32 {
+        self.schedule_overruns
+    }
+
+    /// Reset the jitter and the slot overruns accumulated by the schedule executor to zero.
+    pub fn reset_jitter(&mut self) {
+        self.schedule_jitter_ns = 0;
+        self.schedule_overruns = 0;
+    }
+
+    /// Accumulate the jitter of the slot that just ran its frame with the given data length, which applied
+    /// the inter-frame space the given number of times.
+    fn record_slot_jitter(&mut self, data_len: usize, inter_frame_spaces: u64) {
+        let nominal_ns = self.config.nominal_frame_time_ns(data_len);
+        let frame_ns = self
+            .last_frame_duration_ns
+            .saturating_sub(inter_frame_spaces * self.config.inter_frame_space.get_duration_ns());
+        self.schedule_jitter_ns += frame_ns.saturating_sub(nominal_ns);
+        if frame_ns > nominal_ns * 14 / 10 {
+            self.schedule_overruns += 1;
+        }
+    }
+}
+
+impl<UART, GPIO, DELAY, E> Mcp2003a<UART, GPIO, DELAY>
+where
+    UART: UartRead<Error = E> + UartWrite<Error = E>,
+    E: embedded_hal_nb::serial::Error,
+    GPIO: OutputPin,
+    DELAY: DelayNs,
+{
+    /// Run one cycle of the schedule, running each slot's frame in order then waiting its spacing.
+    ///
+    /// The result of each slot is passed to `on_slot` with the data and checksum of the frame on the bus:
+    /// - For a `Publish` frame, the published data and checksum.
+    /// - For a `Subscribe` frame, the data and checksum received from the slave.
+    ///
+    /// - Note: Errors of a slot are passed to `on_slot` and do not stop the schedule. A `Subscribe` frame with
+    ///   a length of 0 or more than 8 bytes is not read, and fails with `InvalidFrame`.
+    /// - Note: The time each frame took beyond its nominal time is accumulated in `jitter_ns`, and the frames
+    ///   overrunning their budget are counted in `slot_overruns`.
+    pub fn run_schedule(
+        &mut self,
+        schedule: &Schedule,
+        mut on_slot: impl FnMut(&ScheduleFrame, Result<(&[u8], u8), Mcp2003aError<E>>),
+    ) {
+        for slot in schedule.slots.iter() {
+            match slot.frame {
+                ScheduleFrame::Publish { id, data, checksum } => {
+                    let result = self.send_frame(id, data, checksum).map(|_| (data, checksum));
+                    if result.is_ok() {
+                        self.record_slot_jitter(data.len(), 1);
+                    }
+                    on_slot(&slot.frame, result);
+                }
+                ScheduleFrame::Subscribe { id, len } => {
+                    let mut buffer = [0u8; MAX_DATA_LEN];
+                    let result = subscribe_len(len).and_then(|len| self.read_frame(id, &mut buffer[..len]));
+                    match result {
+                        Ok(checksum) => {
+                            self.record_slot_jitter(len, 2);
+                            on_slot(&slot.frame, Ok((&buffer[..len], checksum)))
+                        }
+                        Err(e) => on_slot(&slot.frame, Err(e)),
+                    }
+                }
+            }
+
+            // Wait for the rest of the slot
+            delay_ns_u64(&mut self.delay, slot.spacing.get_duration_ns());
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<UART, GPIO, DELAY, E> Mcp2003a<UART, GPIO, DELAY>
+where
+    UART: AsyncUartRead<Error = E> + AsyncUartWrite<Error = E> + AsyncUartReadReady,
+    GPIO: OutputPin,
+    DELAY: AsyncDelayNs,
+{
+    /// Run one cycle of the schedule, running each slot's frame in order then awaiting its spacing.
+    /// With `embassy`, pass `embassy_time::Delay` as the delay so slots are timed by embassy timers.
+    ///
+    /// The result of each slot is passed to `on_slot` with the data and checksum of the frame on the bus:
+    /// - For a `Publish` frame, the published data and checksum.
+    /// - For a `Subscribe` frame, the data and checksum received from the slave.
+    ///
+    /// - Note: Errors of a slot are passed to `on_slot` and do not stop the schedule. A `Subscribe` frame with
+    ///   a length of 0 or more than 8 bytes is not read, and fails with `InvalidFrame`.
+    /// - Note: The time each frame took beyond its nominal time is accumulated in `jitter_ns`, and the frames
+    ///   overrunning their budget are counted in `slot_overruns`.
+    /// - Note: Only one cycle is run per call, so a task can stop cleanly between cycles. Dropping the
+    ///   future while it waits out a slot's spacing is also clean, as the frame of that slot is complete.
+    /// - Note: Requires the UART to implement `ReadReady`, so the reads of `Subscribe` frames stop once no more bytes
+    ///   were received.
+    /// - Note: This function is async to allow for the delay and serial read/write to be async.
+    pub async fn run_schedule_async(
+        &mut self,
+        schedule: &Schedule<'_>,
+        mut on_slot: impl FnMut(&ScheduleFrame, Result<(&[u8], u8), Mcp2003aError<E>>),
+    ) {
+        for slot in schedule.slots.iter() {
+            match slot.frame {
+                ScheduleFrame::Publish { id, data, checksum } => {
+                    let result = self
+                        .send_frame_async(id, data, checksum)
+                        .await
+                        .map(|_| (data, checksum));
+                    if result.is_ok() {
+                        self.record_slot_jitter(data.len(), 1);
+                    }
+                    on_slot(&slot.frame, result);
+                }
+                ScheduleFrame::Subscribe { id, len } => {
+                    let mut buffer = [0u8; MAX_DATA_LEN];
+                    let result = match subscribe_len(len) {
+                        Ok(len) => self.read_frame_async(id, &mut buffer[..len]).await,
+                        Err(e) => Err(e),
+                    };
+                    match result {
+                        Ok(checksum) => {
+                            self.record_slot_jitter(len, 2);
+                            on_slot(&slot.frame, Ok((&buffer[..len], checksum)))
+                        }
+                        Err(e) => on_slot(&slot.frame, Err(e)),
+                    }
+                }
+            }
+
+            // Wait for the rest of the slot
+            delay_ns_u64_async(&mut self.delay, slot.spacing.get_duration_ns()).await;
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Adapter for HALs with a blocking serial interface.
+//!
+//! The driver takes its UART through one of two sets of traits:
+//! - The blocking methods require `embedded_hal_nb::serial::{Read, Write}`, reading a byte without waiting.
+//! - The async methods (`async` feature) require `embedded_io_async::{Read, Write}`, and `ReadReady` for some of them.
+//!
+//! `embedded-hal` 1.0 has no serial traits of its own, so HALs without `embedded-hal-nb` support usually expose
+//! their UART through the blocking `embedded_io::{Read, Write}` traits. Wrap such a UART in a `BlockingSerial`
+//! to use it with the blocking methods. It also needs `embedded_io::ReadReady`, so a read of the
+//! driver never waits for a byte that is not coming:
+//!
+//! ```rust,ignore
+//! let mut mcp2003a = Mcp2003a::new(BlockingSerial::new(uart), break_pin, delay);
+//! ```
+
+use embedded_hal_nb::nb;
+use embedded_hal_nb::serial::{Error, ErrorKind, ErrorType, Read, Write};
+use embedded_io::{Read as IoRead, ReadReady as IoReadReady, Write as IoWrite};
+
+/// Error of the UART wrapped in a `BlockingSerial`.
+/// - Note: `embedded-io` errors have no framing error kind, so all errors are of the `Other` kind.
+#[derive(Debug)]
+pub struct BlockingSerialError<E>(pub E);
+
+impl<E: core::fmt::Debug> Error for BlockingSerialError<E> {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+/// Adapter implementing the `embedded-hal-nb` serial traits for a UART with the blocking `embedded-io` traits.
+pub struct BlockingSerial<T> {
+    inner: T,
+}
+
+impl<T> BlockingSerial<T> {
+    /// Wrap the given UART.
+    pub fn new(inner: T) -> Self {
+        BlockingSerial { inner }
+    }
+
+    /// Get the wrapped UART, e.g. to change its baud rate from a `BaudSwitcher`.
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Release the wrapped UART.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: embedded_io::ErrorType> ErrorType for BlockingSerial<T> {
+    type Error = BlockingSerialError<T::Error>;
+}
+
+impl<T: IoRead + IoReadReady> Read for BlockingSerial<T> {
+    /// Read a byte if one was received, else return `WouldBlock` instead of waiting for one.
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        if !self
+            .inner
+            .read_ready()
+            .map_err(|e| nb::Error::Other(BlockingSerialError(e)))?
+        {
+            return Err(nb::Error::WouldBlock);
+        }
+        let mut byte = [0u8; 1];
+        match self.inner.read(&mut byte) {
+            Ok(0) => Err(nb::Error::WouldBlock),
+            Ok(_) => Ok(byte[0]),
+            Err(e) => Err(nb::Error::Other(BlockingSerialError(e))),
+        }
+    }
+}
+
+impl<T: IoWrite> Write for BlockingSerial<T> {
+    fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+        self.inner
+            .write_all(&[word])
+            .map_err(|e| nb::Error::Other(BlockingSerialError(e)))
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        self.inner.flush().map_err(|e| nb::Error::Other(BlockingSerialError(e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::vec::Vec;
+
+    struct IoUart {
+        tx: Vec<u8>,
+        rx: Vec<u8>,
+    }
+
+    impl embedded_io::ErrorType for IoUart {
+        type Error = embedded_io::ErrorKind;
+    }
+
+    impl IoRead for IoUart {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            let len = buf.len().min(self.rx.len());
+            for (i, byte) in self.rx.drain(..len).enumerate() {
+                buf[i] = byte;
+            }
+            Ok(len)
+        }
+    }
+
+    impl IoReadReady for IoUart {
+        fn read_ready(&mut self) -> Result<bool, Self::Error> {
+            Ok(!self.rx.is_empty())
+        }
+    }
+
+    impl IoWrite for IoUart {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.tx.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_blocking_serial() {
+        let mut serial = BlockingSerial::new(IoUart {
+            tx: Vec::new(),
+            rx: std::vec![0x55, 0xC1],
+        });
+        serial.write(0x80).unwrap();
+        serial.flush().unwrap();
+        assert_eq!(serial.read().unwrap(), 0x55);
+        assert_eq!(serial.read().unwrap(), 0xC1);
+        assert!(matches!(serial.read(), Err(nb::Error::WouldBlock)));
+        assert_eq!(serial.into_inner().tx, [0x80]);
+    }
+}
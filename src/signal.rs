@@ -0,0 +1,208 @@
+//! Packing and unpacking of LIN signals in the data bytes of a frame.
+//!
+//! A LIN signal is a bit field of 1 to 32 bits in the frame data. Bit `n` of the data is bit `n % 8`
+//! of byte `n / 8`. Signals can be packed with either byte order, as described by the LDF:
+//! - `ByteOrder::Intel` (LSB first, the LIN standard): the start bit is the least significant bit of the
+//!   signal, and the signal continues towards higher bits, then into the next byte.
+//! - `ByteOrder::Motorola` (MSB first, common when bridging to CAN): the start bit is the most significant bit
+//!   of the signal, and the signal continues towards lower bits, then to bit 7 of the next byte.
+//!
+//! ```rust,ignore
+//! let mut data = [0u8; 8];
+//! pack_signal(&mut data, 4, 12, 0xABC, ByteOrder::Intel)?; // 12-bit signal spanning bytes 0 and 1
+//! assert_eq!(unpack_signal(&data, 4, 12, ByteOrder::Intel)?, 0xABC);
+//! ```
+
+/// Bit ordering of a signal in the frame data.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ByteOrder {
+    /// Little-endian, least significant bit first (LIN standard).
+    Intel,
+    /// Big-endian, most significant bit first.
+    Motorola,
+}
+
+/// Errors when packing or unpacking a signal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SignalError {
+    /// The signal length must be between 1 and 32 bits.
+    InvalidLength(usize),
+    /// The signal does not fit in the buffer from its start bit.
+    OutOfBounds,
+}
+
+/// Validate the signal fits in the buffer and get the position of its least significant bit.
+fn signal_lsb_position(
+    buf_len: usize,
+    start_bit: usize,
+    len_bits: usize,
+    order: ByteOrder,
+) -> Result<usize, SignalError> {
+    if !(1..=32).contains(&len_bits) {
+        return Err(SignalError::InvalidLength(len_bits));
+    }
+
+    let (lsb, last_bit) = match order {
+        ByteOrder::Intel => (start_bit, start_bit + len_bits - 1),
+        ByteOrder::Motorola => {
+            // The least significant bit is the last bit of a Motorola signal
+            let mut pos = start_bit;
+            for _ in 1..len_bits {
+                pos = if pos % 8 == 0 {
+                    // Continue at bit 7 of the next byte
+                    pos + 15
+                } else {
+                    pos - 1
+                };
+            }
+            (pos, pos)
+        }
+    };
+    if start_bit >= buf_len * 8 || last_bit >= buf_len * 8 {
+        return Err(SignalError::OutOfBounds);
+    }
+    Ok(lsb)
+}
+
+/// Get the position of the next more significant bit of a signal.
+fn more_significant_bit(pos: usize, order: ByteOrder) -> usize {
+    match order {
+        ByteOrder::Intel => pos + 1,
+        // Continue at bit 0 of the previous byte
+        ByteOrder::Motorola if pos % 8 == 7 => pos - 15,
+        ByteOrder::Motorola => pos + 1,
+    }
+}
+
+/// Pack the lower `len_bits` bits of `value` into `buf`, starting at bit `start_bit` with the given byte order.
+/// Other bits of the buffer are left unchanged.
+pub fn pack_signal(
+    buf: &mut [u8],
+    start_bit: usize,
+    len_bits: usize,
+    value: u32,
+    order: ByteOrder,
+) -> Result<(), SignalError> {
+    let mut bit = signal_lsb_position(buf.len(), start_bit, len_bits, order)?;
+    for i in 0..len_bits {
+        if i > 0 {
+            bit = more_significant_bit(bit, order);
+        }
+        let mask = 1 << (bit % 8);
+        if (value >> i) & 1 == 1 {
+            buf[bit / 8] |= mask;
+        } else {
+            buf[bit / 8] &= !mask;
+        }
+    }
+    Ok(())
+}
+
+/// Unpack the `len_bits` bits signal starting at bit `start_bit` of `buf` with the given byte order.
+pub fn unpack_signal(buf: &[u8], start_bit: usize, len_bits: usize, order: ByteOrder) -> Result<u32, SignalError> {
+    let mut bit = signal_lsb_position(buf.len(), start_bit, len_bits, order)?;
+    let mut value = 0;
+    for i in 0..len_bits {
+        if i > 0 {
+            bit = more_significant_bit(bit, order);
+        }
+        value |= (((buf[bit / 8] >> (bit % 8)) & 1) as u32) << i;
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_signal_in_byte() {
+        let mut data = [0u8; 2];
+        pack_signal(&mut data, 2, 3, 0b101, ByteOrder::Intel).unwrap();
+        assert_eq!(data, [0b0001_0100, 0x00]);
+        assert_eq!(unpack_signal(&data, 2, 3, ByteOrder::Intel), Ok(0b101));
+    }
+
+    #[test]
+    fn test_pack_signal_spanning_bytes() {
+        let mut data = [0u8; 8];
+        pack_signal(&mut data, 4, 12, 0xABC, ByteOrder::Intel).unwrap();
+        assert_eq!(&data[..2], &[0xC0, 0xAB]);
+        assert_eq!(unpack_signal(&data, 4, 12, ByteOrder::Intel), Ok(0xABC));
+    }
+
+    #[test]
+    fn test_pack_signal_keeps_other_bits() {
+        let mut data = [0xFF; 2];
+        pack_signal(&mut data, 6, 4, 0b0000, ByteOrder::Intel).unwrap();
+        assert_eq!(data, [0x3F, 0xFC]);
+    }
+
+    #[test]
+    fn test_pack_signal_truncates_value() {
+        let mut data = [0u8; 1];
+        pack_signal(&mut data, 0, 4, 0xFF, ByteOrder::Intel).unwrap();
+        assert_eq!(data, [0x0F]);
+    }
+
+    #[test]
+    fn test_pack_signal_boundaries() {
+        let mut data = [0u8; 8];
+        pack_signal(&mut data, 0, 32, 0xDEADBEEF, ByteOrder::Intel).unwrap();
+        assert_eq!(&data[..4], &[0xEF, 0xBE, 0xAD, 0xDE]);
+        assert_eq!(unpack_signal(&data, 0, 32, ByteOrder::Intel), Ok(0xDEADBEEF));
+
+        pack_signal(&mut data, 63, 1, 1, ByteOrder::Intel).unwrap();
+        assert_eq!(data[7], 0x80);
+        assert_eq!(unpack_signal(&data, 63, 1, ByteOrder::Intel), Ok(1));
+    }
+
+    #[test]
+    fn test_pack_signal_motorola() {
+        let mut data = [0u8; 2];
+        pack_signal(&mut data, 7, 16, 0x1234, ByteOrder::Motorola).unwrap();
+        assert_eq!(data, [0x12, 0x34]);
+        assert_eq!(unpack_signal(&data, 7, 16, ByteOrder::Motorola), Ok(0x1234));
+    }
+
+    #[test]
+    fn test_pack_signal_intel_vs_motorola() {
+        // 0xAB across the byte boundary: Motorola puts the high nibble in the first byte
+        let mut data = [0u8; 2];
+        pack_signal(&mut data, 3, 8, 0xAB, ByteOrder::Motorola).unwrap();
+        assert_eq!(data, [0x0A, 0xB0]);
+        assert_eq!(unpack_signal(&data, 3, 8, ByteOrder::Motorola), Ok(0xAB));
+
+        // Intel puts the low nibble in the first byte
+        let mut data = [0u8; 2];
+        pack_signal(&mut data, 4, 8, 0xAB, ByteOrder::Intel).unwrap();
+        assert_eq!(data, [0xB0, 0x0A]);
+        assert_eq!(unpack_signal(&data, 4, 8, ByteOrder::Intel), Ok(0xAB));
+    }
+
+    #[test]
+    fn test_pack_signal_errors() {
+        let mut data = [0u8; 1];
+        assert_eq!(
+            pack_signal(&mut data, 4, 5, 0, ByteOrder::Intel),
+            Err(SignalError::OutOfBounds)
+        );
+        assert_eq!(
+            pack_signal(&mut data, 3, 5, 0, ByteOrder::Motorola),
+            Err(SignalError::OutOfBounds)
+        );
+        assert_eq!(
+            pack_signal(&mut data, 0, 0, 0, ByteOrder::Intel),
+            Err(SignalError::InvalidLength(0))
+        );
+        assert_eq!(
+            unpack_signal(&data, 0, 33, ByteOrder::Intel),
+            Err(SignalError::InvalidLength(33))
+        );
+        assert_eq!(
+            unpack_signal(&data, 8, 1, ByteOrder::Intel),
+            Err(SignalError::OutOfBounds)
+        );
+        assert_eq!(unpack_signal(&data, 7, 8, ByteOrder::Motorola), Ok(0));
+    }
+}
@@ -0,0 +1,100 @@
+//! Typestate construction of the driver, checking at compile time that it is configured before use.
+//!
+//! `Mcp2003a::new` starts with the default `LinBusConfig`, and only finds out at runtime with `NotInitialized`
+//! errors that `init` was not called with the timings of your bus. An `Uninitialized` driver has no frame methods,
+//! and its `init` consumes it to return the `Ready` driver, so skipping the configuration fails to compile:
+//!
+//! ```rust,ignore
+//! let mcp2003a = Uninitialized::new(uart2_driver, break_pin_driver, delay);
+//! // mcp2003a.send_frame(0x01, &[0x02], 0x03); // Does not compile
+//! let mut mcp2003a = mcp2003a.init(lin_bus_config);
+//! mcp2003a.send_frame(0x01, &[0x02], 0x03)?;
+//! ```
+//!
+//! - Note: `Mcp2003a::new` is only available with the `unchecked-init` feature (enabled by default).
+//!   Disable default features to only allow the typestate construction.
+
+use crate::config::LinBusConfig;
+use crate::Mcp2003a;
+
+/// A MCP2003A transceiver driver that is ready to send and read frames, returned by `Uninitialized::init`.
+pub type Ready<UART, GPIO, DELAY> = Mcp2003a<UART, GPIO, DELAY>;
+
+/// A MCP2003A transceiver driver that was not configured yet, and so cannot send or read frames.
+pub struct Uninitialized<UART, GPIO, DELAY> {
+    uart: UART,
+    break_pin: GPIO,
+    wakeup_pin: Option<GPIO>,
+    enable_pin: Option<GPIO>,
+    delay: DELAY,
+}
+
+impl<UART, GPIO, DELAY> Uninitialized<UART, GPIO, DELAY> {
+    /// Create a new MCP2003A transceiver instance, to be configured with `init`.
+    ///
+    /// # Arguments
+    ///
+    /// * `uart` - UART interface for data communication to and from the transceiver.
+    /// * `break_pin` - GPIO pin for the break signal.
+    /// * `delay` - Delay implementation for break signal timing.
+    pub fn new(uart: UART, break_pin: GPIO, delay: DELAY) -> Self {
+        Uninitialized {
+            uart,
+            break_pin,
+            wakeup_pin: None,
+            enable_pin: None,
+            delay,
+        }
+    }
+
+    /// Create a new MCP2003A transceiver instance, to be configured with `init`, for boards where the wakeup
+    /// signal is driven by a dedicated control line instead of the break pin.
+    ///
+    /// # Arguments
+    ///
+    /// * `uart` - UART interface for data communication to and from the transceiver.
+    /// * `break_pin` - GPIO pin for the break signal.
+    /// * `wakeup_pin` - GPIO pin for the wakeup signal, or `None` to use the break pin.
+    /// * `delay` - Delay implementation for break signal timing.
+    pub fn new_with_wakeup_pin(uart: UART, break_pin: GPIO, wakeup_pin: Option<GPIO>, delay: DELAY) -> Self {
+        Uninitialized {
+            uart,
+            break_pin,
+            wakeup_pin,
+            enable_pin: None,
+            delay,
+        }
+    }
+
+    /// Create a new MCP2003A transceiver instance, to be configured with `init`, for boards gating
+    /// the transceiver with an enable pin, e.g. to power it down between bursts of frames.
+    ///
+    /// # Arguments
+    ///
+    /// * `uart` - UART interface for data communication to and from the transceiver.
+    /// * `break_pin` - GPIO pin for the break signal.
+    /// * `enable_pin` - GPIO pin enabling the transceiver when high.
+    /// * `delay` - Delay implementation for break signal timing.
+    pub fn new_with_enable(uart: UART, break_pin: GPIO, enable_pin: GPIO, delay: DELAY) -> Self {
+        Uninitialized {
+            uart,
+            break_pin,
+            wakeup_pin: None,
+            enable_pin: Some(enable_pin),
+            delay,
+        }
+    }
+
+    /// Initialize the MCP2003A transceiver with the given LIN bus configuration, returning the ready driver.
+    pub fn init(self, config: LinBusConfig) -> Ready<UART, GPIO, DELAY> {
+        Mcp2003a::from_parts(
+            self.uart,
+            self.break_pin,
+            self.wakeup_pin,
+            self.enable_pin,
+            self.delay,
+            config,
+            true,
+        )
+    }
+}